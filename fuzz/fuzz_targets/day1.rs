@@ -0,0 +1,10 @@
+#![no_main]
+
+use advent_of_code_2021::day1_sonar_sweep::Day1;
+use advent_of_code_2021::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// malformed input should come back as a `ParseError`, never as a panic
+fuzz_target!(|input: &str| {
+    let _ = Day1::parse(input);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use advent_of_code_2021::day17_trick_shot::Day17;
+use advent_of_code_2021::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// malformed input should come back as a `ParseError`, never as a panic
+fuzz_target!(|input: &str| {
+    let _ = Day17::parse(input);
+});
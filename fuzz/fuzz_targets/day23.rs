@@ -0,0 +1,13 @@
+#![no_main]
+
+use advent_of_code_2021::day23_amphipod::Day23;
+use advent_of_code_2021::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// malformed input should come back as a `ParseError`, never as a panic, and so should input that parses but
+// can't be solved
+fuzz_target!(|input: &str| {
+    if let Ok(mut parsed) = Day23::parse(input) {
+        let _ = Day23::prepare(&mut parsed);
+    }
+});
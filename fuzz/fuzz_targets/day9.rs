@@ -0,0 +1,13 @@
+#![no_main]
+
+use advent_of_code_2021::day9_low_points::Day9;
+use advent_of_code_2021::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// malformed input should come back as a `ParseError`, never as a panic, and so should input that parses but
+// can't be solved
+fuzz_target!(|input: &str| {
+    if let Ok(mut parsed) = Day9::parse(input) {
+        let _ = Day9::prepare(&mut parsed);
+    }
+});
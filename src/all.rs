@@ -0,0 +1,106 @@
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
+use serde_json::json;
+use advent_of_code_2021::solution::Answers;
+use crate::input::InputSource;
+use crate::output::{self, Format};
+use crate::{read_and_solve, Failure, LAST_DAY};
+
+const ANSWER_WIDTH: usize = 18;
+
+pub(crate) struct DayResult {
+    pub(crate) day: usize,
+    pub(crate) outcome: Result<Answers, Failure>,
+    pub(crate) elapsed: Duration,
+}
+
+pub(crate) fn run(parallel: bool, format: Format) {
+    let start = Instant::now();
+    let days = 1..=LAST_DAY;
+    let results: Vec<DayResult> = if parallel {
+        days.into_par_iter().map(run_day).collect()
+    } else {
+        days.map(run_day).collect()
+    };
+    let wall_clock = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!();
+            print_summary(&results);
+            println!("wall clock: {}{}", format_duration(wall_clock), if parallel { " (parallel)" } else { "" });
+        }
+        Format::Json => print_json(&results, wall_clock, parallel),
+    }
+
+    if results.iter().any(|r| r.outcome.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+pub(crate) fn run_day(day: usize) -> DayResult {
+    let start = Instant::now();
+    let outcome = read_and_solve(day, &InputSource::from_arg(None, day));
+    DayResult {
+        day,
+        outcome,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn print_summary(results: &[DayResult]) {
+    println!("{:>3} | {:<w$} | {:<w$} | {:>10} | {:>10} | {:>10} | {:>10} | {:>10}",
+             "day", "part 1", "part 2", "parse", "prepare", "part 1", "part 2", "total", w = ANSWER_WIDTH);
+    println!("{}", "-".repeat(3 + 2 * (ANSWER_WIDTH + 3) + 5 * 13));
+    for result in results {
+        match &result.outcome {
+            Ok(answers) => println!("{:>3} | {:<w$} | {:<w$} | {:>10} | {:>10} | {:>10} | {:>10} | {:>10}",
+                                                 result.day,
+                                                 fit_answer(&answers.part1),
+                                                 fit_answer(&answers.part2),
+                                                 format_duration(answers.timings.parse),
+                                                 format_duration(answers.timings.prepare),
+                                                 format_duration(answers.timings.part1),
+                                                 format_duration(answers.timings.part2),
+                                                 format_duration(result.elapsed),
+                                                 w = ANSWER_WIDTH),
+            Err(e) => println!("{:>3} | failed: {}", result.day, e),
+        }
+    }
+    let total: Duration = results.iter().map(|r| r.elapsed).sum();
+    println!("{}", "-".repeat(3 + 2 * (ANSWER_WIDTH + 3) + 5 * 13));
+    println!("{:<w$} | {:>10}", "total", format_duration(total), w = 3 + 2 * (ANSWER_WIDTH + 3) + 4 * 13);
+}
+
+fn print_json(results: &[DayResult], wall_clock: Duration, parallel: bool) {
+    let days: Vec<_> = results.iter()
+        .map(|result| {
+            let metadata = json!({ "input": InputSource::from_arg(None, result.day).to_string() });
+            output::day_json(result.day, &result.outcome, metadata)
+        })
+        .collect();
+    let total: Duration = results.iter().map(|r| r.elapsed).sum();
+    println!("{}", json!({
+        "days": days,
+        "duration_ms": output::millis(total),
+        "metadata": {
+            "wall_clock_ms": output::millis(wall_clock),
+            "parallel": parallel,
+        },
+    }));
+}
+
+// multi-line answers (such as letters drawn on a grid) don't fit in a table cell
+fn fit_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("[{} lines]", answer.lines().count())
+    } else if answer.chars().count() > ANSWER_WIDTH {
+        answer.chars().take(ANSWER_WIDTH - 1).chain(std::iter::once('…')).collect()
+    } else {
+        answer.to_string()
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,77 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+use advent_of_code_2021::animation::{self, Recorder};
+use advent_of_code_2021::grid::Grid;
+use advent_of_code_2021::info;
+use advent_of_code_2021::picture::{Picture, Rgb};
+use crate::input::InputSource;
+use crate::{exit_with_usage, take_option, LAST_DAY};
+
+const DEFAULT_DELAY_MS: u64 = 100;
+
+/// Records a day's simulation: `animate day [input] [--out file] [--frames dir] [--every n] [--scale n] [--delay ms]`.
+/// It becomes a GIF at `--out` (by default `day<N>.gif`), or with `--frames` numbered PPMs in that directory.
+pub(crate) fn run(mut args: Vec<String>) {
+    let number = |name: &str, default: u64, args: &mut Vec<String>| take_option(args, name)
+        .map(|value| match value.parse() {
+            Ok(value) if value > 0 => value,
+            _ => exit_with_usage(format!("{} should be a positive number", name)),
+        })
+        .unwrap_or(default);
+    let every = number("--every", 1, &mut args) as usize;
+    let scale = number("--scale", 1, &mut args) as usize;
+    let delay = Duration::from_millis(number("--delay", DEFAULT_DELAY_MS, &mut args));
+    let out = take_option(&mut args, "--out");
+    let frames_dir = take_option(&mut args, "--frames");
+    if out.is_some() && frames_dir.is_some() {
+        exit_with_usage("animate writes either a GIF with --out or frames with --frames");
+    }
+    let mut args = args.drain(..);
+    let day = match args.next().map(|day| day.parse()) {
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+        _ => exit_with_usage(format!("animate needs a day between 1 and {}", LAST_DAY)),
+    };
+
+    let input = match InputSource::from_arg(args.next(), day).read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not read input for day {}: {}", day, e);
+            std::process::exit(1);
+        }
+    };
+    let mut recorder = Recorder::new(every);
+    match animation::for_day(day, &input, &mut recorder) {
+        Some(Ok(())) => {}
+        Some(Err(e)) => {
+            eprintln!("could not parse input: {}", e);
+            std::process::exit(1);
+        }
+        None => exit_with_usage(format!("day {} has nothing to animate", day)),
+    }
+
+    let written = match frames_dir {
+        Some(dir) => write_frames(Path::new(&dir), day, recorder.frames(), scale).map(|()| dir),
+        None => {
+            let out = out.unwrap_or_else(|| format!("day{}.gif", day));
+            animation::gif(recorder.frames(), scale, delay).and_then(|bytes| fs::write(&out, bytes)).map(|()| out)
+        }
+    };
+    match written {
+        Ok(path) => info!("wrote {} frames to {}", recorder.frames().len(), path),
+        Err(e) => {
+            eprintln!("could not write the animation: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn write_frames(dir: &Path, day: usize, frames: &[Grid<Rgb>], scale: usize) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("day{}_{:04}.ppm", day, i));
+        fs::write(path, Picture::Pixmap(frame.clone()).encode(scale))?;
+    }
+    Ok(())
+}
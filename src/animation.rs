@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+use crate::day11_dumbo_octopus::Day11;
+use crate::day20_trench_map::Day20;
+use crate::day25_sea_cucumber::Day25;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::picture::{upscale, Rgb};
+use crate::solution::Solution;
+
+/// Collects the frames of a step-based simulation, keeping one step in every `every`.
+pub struct Recorder {
+    every: usize,
+    steps: usize,
+    frames: Vec<Grid<Rgb>>,
+}
+
+impl Recorder {
+    pub fn new(every: usize) -> Self {
+        assert!(every > 0, "a recorder has to keep some steps");
+        Self {
+            every,
+            steps: 0,
+            frames: vec![],
+        }
+    }
+
+    /// Called once per step, starting with the initial state; `frame` is only drawn for the steps that are kept.
+    /// Every frame has to be as large as the first.
+    pub fn record(&mut self, frame: impl FnOnce() -> Grid<Rgb>) {
+        if self.steps.is_multiple_of(self.every) {
+            let frame = frame();
+            if let Some(first) = self.frames.first() {
+                assert_eq!((frame.width(), frame.height()), (first.width(), first.height()), "frames differ in size");
+            }
+            self.frames.push(frame);
+        }
+        self.steps += 1;
+    }
+
+    pub fn frames(&self) -> &[Grid<Rgb>] {
+        &self.frames
+    }
+}
+
+/// A [`Solution`] that simulates step by step, and can record each step as a frame.
+pub trait Animate: Solution {
+    fn animate(input: &Self::Input<'_>, recorder: &mut Recorder);
+}
+
+/// Parses and prepares `input` as the given day's and records its simulation, or `None` if the day has nothing to animate.
+pub fn for_day(day: usize, input: &str, recorder: &mut Recorder) -> Option<Result<(), ParseError>> {
+    fn animate<S: Animate>(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        let mut input = S::parse(input)?;
+        S::prepare(&mut input)?;
+        S::animate(&input, recorder);
+        Ok(())
+    }
+
+    Some(match day {
+        11 => animate::<Day11>(input, recorder),
+        20 => animate::<Day20>(input, recorder),
+        25 => animate::<Day25>(input, recorder),
+        _ => return None,
+    })
+}
+
+/// The frames as a looping GIF, showing each for `delay` with every cell drawn `scale` pixels wide.
+/// Frames with more than 256 colours between them are reduced to a fixed palette, and frames wider or taller than a
+/// GIF can hold are an error.
+pub fn gif(frames: &[Grid<Rgb>], scale: usize, delay: Duration) -> io::Result<Vec<u8>> {
+    let palette = Palette::new(frames);
+    let (width, height) = frames.first().map_or((0, 0), |frame| (frame.width() * scale, frame.height() * scale));
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                  format!("a GIF is at most {} pixels wide and high, not {} by {}", u16::MAX, width, height)));
+    };
+    let delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+    let mut bytes = b"GIF89a".to_vec();
+    bytes.extend(width.to_le_bytes());
+    bytes.extend(height.to_le_bytes());
+    // a global colour table of 2^bits entries, with 8 bits per primary
+    bytes.extend([0xf0 | (palette.bits - 1), 0, 0]);
+    for i in 0..1 << palette.bits {
+        let Rgb(r, g, b) = palette.colors.get(i).copied().unwrap_or(Rgb::BLACK);
+        bytes.extend([r, g, b]);
+    }
+    // loop forever
+    bytes.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for frame in frames {
+        bytes.extend([0x21, 0xf9, 0x04, 0x00]);
+        bytes.extend(delay.to_le_bytes());
+        bytes.extend([0x00, 0x00]);
+
+        bytes.push(0x2c);
+        bytes.extend([0, 0, 0, 0]);
+        bytes.extend(width.to_le_bytes());
+        bytes.extend(height.to_le_bytes());
+        bytes.push(0x00);
+
+        let min_code_size = palette.bits.max(2);
+        let indices: Vec<u8> = upscale(frame, scale).values().map(|&color| palette.index(color)).collect();
+        bytes.push(min_code_size);
+        for block in lzw(&indices, min_code_size).chunks(255) {
+            bytes.push(block.len() as u8);
+            bytes.extend(block);
+        }
+        bytes.push(0x00);
+    }
+    bytes.push(0x3b);
+    Ok(bytes)
+}
+
+struct Palette {
+    colors: Vec<Rgb>,
+    indices: Option<HashMap<Rgb, u8>>,
+    bits: u8,
+}
+
+impl Palette {
+    fn new(frames: &[Grid<Rgb>]) -> Self {
+        let mut colors: Vec<Rgb> = vec![];
+        let mut indices: HashMap<Rgb, u8> = HashMap::new();
+        for &color in frames.iter().flat_map(|frame| frame.values()) {
+            if indices.contains_key(&color) {
+                continue;
+            }
+            if colors.len() == 256 {
+                // 3 bits of red and green and 2 of blue
+                let level = |value: u16, max: u16| (value * 255 / max) as u8;
+                let colors = (0..=255u16)
+                    .map(|i| Rgb(level(i >> 5, 7), level(i >> 2 & 7, 7), level(i & 3, 3)))
+                    .collect();
+                return Self { colors, indices: None, bits: 8 };
+            }
+            indices.insert(color, colors.len() as u8);
+            colors.push(color);
+        }
+        let bits = (1..=8).find(|bits| colors.len() <= 1 << bits).unwrap();
+        Self { colors, indices: Some(indices), bits }
+    }
+
+    fn index(&self, color: Rgb) -> u8 {
+        match &self.indices {
+            Some(indices) => indices[&color],
+            None => (color.0 & 0xe0) | (color.1 >> 3 & 0x1c) | color.2 >> 6,
+        }
+    }
+}
+
+const MAX_CODES: u16 = 1 << 12;
+
+/// Variable-length LZW as GIF uses it, with codes packed least significant bit first.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter::default();
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut code_size = min_code_size + 1;
+    out.write(clear, code_size);
+
+    let mut indices = indices.iter();
+    let mut prefix = match indices.next() {
+        Some(&index) => index as u16,
+        None => {
+            out.write(end, code_size);
+            return out.finish();
+        }
+    };
+    for &index in indices {
+        if let Some(&code) = codes.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        out.write(prefix, code_size);
+        if next < MAX_CODES {
+            codes.insert((prefix, index), next);
+            next += 1;
+            // the decoder adds each code one step later, so it widens once `next` is past the current width
+            if next > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            out.write(clear, code_size);
+            codes.clear();
+            next = end + 1;
+            code_size = min_code_size + 1;
+        }
+        prefix = index as u16;
+    }
+    out.write(prefix, code_size);
+    if next == 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    out.write(end, code_size);
+    out.finish()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder_keeps_every_nth_step() {
+        let mut recorder = Recorder::new(2);
+        let mut drawn = 0;
+        for step in 0..5 {
+            recorder.record(|| {
+                drawn += 1;
+                Grid::new(1, 1, Rgb(step, 0, 0))
+            });
+        }
+        assert_eq!(drawn, 3);
+        assert_eq!(recorder.frames().iter().map(|frame| frame.values().next().unwrap().0).collect::<Vec<_>>(), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn lzw_matches_a_known_encoding() {
+        // the 10x10 red/blue/white sample image from the GIF89a walkthrough at giflib.sourceforge.net/whatsinagif
+        let rows = ["1111122222", "1111122222", "1111122222", "1110000222", "1110000222",
+            "2220000111", "2220000111", "2222211111", "2222211111", "2222211111"];
+        let indices: Vec<u8> = rows.concat().bytes().map(|b| b - b'0').collect();
+        assert_eq!(lzw(&indices, 2), vec![
+            0x8c, 0x2d, 0x99, 0x87, 0x2a, 0x1c, 0xdc, 0x33, 0xa0, 0x02, 0x75, 0xec, 0x95, 0xfa, 0xa8, 0xde, 0x60, 0x8c, 0x04, 0x91, 0x4c, 0x01,
+        ]);
+    }
+
+    #[test]
+    fn too_many_colors_fall_back_to_a_fixed_palette() {
+        let few = Palette::new(&[Grid::from_fn(5, 1, |pos| Rgb(pos.x as u8, 0, 0))]);
+        assert_eq!((few.bits, few.index(Rgb(3, 0, 0))), (3, 3));
+        let many = Palette::new(&[Grid::from_fn(300, 1, |pos| Rgb((pos.x / 2) as u8, pos.x as u8, 7))]);
+        assert_eq!(many.bits, 8);
+        assert_eq!(many.colors[many.index(Rgb(255, 255, 255)) as usize], Rgb::WHITE);
+        assert_eq!(many.colors[many.index(Rgb(130, 20, 200)) as usize], Rgb(145, 0, 255));
+    }
+
+    #[test]
+    fn gif_has_a_frame_per_grid() {
+        let frames = vec![Grid::new(2, 2, Rgb::BLACK), Grid::new(2, 2, Rgb::WHITE)];
+        let bytes = gif(&frames, 3, Duration::from_millis(50)).unwrap();
+        assert_eq!(&bytes[..10], b"GIF89a\x06\x00\x06\x00");
+        assert_eq!(bytes.iter().filter(|&&b| b == 0x2c).count(), 2);
+        assert_eq!(bytes.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn gif_refuses_frames_too_wide_for_it() {
+        let frames = vec![Grid::new(2, 1, Rgb::BLACK)];
+        assert_eq!(gif(&frames, 32768, Duration::from_millis(50)).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
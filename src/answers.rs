@@ -0,0 +1,167 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use toml::value::Table;
+use toml::Value;
+use advent_of_code_2021::client::Verdict;
+
+/// The known answers for one day's input, read from `answers/day{N}.toml`.
+/// Either part may be missing, e.g. while a day is still being solved.
+#[derive(Default)]
+pub(crate) struct ExpectedAnswers {
+    pub(crate) part1: Option<String>,
+    pub(crate) part2: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) enum AnswersError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    InvalidValue(&'static str, &'static str),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io(e) => write!(f, "{}", e),
+            AnswersError::Toml(e) => write!(f, "{}", e),
+            AnswersError::InvalidValue(key, expected) => write!(f, "{} should be {}", key, expected),
+        }
+    }
+}
+
+pub(crate) fn default_path(day: usize) -> PathBuf {
+    PathBuf::from(format!("answers/day{}.toml", day))
+}
+
+impl ExpectedAnswers {
+    pub(crate) fn load(day: usize) -> Result<Self, AnswersError> {
+        let file = AnswersFile::load(day)?;
+        Ok(Self {
+            part1: file.answer(1)?,
+            part2: file.answer(2)?,
+        })
+    }
+}
+
+/// A day's answers file as a whole, for keeping what submitting answers taught. Next to `part1` and `part2` it lists
+/// the answers the site turned down, as `part1_wrong`, and the tightest bounds it gave, as `part1_too_high` and
+/// `part1_too_low`.
+pub(crate) struct AnswersFile {
+    day: usize,
+    table: Table,
+}
+
+struct Keys {
+    answer: &'static str,
+    wrong: &'static str,
+    too_high: &'static str,
+    too_low: &'static str,
+}
+
+const KEYS: [Keys; 2] = [
+    Keys { answer: "part1", wrong: "part1_wrong", too_high: "part1_too_high", too_low: "part1_too_low" },
+    Keys { answer: "part2", wrong: "part2_wrong", too_high: "part2_too_high", too_low: "part2_too_low" },
+];
+
+impl AnswersFile {
+    /// Reads the file for `day`; a missing file means nothing is known yet.
+    pub(crate) fn load(day: usize) -> Result<Self, AnswersError> {
+        let table = match fs::read_to_string(default_path(day)) {
+            Ok(text) => toml::from_str(&text).map_err(AnswersError::Toml)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Table::new(),
+            Err(e) => return Err(AnswersError::Io(e)),
+        };
+        Ok(Self { day, table })
+    }
+
+    pub(crate) fn save(&self) -> Result<(), AnswersError> {
+        let text = toml::to_string(&self.table).expect("answers are plain values");
+        fs::write(default_path(self.day), text).map_err(AnswersError::Io)
+    }
+
+    /// The right answer to `part`, once known.
+    pub(crate) fn answer(&self, part: u8) -> Result<Option<String>, AnswersError> {
+        read_answer(&self.table, keys(part).answer)
+    }
+
+    /// Why the site would turn down `answer` to `part`, going by its earlier verdicts.
+    pub(crate) fn rejection(&self, part: u8, answer: &str) -> Result<Option<String>, AnswersError> {
+        let keys = keys(part);
+        if read_wrong(&self.table, keys.wrong)?.iter().any(|wrong| wrong == answer) {
+            return Ok(Some("it was turned down before".to_string()));
+        }
+        if let Ok(answer) = answer.parse::<i64>() {
+            match (read_bound(&self.table, keys.too_high)?, read_bound(&self.table, keys.too_low)?) {
+                (Some(high), _) if answer >= high => return Ok(Some(format!("{} was already too high", high))),
+                (_, Some(low)) if answer <= low => return Ok(Some(format!("{} was already too low", low))),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Notes the site's verdict on `answer` to `part`; verdicts that say nothing about the answer leave no trace.
+    pub(crate) fn record(&mut self, part: u8, answer: &str, verdict: Verdict) -> Result<(), AnswersError> {
+        let keys = keys(part);
+        let number = answer.parse::<i64>().ok();
+        match verdict {
+            Verdict::Correct => {
+                self.table.insert(keys.answer.to_string(), number.map_or_else(|| Value::String(answer.to_string()), Value::Integer));
+            }
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow => {
+                let mut wrong = read_wrong(&self.table, keys.wrong)?;
+                wrong.push(answer.to_string());
+                self.table.insert(keys.wrong.to_string(), Value::Array(wrong.into_iter().map(Value::String).collect()));
+
+                let bound = match (verdict, number) {
+                    (Verdict::TooHigh, Some(number)) => Some((keys.too_high, read_bound(&self.table, keys.too_high)?.map_or(number, |high| high.min(number)))),
+                    (Verdict::TooLow, Some(number)) => Some((keys.too_low, read_bound(&self.table, keys.too_low)?.map_or(number, |low| low.max(number)))),
+                    _ => None,
+                };
+                if let Some((key, bound)) = bound {
+                    self.table.insert(key.to_string(), Value::Integer(bound));
+                }
+            }
+            Verdict::TooSoon(_) | Verdict::WrongLevel => {}
+        }
+        Ok(())
+    }
+}
+
+fn keys(part: u8) -> &'static Keys {
+    &KEYS[usize::from(part) - 1]
+}
+
+// numeric answers may be written as plain integers rather than strings
+fn read_answer(table: &Table, key: &'static str) -> Result<Option<String>, AnswersError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(Value::Integer(i)) => Ok(Some(i.to_string())),
+        Some(_) => Err(AnswersError::InvalidValue(key, "a string or an integer")),
+    }
+}
+
+fn read_wrong(table: &Table, key: &'static str) -> Result<Vec<String>, AnswersError> {
+    match table.get(key) {
+        None => Ok(vec![]),
+        Some(Value::Array(values)) => values.iter()
+            .map(|value| match value {
+                Value::String(s) => Ok(s.clone()),
+                Value::Integer(i) => Ok(i.to_string()),
+                _ => Err(AnswersError::InvalidValue(key, "a list of strings or integers")),
+            })
+            .collect(),
+        Some(_) => Err(AnswersError::InvalidValue(key, "a list of strings or integers")),
+    }
+}
+
+fn read_bound(table: &Table, key: &'static str) -> Result<Option<i64>, AnswersError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Integer(i)) => Ok(Some(*i)),
+        Some(_) => Err(AnswersError::InvalidValue(key, "an integer")),
+    }
+}
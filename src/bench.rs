@@ -0,0 +1,221 @@
+use std::fs;
+use std::time::Duration;
+use serde_json::{json, Value};
+use advent_of_code_2021::error::ParseError;
+use advent_of_code_2021::solution::{bench, for_day, Solution, SolutionVisitor, Step};
+use crate::alloc::AllocationCount;
+use crate::input::InputSource;
+use crate::output::millis;
+use crate::{exit_with_usage, take_option, LAST_DAY};
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_WARMUP: usize = 2;
+
+struct Options {
+    day: usize,
+    input: Option<String>,
+    part: Option<u8>,
+    runs: usize,
+    warmup: usize,
+    save: Option<String>,
+    compare: Option<String>,
+}
+
+/// Timings and allocations of one step over every measured run; allocations are per run, and those of preparing
+/// include a fresh parse.
+struct Measurement {
+    step: Step,
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    allocations: usize,
+    bytes: usize,
+}
+
+pub(crate) fn run(mut args: Vec<String>) {
+    let options = Options::take(&mut args);
+    let input = match InputSource::from_arg(options.input.clone(), options.day).read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not read input for day {}: {}", options.day, e);
+            std::process::exit(1);
+        }
+    };
+    let baseline = options.compare.as_ref().map(|path| load_baseline(path, options.day));
+
+    let measurements = match for_day(options.day, Bench { input: &input, options: &options }) {
+        Ok(measurements) => measurements,
+        Err(e) => {
+            eprintln!("could not parse input: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    print_measurements(&options, &measurements, baseline.as_ref());
+    if let Some(path) = &options.save {
+        if let Err(e) = fs::write(path, to_json(&options, &measurements).to_string()) {
+            eprintln!("could not write {}: {}", path, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+impl Options {
+    fn take(args: &mut Vec<String>) -> Self {
+        let number = |name: &str, default: usize, args: &mut Vec<String>| take_option(args, name)
+            .map(|value| value.parse().unwrap_or_else(|_| exit_with_usage(format!("{} should be a number", name))))
+            .unwrap_or(default);
+        let runs = number("--runs", DEFAULT_RUNS, args);
+        let warmup = number("--warmup", DEFAULT_WARMUP, args);
+        let part = take_option(args, "--part").map(|part| match part.as_str() {
+            "1" => 1,
+            "2" => 2,
+            _ => exit_with_usage("--part should be 1 or 2"),
+        });
+        let save = take_option(args, "--save");
+        let compare = take_option(args, "--compare");
+        if runs == 0 {
+            exit_with_usage("--runs should be at least 1");
+        }
+
+        let mut args = args.drain(..);
+        let day = match args.next().map(|day| day.parse()) {
+            Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+            _ => exit_with_usage(format!("bench needs a day between 1 and {}", LAST_DAY)),
+        };
+        Self {
+            day,
+            input: args.next(),
+            part,
+            runs,
+            warmup,
+            save,
+            compare,
+        }
+    }
+
+    fn includes(&self, step: Step) -> bool {
+        match (self.part, step) {
+            (Some(1), Step::Part2) | (Some(2), Step::Part1) => false,
+            _ => true,
+        }
+    }
+}
+
+struct Bench<'a> {
+    input: &'a str,
+    options: &'a Options,
+}
+
+impl SolutionVisitor for Bench<'_> {
+    type Output = Result<Vec<Measurement>, ParseError>;
+
+    fn visit<S: Solution>(self) -> Self::Output {
+        let mut measurements = vec![];
+        bench::<S>(self.input, |step, run| {
+            if self.options.includes(step) {
+                measurements.push(measure(step, run, self.options));
+            }
+        })?;
+        Ok(measurements)
+    }
+}
+
+fn measure(step: Step, run: &mut dyn FnMut() -> Duration, options: &Options) -> Measurement {
+    for _ in 0..options.warmup {
+        run();
+    }
+    let start_allocations = AllocationCount::now();
+    let mut times: Vec<Duration> = (0..options.runs).map(|_| run()).collect();
+    let allocations = AllocationCount::now().since(start_allocations);
+
+    times.sort();
+    let middle = times.len() / 2;
+    let median = if times.len() % 2 == 0 {
+        (times[middle - 1] + times[middle]) / 2
+    } else {
+        times[middle]
+    };
+    Measurement {
+        step,
+        min: times[0],
+        median,
+        mean: times.iter().sum::<Duration>() / times.len() as u32,
+        allocations: allocations.allocations / options.runs,
+        bytes: allocations.bytes / options.runs,
+    }
+}
+
+fn print_measurements(options: &Options, measurements: &[Measurement], baseline: Option<&Value>) {
+    println!("day {}, {} runs after {} warm-up runs", options.day, options.runs, options.warmup);
+    println!();
+    println!("{:<7} | {:>10} | {:>10} | {:>10} | {:>11} | {:>12}{}",
+             "step", "min", "median", "mean", "allocations", "bytes",
+             if baseline.is_some() { " | baseline median" } else { "" });
+    for m in measurements {
+        let comparison = baseline
+            .map(|baseline| match baseline_median(baseline, m.step) {
+                Some(before) => format!(" | {:>10} ({:+.1}%)", format_duration(before), (millis(m.median) / millis(before) - 1.0) * 100.0),
+                None => " | -".to_string(),
+            })
+            .unwrap_or_default();
+        println!("{:<7} | {:>10} | {:>10} | {:>10} | {:>11} | {:>12}{}",
+                 step_name(m.step),
+                 format_duration(m.min),
+                 format_duration(m.median),
+                 format_duration(m.mean),
+                 m.allocations,
+                 m.bytes,
+                 comparison);
+    }
+}
+
+fn step_name(step: Step) -> &'static str {
+    match step {
+        Step::Parse => "parse",
+        Step::Prepare => "prepare",
+        Step::Part1 => "part1",
+        Step::Part2 => "part2",
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", millis(duration))
+}
+
+/// The results saved with `--save`, e.g.
+/// `{"day":1,"runs":10,"warmup":2,"steps":{"part1":{"min_ms":0.1,"median_ms":0.1,"mean_ms":0.1,"allocations":0,"bytes":0}}}`.
+fn to_json(options: &Options, measurements: &[Measurement]) -> Value {
+    let steps: serde_json::Map<String, Value> = measurements.iter()
+        .map(|m| (step_name(m.step).to_string(), json!({
+            "min_ms": millis(m.min),
+            "median_ms": millis(m.median),
+            "mean_ms": millis(m.mean),
+            "allocations": m.allocations,
+            "bytes": m.bytes,
+        })))
+        .collect();
+    json!({
+        "day": options.day,
+        "runs": options.runs,
+        "warmup": options.warmup,
+        "steps": steps,
+    })
+}
+
+fn load_baseline(path: &str, day: usize) -> Value {
+    let baseline: Value = match fs::read_to_string(path).map(|text| serde_json::from_str(&text)) {
+        Ok(Ok(baseline)) => baseline,
+        Ok(Err(e)) => exit_with_usage(format!("{} is not a saved benchmark: {}", path, e)),
+        Err(e) => exit_with_usage(format!("could not read {}: {}", path, e)),
+    };
+    if baseline["day"].as_u64() != Some(day as u64) {
+        exit_with_usage(format!("{} holds a benchmark of another day", path));
+    }
+    baseline
+}
+
+fn baseline_median(baseline: &Value, step: Step) -> Option<Duration> {
+    baseline["steps"][step_name(step)]["median_ms"].as_f64()
+        .map(|ms| Duration::from_secs_f64(ms / 1000.0))
+}
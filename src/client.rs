@@ -0,0 +1,371 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The site the puzzles come from.
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2021;
+/// The environment variable holding the `session` cookie of a logged-in browser, which every input is tied to.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// The least time between two requests unless told otherwise, to go easy on the site.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Why the site gave no input, or no verdict on an answer.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The site asked to log in, so the session cookie is wrong or has expired.
+    LoggedOut,
+    /// The day has not unlocked yet.
+    Locked(usize),
+    /// Any other response that isn't a success.
+    Status(u16, String),
+    /// A page that doesn't say what became of an answer, with its text.
+    Unrecognized(String),
+    /// The site could not be reached.
+    Transport(String),
+    /// The input arrived but could not be read, or kept.
+    Io(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::LoggedOut => write!(f, "the site did not accept the session in {}; log in again and copy the new session cookie", SESSION_VAR),
+            ClientError::Locked(day) => write!(f, "day {} has not unlocked yet", day),
+            ClientError::Status(status, message) => write!(f, "the site answered {}: {}", status, message),
+            ClientError::Unrecognized(text) => write!(f, "the site's reply says nothing about the answer: {}", text),
+            ClientError::Transport(e) => write!(f, "could not reach the site: {}", e),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint as to which way.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Another answer went in too recently, so this one was not looked at; the site says how long to wait.
+    TooSoon(Option<Duration>),
+    /// The part is solved already, or not unlocked yet.
+    WrongLevel,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "that's the right answer"),
+            Verdict::Incorrect => write!(f, "that's not the right answer"),
+            Verdict::TooHigh => write!(f, "that's not the right answer, it's too high"),
+            Verdict::TooLow => write!(f, "that's not the right answer, it's too low"),
+            Verdict::TooSoon(Some(wait)) => write!(f, "an answer went in too recently; try again in {}s", wait.as_secs()),
+            Verdict::TooSoon(None) => write!(f, "an answer went in too recently; try again later"),
+            Verdict::WrongLevel => write!(f, "that part is solved already, or not unlocked yet"),
+        }
+    }
+}
+
+/// Talks to the puzzle site on behalf of one login, leaving at least `interval` between requests.
+pub struct Client {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    last_request: Option<Instant>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// A client for the site at `base_url`, which is [`BASE_URL`] unless testing against a stand-in.
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            interval: DEFAULT_INTERVAL,
+            last_request: None,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The input of `day`, read from `cache` if it is there and downloaded into it otherwise;
+    /// `refresh` downloads it again regardless.
+    pub fn input(&mut self, day: usize, cache: &Path, refresh: bool) -> Result<String, ClientError> {
+        if !refresh {
+            match fs::read_to_string(cache) {
+                Ok(input) => {
+                    debug!("day {} input read from {}", day, cache.display());
+                    return Ok(input);
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(ClientError::Io(e)),
+            }
+        }
+
+        let input = self.get(&format!("/{}/day/{}/input", YEAR, day), day)?;
+        if let Some(dir) = cache.parent() {
+            fs::create_dir_all(dir).map_err(ClientError::Io)?;
+        }
+        fs::write(cache, &input).map_err(ClientError::Io)?;
+        info!("day {} input saved to {}", day, cache.display());
+        Ok(input)
+    }
+
+    /// Posts `answer` to `part` of `day`, and reads the site's verdict on it.
+    pub fn submit(&mut self, day: usize, part: u8, answer: &str) -> Result<Verdict, ClientError> {
+        let request = self.agent.post(&format!("{}/{}/day/{}/answer", self.base_url, YEAR, day));
+        let page = self.send(request, Some(&[("level", &part.to_string()), ("answer", answer)]), day)?;
+        let text = article_text(&page);
+        parse_verdict(&text).ok_or(ClientError::Unrecognized(text))
+    }
+
+    fn get(&mut self, path: &str, day: usize) -> Result<String, ClientError> {
+        let request = self.agent.get(&format!("{}{}", self.base_url, path));
+        self.send(request, None, day)
+    }
+
+    /// Makes `request`, with `form` as its body if there is one, once it is this client's turn, and reads the body of
+    /// the response.
+    fn send(&mut self, request: ureq::Request, form: Option<&[(&str, &str)]>, day: usize) -> Result<String, ClientError> {
+        self.wait_turn();
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        debug!("{} {}", request.method(), request.url());
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response.into_string().map_err(ClientError::Io),
+            Err(ureq::Error::Status(status, response)) => Err(status_error(status, response, day)),
+            Err(ureq::Error::Transport(e)) => Err(ClientError::Transport(e.to_string())),
+        }
+    }
+
+    fn wait_turn(&mut self) {
+        if let Some(last) = self.last_request {
+            let next = last + self.interval;
+            let now = Instant::now();
+            if next > now {
+                debug!("waiting {} ms before the next request", (next - now).as_millis());
+                thread::sleep(next - now);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+fn status_error(status: u16, response: ureq::Response, day: usize) -> ClientError {
+    let body = response.into_string().unwrap_or_default();
+    match status {
+        // the site asks to log in with a 400 rather than a 401
+        400 | 401 => ClientError::LoggedOut,
+        404 => ClientError::Locked(day),
+        _ => ClientError::Status(status, body.lines().next().unwrap_or_default().trim().to_string()),
+    }
+}
+
+/// The text of a page's `<article>`, or of the whole page if it has none, without tags and with single spaces.
+fn article_text(page: &str) -> String {
+    let article = page.split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Reads a verdict out of the text of the page answering a submission.
+pub fn parse_verdict(text: &str) -> Option<Verdict> {
+    if text.contains("not the right answer") {
+        Some(if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        })
+    } else if text.contains("the right answer") {
+        Some(Verdict::Correct)
+    } else if text.contains("answer too recently") {
+        Some(Verdict::TooSoon(parse_wait(text)))
+    } else if text.contains("solving the right level") {
+        Some(Verdict::WrongLevel)
+    } else {
+        None
+    }
+}
+
+// as in "You have 1m 20s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, wait) = before.rsplit_once("You have ")?;
+    wait.split_whitespace()
+        .try_fold(0u64, |total, part| {
+            let (unit_start, _) = part.char_indices().last()?;
+            let (number, unit) = part.split_at(unit_start);
+            let number: u64 = number.parse().ok()?;
+            let seconds = match unit {
+                "h" => number.checked_mul(3600)?,
+                "m" => number.checked_mul(60)?,
+                "s" => number,
+                _ => return None,
+            };
+            total.checked_add(seconds)
+        })
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use super::*;
+
+    /// Serves `responses` in turn on a local port, returning its address and the request line, headers and body of
+    /// each request it got.
+    fn stand_in(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut request: Vec<String> = (&mut reader).lines()
+                    .map(Result::unwrap)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                let length = request.iter()
+                    .find_map(|header| header.to_ascii_lowercase().strip_prefix("content-length: ").map(|length| length.parse().unwrap()))
+                    .unwrap_or(0);
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push(String::from_utf8(content).unwrap());
+                sender.send(request).unwrap();
+                write!(stream, "HTTP/1.1 {} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+            }
+        });
+        (address, requests)
+    }
+
+    fn cache_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("day1.txt")
+    }
+
+    #[test]
+    fn downloads_inputs_once() {
+        let (address, requests) = stand_in(vec![(200, "199\n200\n208\n")]);
+        let cache = cache_path("once");
+        let mut client = Client::new(address, "abc123");
+
+        assert_eq!(client.input(1, &cache, false).unwrap(), "199\n200\n208\n");
+        let request = requests.recv().unwrap();
+        assert_eq!(request[0], "GET /2021/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|header| header.eq_ignore_ascii_case("cookie: session=abc123")), "{:?}", request);
+
+        // the stand-in would not answer again, so this has to come from the cache
+        assert_eq!(client.input(1, &cache, false).unwrap(), "199\n200\n208\n");
+        assert_eq!(fs::read_to_string(&cache).unwrap(), "199\n200\n208\n");
+        fs::remove_dir_all(cache.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let (address, _requests) = stand_in(vec![(200, "1"), (200, "2")]);
+        let cache = cache_path("spaced");
+        let mut client = Client::new(address, "abc123").with_interval(Duration::from_millis(300));
+
+        let start = Instant::now();
+        assert_eq!(client.input(1, &cache, true).unwrap(), "1");
+        assert_eq!(client.input(1, &cache, true).unwrap(), "2");
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        fs::remove_dir_all(cache.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn reports_what_went_wrong() {
+        let (address, _requests) = stand_in(vec![
+            (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            (404, "Please don't repeatedly request this endpoint before it unlocks!\n"),
+            (503, "Service Unavailable\n"),
+        ]);
+        let cache = cache_path("errors");
+        let mut client = Client::new(address, "expired").with_interval(Duration::ZERO);
+
+        assert!(matches!(client.input(1, &cache, false), Err(ClientError::LoggedOut)));
+        assert!(matches!(client.input(1, &cache, false), Err(ClientError::Locked(1))));
+        match client.input(1, &cache, false) {
+            Err(ClientError::Status(503, message)) => assert_eq!(message, "Service Unavailable"),
+            other => panic!("expected a 503, got {:?}", other.map(|_| ())),
+        }
+        assert!(!cache.exists());
+    }
+
+    const RIGHT: &str = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer \
+        to finding the sleigh keys. <a href=\"/2021/day/1#part2\">[Continue to Part Two]</a></p></article>\n</main>";
+
+    #[test]
+    fn submits_answers_as_forms() {
+        let (address, requests) = stand_in(vec![(200, RIGHT)]);
+        let mut client = Client::new(address, "abc123");
+
+        assert_eq!(client.submit(1, 2, "1627").unwrap(), Verdict::Correct);
+        let request = requests.recv().unwrap();
+        assert_eq!(request[0], "POST /2021/day/1/answer HTTP/1.1");
+        assert!(request.iter().any(|header| header.eq_ignore_ascii_case("cookie: session=abc123")), "{:?}", request);
+        assert_eq!(request.last().unwrap(), "level=2&answer=1627");
+    }
+
+    #[test]
+    fn reads_verdicts() {
+        let verdict = |article: &str| parse_verdict(&article_text(&format!("<main>\n<article><p>{}</p></article>\n</main>", article)));
+        assert_eq!(parse_verdict(&article_text(RIGHT)), Some(Verdict::Correct));
+        assert_eq!(verdict("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the \
+            full input data. Please wait one minute before trying again. <a href=\"/2021/day/1\">[Return to Day 1]</a>"), Some(Verdict::TooHigh));
+        assert_eq!(verdict("That's not the right answer; your answer is too low. <a href=\"/2021/day/1\">[Return to Day 1]</a>"), Some(Verdict::TooLow));
+        assert_eq!(verdict("That's not the right answer.  If you're stuck, make sure you're using the full input data."), Some(Verdict::Incorrect));
+        assert_eq!(verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again.  \
+            You have 1m 20s left to wait. <a href=\"/2021/day/1\">[Return to Day 1]</a>"), Some(Verdict::TooSoon(Some(Duration::from_secs(80)))));
+        assert_eq!(verdict("You gave an answer too recently. You have 1m 20é left to wait."), Some(Verdict::TooSoon(None)));
+        assert_eq!(verdict("You don't seem to be solving the right level.  Did you already complete it?"), Some(Verdict::WrongLevel));
+        assert_eq!(verdict("Something else entirely"), None);
+    }
+
+    #[test]
+    fn reports_pages_without_a_verdict() {
+        let (address, _requests) = stand_in(vec![(200, "<html><body><article><p>Maintenance</p></article></body></html>")]);
+        let mut client = Client::new(address, "abc123");
+
+        match client.submit(1, 1, "1583") {
+            Err(ClientError::Unrecognized(text)) => assert_eq!(text, "Maintenance"),
+            other => panic!("expected an unrecognized page, got {:?}", other),
+        }
+    }
+}
@@ -1,36 +1,102 @@
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
+use crate::animation::{Animate, Recorder};
+use crate::error::ParseError;
+use crate::generate::{Generate, grid_lines, Rng, Size};
+use crate::grid::{Grid, Pos};
+use crate::picture::Rgb;
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "5483143223
-2745854711
-5264556173
-6141336146
-6357385478
-4167524645
-2176841721
-6882881134
-4846848554
-5283751526";
-    let _input = _get_input();
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+
+    type Input<'a> = Cavern;
+    type Part1 = u64;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn part1(cavern: &Cavern) -> Self::Part1 {
+        let mut cavern = cavern.clone();
+        for _ in 0..100 {
+            cavern.step();
+        }
+        debug!("after step 100:");
+        cavern.print();
 
-    let mut grid: Grid = _input.parse().unwrap();
+        cavern.1
+    }
+
+    fn prepare(cavern: &mut Cavern) -> Result<(), ParseError> {
+        let steps = steps_until_simultaneous_flash(cavern, |_| {}).ok_or_else(|| ParseError::new(1, 1,
+            format!("octopuses that all flash together within {} steps", MAX_STEPS_UNTIL_SIMULTANEOUS_FLASH)))?;
+        cavern.2 = Some(steps);
+        Ok(())
+    }
+
+    fn part2(cavern: &Cavern) -> Self::Part2 {
+        cavern.2.expect("the octopuses are stepped by Day11::prepare")
+    }
+}
+
+impl Generate for Day11 {
+    const SIZE: Option<Size> = Some(Size::new("rows and columns", 10));
+
+    /// Random energy levels, drawn again until the octopuses flash together within a reasonable time.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        loop {
+            let input = grid_lines(size, size, |_, _| char::from_digit(rng.range(1..=9) as u32, 10).unwrap());
+            if steps_until_simultaneous_flash(&input.parse().unwrap(), |_| {}).is_some() {
+                return input;
+            }
+        }
+    }
+}
+
+/// How long the octopuses get to flash together, as some never do.
+const MAX_STEPS_UNTIL_SIMULTANEOUS_FLASH: u32 = 1000;
+
+impl Animate for Day11 {
+    /// The octopuses up to their first simultaneous flash; flashing ones light up, the rest glow with their energy.
+    fn animate(cavern: &Cavern, recorder: &mut Recorder) {
+        let frame = |cavern: &Cavern| cavern.0.map(|octopus| match octopus.0 {
+            0 => Rgb(255, 240, 160),
+            energy => Rgb(0, 20, 60).scaled(1.0 + energy as f64 / 3.0),
+        });
+        recorder.record(|| frame(cavern));
+        steps_until_simultaneous_flash(cavern, |cavern| recorder.record(|| frame(cavern)));
+    }
+}
+
+/// The step on which every octopus flashes, or `None` if none does within [`MAX_STEPS_UNTIL_SIMULTANEOUS_FLASH`].
+fn steps_until_simultaneous_flash(cavern: &Cavern, mut on_step: impl FnMut(&Cavern)) -> Option<u32> {
+    let mut cavern = cavern.clone();
     let mut i = 0u32;
-    println!("start:");
-    grid.print();
+    debug!("start:");
+    cavern.print();
 
-    while !grid.has_simultaneous_flash() {
+    while !cavern.has_simultaneous_flash() {
+        if i == MAX_STEPS_UNTIL_SIMULTANEOUS_FLASH {
+            return None;
+        }
         i += 1;
-        grid.step();
+        cavern.step();
+        on_step(&cavern);
         if i % 10 == 0 {
-            println!("after step {}:", i);
-            grid.print();
+            debug!("after step {}:", i);
+            cavern.print();
         }
     }
 
-    println!("first simultaneous flash: after step {}", i);
+    info!("first simultaneous flash: after step {}", i);
+    Some(i)
 }
 
+#[derive(Clone)]
 struct Octopus(u8);
 
 impl Debug for Octopus {
@@ -53,99 +119,78 @@ impl Octopus {
     }
 }
 
-impl From<char> for Octopus {
-    fn from(c: char) -> Self {
-        Self(c as u8 - '0' as u8)
-    }
-}
-
+/// The octopuses and how many flashes there have been, and once prepared, the step on which they first flash together.
+#[derive(Clone)]
+pub struct Cavern(Grid<Octopus>, u64, Option<u32>);
 
-struct Grid(Vec<Vec<Octopus>>, u64);
-
-impl Grid {
+impl Cavern {
     pub fn has_simultaneous_flash(&self) -> bool {
-        self.0.iter().all(|line| line.iter().all(|oct| oct.0 == 0))
+        self.0.values().all(|oct| oct.0 == 0)
     }
     pub fn print(&self) {
-        for j in 0..self.0.len() {
-            println!("{:?}", self.0[j]);
+        for row in self.0.rows() {
+            debug!("{:?}", row);
         }
-        println!("flashes: {}", self.1);
+        debug!("flashes: {}", self.1);
     }
-    fn get_neighbors(i: usize, j: usize, len_i: usize, len_j: usize) -> impl Iterator<Item=(usize, usize)> {
-        let min_i = if i == 0 {
-            0
-        } else {
-            i - 1
-        };
-        let max_i = if i == len_i - 1 {
-            i
-        } else {
-            i + 1
-        };
-        let min_j = if j == 0 {
-            0
-        } else {
-            j - 1
-        };
-        let max_j = if j == len_j - 1 {
-            j
-        } else {
-            j + 1
-        };
-        (min_i..=max_i)
-            .flat_map(move |i0| (min_j..=max_j).map(move |j0| (i0, j0)))
-            .filter(move |val| val != &(i, j))
-    }
-    fn step_octopus(&mut self, i: usize, j: usize) {
-        self.0[j][i].increase();
-        if self.0[j][i].0 == 10 {
-            for neighbor in Self::get_neighbors(i, j, self.0[j].len(), self.0.len()) {
-                Self::step_octopus(self, neighbor.0, neighbor.1);
+    fn step_octopus(&mut self, pos: Pos) {
+        self.0[pos].increase();
+        if self.0[pos].0 == 10 {
+            let neighbors: Vec<Pos> = self.0.neighbors8(pos).collect();
+            for neighbor in neighbors {
+                Self::step_octopus(self, neighbor);
             }
         }
     }
     pub fn step(&mut self) {
-        for j in 0..self.0.len() {
-            for i in 0..self.0[j].len() {
-                Self::step_octopus(self, i, j);
-            }
+        for pos in self.0.positions() {
+            Self::step_octopus(self, pos);
         }
-        for j in 0..self.0.len() {
-            for i in 0..self.0[j].len() {
-                if self.0[j][i].resolve_flash() {
-                    self.1 += 1;
-                }
+        for octopus in self.0.values_mut() {
+            if octopus.resolve_flash() {
+                self.1 += 1;
             }
         }
     }
 }
 
-impl FromStr for Grid {
-    type Err = ();
+impl FromStr for Cavern {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut grid: Vec<Vec<Octopus>> = vec![];
-        for line in s.split('\n') {
-            let mut row: Vec<Octopus> = vec![];
-            for c in line.chars() {
-                row.push(c.into());
-            }
-            grid.push(row);
-        }
-        Ok(Self(grid, 0))
+        let octopuses = Grid::parse(s, "an energy level digit", |c| c.to_digit(10).map(|d| Octopus(d as u8)))?;
+        Ok(Self(octopuses, 0, None))
     }
 }
 
-fn _get_input() -> &'static str {
-    "2344671212
-6611742681
-5575575573
-3167848536
-1353827311
-4416463266
-2624761615
-1786561263
-3622643215
-4143284653"
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use crate::solution::solve;
+    use super::*;
+
+    const EXAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn example() {
+        let mut input = Day11::parse(EXAMPLE).unwrap();
+        Day11::prepare(&mut input).unwrap();
+        assert_eq!(Day11::part1(&input), 1656);
+        assert_eq!(Day11::part2(&input), 195);
+    }
+
+    #[test]
+    fn reports_octopuses_that_never_flash_together() {
+        assert!(Day11::parse("792").is_ok());
+        assert_eq!(solve::<Day11>("792").err(),
+                   Some(ParseError::new(1, 1, "octopuses that all flash together within 1000 steps").on_day(11)));
+    }
+}
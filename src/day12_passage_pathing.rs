@@ -1,88 +1,124 @@
 use std::collections::{HashMap, HashSet};
+use crate::error::{next_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "start-A
-start-b
-A-c
-A-b
-b-d
-A-end
-b-end";
-    let _input = "dc-end
-HN-start
-start-kj
-dc-start
-dc-HN
-LN-dc
-HN-end
-kj-sa
-kj-HN
-kj-dc";
-    let _input = "fs-end
-he-DX
-fs-he
-start-DX
-pj-DX
-end-zg
-zg-sl
-zg-pj
-pj-he
-RW-he
-fs-DX
-pj-RW
-zg-RW
-start-pj
-he-WI
-zg-he
-pj-fs
-start-RW";
-    let _input = _get_input();
+pub struct Day12;
 
-    let network: Network = _input.into();
+impl Solution for Day12 {
+    const DAY: usize = 12;
 
-    let valid_paths = get_paths(&network, &vec![]);
-    for path in valid_paths.iter() {
-        println!("{:?}", path);
+    type Input<'a> = Network<'a>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.try_into()
+    }
+
+    fn part1(network: &Network<'_>) -> Self::Part1 {
+        count_paths(network, is_valid_path_v1)
     }
-    println!("there are {} valid paths", valid_paths.len());
 
+    fn part2(network: &Network<'_>) -> Self::Part2 {
+        count_paths(network, is_valid_path_v2)
+    }
 }
 
-fn get_paths<'a>(network: &'a Network<'a>, parent: &Vec<&'a str>) -> Vec<Vec<&'a str>> {
-    let current_cave = if parent.is_empty() {
-        "start"
-    } else {
-        parent.last().unwrap()
-    };
+impl Generate for Day12 {
+    const SIZE: Option<Size> = Some(Size::new("small caves", 6).up_to(26 * 26));
 
-    let _is_valid_path_v1 = |path: &Vec<&'a str>| -> bool {
-        let mut set: HashSet<&'a str> = Default::default();
-        for p in path.iter() {
-            if p.chars().next().unwrap().is_lowercase() {
-                if set.contains(p) {
-                    return false;
-                }
-                set.insert(p);
+    /// A connected cave system with a big cave for every three small ones. Big caves never connect to
+    /// each other, since the paths between them could go on forever.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let name = |i: usize, big: bool| {
+            let base = if big { b'A' } else { b'a' };
+            [base + (i / 26) as u8, base + (i % 26) as u8].iter().map(|&b| b as char).collect::<String>()
+        };
+        let mut caves: Vec<(String, bool)> = vec![("start".to_string(), false), ("end".to_string(), false)];
+        caves.extend((0..size).map(|i| (name(i, false), false)));
+        caves.extend((0..(size / 3).max(1)).map(|i| (name(i, true), true)));
+        rng.shuffle(&mut caves[2..]);
+        // the tree below hangs everything off the first cave, which has to be small to take big ones
+        let first_small = caves.iter().skip(2).position(|cave| !cave.1).unwrap() + 2;
+        caves.swap(2, first_small);
+
+        let mut connections: Vec<(usize, usize)> = vec![];
+        let can_connect = |a: usize, b: usize, connections: &Vec<(usize, usize)>| {
+            a != b && !(caves[a].1 && caves[b].1) && (a, b) != (0, 1) && (a, b) != (1, 0)
+                && !connections.contains(&(a, b)) && !connections.contains(&(b, a))
+        };
+        // a spanning tree, so that every cave has a neighbour other than the start and end (caves
+        // without one are left out of the network), then a few shortcuts
+        connections.push((0, 2));
+        for cave in 3..caves.len() {
+            let earlier: Vec<usize> = (2..cave).filter(|&other| can_connect(cave, other, &connections)).collect();
+            connections.push((*rng.choose(&earlier), cave));
+        }
+        connections.push((rng.range(2..=caves.len() as i64 - 1) as usize, 1));
+        for _ in 0..size {
+            let (a, b) = (rng.below(caves.len()), rng.below(caves.len()));
+            if can_connect(a, b, &connections) {
+                connections.push((a, b));
             }
         }
-        true
-    };
-    let _is_valid_path_v2 = |path: &Vec<&'a str>| -> bool {
-        let mut set: HashSet<&'a str> = Default::default();
-        let mut visited_twice = false;
-        for p in path.iter() {
-            if p.chars().next().unwrap().is_lowercase() {
-                if set.contains(p) {
-                    if visited_twice {
-                        return false;
-                    } else {
-                        visited_twice = true;
-                    }
+        rng.shuffle(&mut connections);
+        connections.iter()
+            .map(|&(a, b)| format!("{}-{}", caves[a].0, caves[b].0))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+fn count_paths(network: &Network<'_>, is_valid_path: fn(&[&str]) -> bool) -> usize {
+    let valid_paths = get_paths(network, &vec![], is_valid_path);
+    for path in valid_paths.iter() {
+        debug!("{:?}", path);
+    }
+    info!("there are {} valid paths", valid_paths.len());
+    valid_paths.len()
+}
+
+fn is_big(cave: &str) -> bool {
+    cave.starts_with(|c: char| c.is_uppercase())
+}
+
+fn is_valid_path_v1(path: &[&str]) -> bool {
+    let mut set: HashSet<&str> = Default::default();
+    for p in path.iter() {
+        if !is_big(p) {
+            if set.contains(p) {
+                return false;
+            }
+            set.insert(p);
+        }
+    }
+    true
+}
+
+fn is_valid_path_v2(path: &[&str]) -> bool {
+    let mut set: HashSet<&str> = Default::default();
+    let mut visited_twice = false;
+    for p in path.iter() {
+        if !is_big(p) {
+            if set.contains(p) {
+                if visited_twice {
+                    return false;
+                } else {
+                    visited_twice = true;
                 }
-                set.insert(p);
             }
+            set.insert(p);
         }
-        true
+    }
+    true
+}
+
+fn get_paths<'a>(network: &'a Network<'a>, parent: &Vec<&'a str>, is_valid_path: fn(&[&str]) -> bool) -> Vec<Vec<&'a str>> {
+    let current_cave = if parent.is_empty() {
+        "start"
+    } else {
+        parent.last().unwrap()
     };
 
     network.paths_from(current_cave)
@@ -91,31 +127,41 @@ fn get_paths<'a>(network: &'a Network<'a>, parent: &Vec<&'a str>) -> Vec<Vec<&'a
             next_path.push(next);
             if next == "end" {
                 Some(vec![next_path])
-            } else if !_is_valid_path_v2(&next_path) {
+            } else if !is_valid_path(&next_path) {
                 None
             } else {
-                Some(get_paths(network, &next_path))
+                Some(get_paths(network, &next_path, is_valid_path))
             }
         })
         .flat_map(|paths| paths.into_iter())
         .collect::<Vec<Vec<&'a str>>>()
 }
 
-struct Network<'a>(HashMap<&'a str, HashSet<&'a str>>);
+pub struct Network<'a>(HashMap<&'a str, HashSet<&'a str>>);
 
 impl<'a> Network<'a> {
     pub fn paths_from(&self, cave: &'a str) -> impl Iterator<Item=&&str> {
-        self.0.get(cave).unwrap().into_iter()
+        self.0.get(cave).into_iter().flatten()
     }
 }
 
-impl<'a> From<&'a str> for Network<'a> {
-    fn from(s: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for Network<'a> {
+    type Error = ParseError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let mut map: HashMap<&'a str, HashSet<&'a str>> = Default::default();
         for line in s.split('\n') {
             let mut parts = line.split('-');
-            let left = parts.next().unwrap();
-            let right = parts.next().unwrap();
+            let left = next_token(s, &mut parts, "a cave")?;
+            let right = next_token(s, &mut parts, "'-'")?;
+            for cave in [left, right] {
+                if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                    return Err(ParseError::at(s, cave, "a cave name"));
+                }
+            }
+            if is_big(left) && is_big(right) {
+                return Err(ParseError::at(s, right, "a small cave, as paths between big caves could go on forever"));
+            }
             for items in [(left, right), (right, left)] {
                 if items.1 == "start" || items.0 == "end" {
                     continue;
@@ -128,32 +174,78 @@ impl<'a> From<&'a str> for Network<'a> {
             }
         }
 
-        Self(map)
+        Ok(Self(map))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    const SMALL_EXAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
 
-fn _get_input() -> &'static str {
-    "mx-IQ
-mx-HO
-xq-start
-start-HO
-IE-qc
-HO-end
-oz-xq
-HO-ni
-ni-oz
-ni-MU
-sa-IE
-IE-ni
-end-sa
-oz-sa
-MU-start
-MU-sa
-oz-IE
-HO-xq
-MU-xq
-IE-end
-MU-mx"
-}
\ No newline at end of file
+    const LARGER_EXAMPLE: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+    const LARGEST_EXAMPLE: &str = "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+    #[test]
+    fn small_example() {
+        let input = Day12::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&input), 10);
+        assert_eq!(Day12::part2(&input), 36);
+    }
+
+    #[test]
+    fn larger_example() {
+        let input = Day12::parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&input), 19);
+        assert_eq!(Day12::part2(&input), 103);
+    }
+
+    #[test]
+    fn largest_example() {
+        let input = Day12::parse(LARGEST_EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&input), 226);
+        assert_eq!(Day12::part2(&input), 3509);
+    }
+
+    #[test]
+    fn keeps_paths_finite() {
+        assert_eq!(Day12::parse("start-A\nA-BC\nA-end").err(),
+                   Some(ParseError::new(2, 3, "a small cave, as paths between big caves could go on forever")));
+        let input = Day12::parse("AA-end\naa-acs").unwrap();
+        assert_eq!((Day12::part1(&input), Day12::part2(&input)), (0, 0));
+    }
+}
@@ -1,70 +1,121 @@
 use std::collections::HashMap;
 use std::str::FromStr;
+use crate::error::{next_token, split_at_blank_line, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::reference::Reference;
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "NNCB
+pub struct Day14;
 
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C";
-    let _input = _get_input();
-    let mut lines = _input.split('\n');
-    let mut template: TemplateV2 = lines.next().unwrap().parse().unwrap();
-    lines.next();
-    let rules: Rules = Rules::from_iter(lines);
-
-    // println!("template: {}", template.0);
-    println!("template: {:?}", template);
-
-    for _i in 1..=40 {
-        template.next(&rules);
-        // println!("after step {}: {}", _i, template.0);
-        println!("{:?}", template);
+impl Solution for Day14 {
+    const DAY: usize = 14;
+
+    type Input<'a> = (Template, Rules<'a>);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (template, rules) = split_at_blank_line(input, "a blank line after the template")?;
+        let template: Template = template.parse()?;
+        let rules: Rules = rules.try_into().map_err(|e: ParseError| e.relative_to(input, rules))?;
+
+        debug!("template: {}", template.0);
+        Ok((template, rules))
     }
-    println!("score: {}", template.score());
 
+    fn part1((template, rules): &Self::Input<'_>) -> Self::Part1 {
+        score_after(template, rules, 10)
+    }
+
+    fn part2((template, rules): &Self::Input<'_>) -> Self::Part2 {
+        score_after(template, rules, 40)
+    }
+}
+
+impl Generate for Day14 {
+    const SIZE: Option<Size> = Some(Size::new("elements", 10).up_to(26));
+
+    /// A template of 20 elements, and an insertion rule for every pair of elements.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut letters: Vec<char> = ('A'..='Z').collect();
+        rng.shuffle(&mut letters);
+        let elements = &letters[..size];
+        let template: String = (0..20).map(|_| *rng.choose(elements)).collect();
+        let rules = elements.iter()
+            .flat_map(|&a| elements.iter().map(move |&b| (a, b)))
+            .map(|(a, b)| format!("{}{} -> {}", a, b, rng.choose(elements)));
+        std::iter::once(template).chain(std::iter::once(String::new())).chain(rules).collect::<Vec<String>>().join("\n")
+    }
+}
+
+impl Reference for Day14 {
+    /// The polymer itself grown step by step; after 40 steps it is far too long for that.
+    fn reference_part1((template, rules): &Self::Input<'_>) -> Option<Self::Part1> {
+        let mut template = template.clone();
+        for _ in 0..10 {
+            template.next(rules);
+        }
+        Some(template.score())
+    }
+
+    fn reference_part2(_input: &Self::Input<'_>) -> Option<Self::Part2> {
+        None
+    }
+}
+
+fn score_after(template: &Template, rules: &Rules, steps: u32) -> u64 {
+    let mut template = TemplateV2::from(template);
+    for _i in 1..=steps {
+        template.next(rules);
+        // info!("after step {}: {}", _i, template.0);
+        debug!("{:?}", template);
+    }
+    info!("score: {}", template.score());
+    template.score()
 }
 
-struct Rules<'a>(HashMap<&'a str, &'a str>);
+pub struct Rules<'a>(HashMap<&'a str, &'a str>);
 
-impl<'a> FromIterator<&'a str> for Rules<'a> {
-    fn from_iter<T: IntoIterator<Item=&'a str>>(iter: T) -> Self {
+impl<'a> TryFrom<&'a str> for Rules<'a> {
+    type Error = ParseError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let mut map: HashMap<&'a str, &'a str> = Default::default();
-        for line in iter {
+        for line in s.split('\n') {
             let mut parts = line.split_whitespace();
-            let key = parts.next().unwrap();
-            parts.next();
-            let val = parts.next().unwrap();
+            let key = next_token(s, &mut parts, "a pair of elements")?;
+            if key.len() != 2 {
+                return Err(ParseError::at(s, key, "a pair of elements"));
+            }
+            match next_token(s, &mut parts, "'->'")? {
+                "->" => {}
+                arrow => return Err(ParseError::at(s, arrow, "'->'"))
+            }
+            let val = next_token(s, &mut parts, "an element to insert")?;
+            if val.len() != 1 {
+                return Err(ParseError::at(s, val, "a single element"));
+            }
             map.insert(key, val);
         }
-        Self(map)
+        Ok(Self(map))
     }
 }
 
-struct Template(String);
+#[derive(Clone)]
+pub struct Template(String);
 
 impl FromStr for Template {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new(1, 1, "a polymer template"));
+        }
         Ok(Self(s.to_string()))
     }
 }
 
 impl Template {
-    pub fn _next(&mut self, rules: &Rules) {
+    pub fn next(&mut self, rules: &Rules) {
         let mut next: String = Default::default();
         let mut chars = self.0.chars();
         let mut last_char = chars.next().unwrap();
@@ -80,9 +131,9 @@ impl Template {
 
         self.0 = next;
     }
-    pub fn _score(&self) -> i32 {
-        let quantities: Vec<i32> = self.0.chars().fold(HashMap::new(), |mut acc, next| {
-            let val = acc.entry(next).or_insert(0i32);
+    pub fn score(&self) -> u64 {
+        let quantities: Vec<u64> = self.0.chars().fold(HashMap::new(), |mut acc, next| {
+            let val = acc.entry(next).or_insert(0u64);
             *val += 1;
             acc
         }).values().copied().collect();
@@ -90,14 +141,13 @@ impl Template {
     }
 }
 
-#[derive(Debug)]
-struct TemplateV2(HashMap<String, u64>);
+#[derive(Debug, Clone)]
+pub struct TemplateV2(HashMap<String, u64>);
 
-impl FromStr for TemplateV2 {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl From<&Template> for TemplateV2 {
+    fn from(template: &Template) -> Self {
         let mut map: HashMap<String, u64> = Default::default();
-        let mut chars = s.chars();
+        let mut chars = template.0.chars();
         let mut last_char = chars.next().unwrap();
         for c in chars {
             let key: String = vec![last_char, c].iter().collect();
@@ -106,7 +156,7 @@ impl FromStr for TemplateV2 {
             last_char = c;
         }
         map.insert(last_char.to_string(), 1);
-        Ok(Self(map))
+        Self(map)
     }
 }
 
@@ -142,107 +192,34 @@ impl TemplateV2 {
     }
 }
 
-fn _get_input() -> &'static str {
-    "PSVVKKCNBPNBBHNSFKBO
-
-CF -> H
-PP -> H
-SP -> V
-NO -> C
-SF -> F
-FS -> H
-OF -> P
-PN -> B
-SH -> V
-BO -> K
-ON -> V
-VP -> S
-HN -> B
-PS -> P
-FV -> H
-NC -> N
-FN -> S
-PF -> F
-BF -> F
-NB -> O
-HS -> C
-SC -> V
-PC -> K
-KF -> K
-HC -> C
-OK -> H
-KS -> P
-VF -> C
-NV -> S
-KK -> F
-HV -> H
-SV -> V
-KC -> N
-HF -> P
-SN -> F
-VS -> P
-VN -> F
-VH -> C
-OB -> K
-VV -> O
-VC -> O
-KP -> V
-OP -> C
-HO -> S
-NP -> K
-HB -> C
-CS -> S
-OO -> S
-CV -> K
-BS -> F
-BH -> P
-HP -> P
-PK -> B
-BB -> H
-PV -> N
-VO -> P
-SS -> B
-CC -> F
-BC -> V
-FF -> S
-HK -> V
-OH -> N
-BV -> C
-CP -> F
-KN -> K
-NN -> S
-FB -> F
-PH -> O
-FH -> N
-FK -> P
-CK -> V
-CN -> S
-BP -> K
-CH -> F
-FP -> K
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "NNCB
+
+CH -> B
 HH -> N
-NF -> C
-VB -> B
-FO -> N
-PB -> C
-KH -> K
-PO -> K
-OV -> F
-NH -> H
-KV -> B
-OS -> K
-OC -> K
-FC -> H
-SO -> H
-KO -> P
-NS -> F
-CB -> C
-CO -> F
-KB -> V
-BK -> K
-NK -> O
-SK -> C
-SB -> B
-VK -> O
-BN -> H"
-}
\ No newline at end of file
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn example() {
+        let input = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part1(&input), 1588);
+        assert_eq!(Day14::part2(&input), 2188189693529);
+        assert_eq!(Day14::reference_part1(&input), Some(1588));
+    }
+}
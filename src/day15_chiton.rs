@@ -1,110 +1,100 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeSet;
 use std::str::FromStr;
+use crate::error::ParseError;
+use crate::generate::{Generate, grid_lines, Rng, Size};
+use crate::grid::{Grid, Pos};
+use crate::log::{self, Level};
+use crate::picture::{Export, Picture, Rgb};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "1163751742
-1381373672
-2136511328
-3694931569
-7463417111
-1319128137
-1359912421
-3125421639
-1293138521
-2311944581";
-    // let _input = _get_input();
+pub struct Day15;
 
-    let mut network: Network = _input.parse().unwrap();
-    network.expand(5);
-    network.solve();
-    network.print(true);
-    println!("total risk: {}", network.total_risk());
+impl Solution for Day15 {
+    const DAY: usize = 15;
+
+    type Input<'a> = Network;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn part1(network: &Network) -> Self::Part1 {
+        lowest_total_risk(network.clone())
+    }
+
+    fn part2(network: &Network) -> Self::Part2 {
+        let mut network = network.clone();
+        network.expand(5);
+        lowest_total_risk(network)
+    }
+}
+
+impl Generate for Day15 {
+    const SIZE: Option<Size> = Some(Size::new("rows and columns", 100));
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        grid_lines(size, size, |_, _| char::from_digit(rng.range(1..=9) as u32, 10).unwrap())
+    }
+}
+
+impl Export for Day15 {
+    /// The full cave from part 2, lighter where the risk is lower, with the safest path in red.
+    fn picture(network: &Network) -> Picture {
+        let mut network = network.clone();
+        network.expand(5);
+        network.solve();
+        let mut pixels = network.0.map(|node| Rgb::WHITE.scaled(1.0 - node.risk as f64 / 10.0));
+        for pos in network.get_solution() {
+            pixels[pos] = Rgb(220, 20, 60);
+        }
+        Picture::Pixmap(pixels)
+    }
 }
 
-#[derive(Debug, PartialEq, Clone, Default, Ord, Eq, PartialOrd)]
-struct Coord {
-    x: usize,
-    y: usize,
+fn lowest_total_risk(mut network: Network) -> u32 {
+    network.solve();
+    if log::enabled(Level::Debug) {
+        network.print(true);
+    }
+    info!("total risk: {}", network.total_risk());
+    network.total_risk()
 }
 
+#[derive(Clone)]
 struct Node {
     risk: u32,
     cumulative_risk: Option<u32>,
 }
 
-impl From<char> for Node {
-    fn from(c: char) -> Self {
+impl Node {
+    fn new(risk: u32) -> Self {
         Self {
-            risk: (c as u8 - '0' as u8) as u32,
+            risk,
             cumulative_risk: None,
         }
     }
 }
 
 impl FromStr for Network {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map: BTreeMap<Coord, Node> = Default::default();
-        let mut y = 0usize;
-        let mut len_x = 0usize;
-        for line in s.split('\n') {
-            let mut x = 0usize;
-            for c in line.chars() {
-                let coord = Coord { x, y };
-                map.insert(coord, c.into());
-                x += 1;
-            }
-            y += 1;
-            len_x = x;
+        let grid = Grid::parse(s, "a risk level from 1 to 9", |c| c.to_digit(10).filter(|&risk| risk > 0).map(Node::new))?;
+        if grid.width() == 0 {
+            return Err(ParseError::new(1, 1, "a risk level from 1 to 9"));
         }
-
-        Ok(Self {
-            map,
-            len_x,
-            len_y: y,
-        })
+        Ok(Self(grid))
     }
 }
 
-struct Network {
-    map: BTreeMap<Coord, Node>,
-    len_x: usize,
-    len_y: usize,
-}
+#[derive(Clone)]
+pub struct Network(Grid<Node>);
 
 impl Network {
-    fn neighbors<'a, 'b>(&'a self, coord: &'b Coord) -> impl Iterator<Item=Coord> + 'b
-        where 'a : 'b {
-        let min_x = if coord.x > 0 {
-            coord.x - 1
-        } else {
-            coord.x
-        };
-        let max_x = if coord.x < self.len_x - 1 {
-            coord.x + 1
-        } else {
-            coord.x
-        };
-        let min_y = if coord.y > 0 {
-            coord.y - 1
-        } else {
-            coord.y
-        };
-        let max_y = if coord.y < self.len_y - 1 {
-            coord.y + 1
-        } else {
-            coord.y
-        };
-        // (min_x..=max_x)
-        //     .flat_map(move |x| (min_y..=max_y).map(move |y| Coord { x, y }))
-        (min_x..=max_x)
-            .filter(|&x| x != coord.x)
-            .map(move |x| Coord { x, y: coord.y })
-            .chain((min_y..=max_y).filter(|&y| y != coord.y).map(move |y| Coord { y, x: coord.x }))
-    }
     pub fn total_risk(&self) -> u32 {
-        self.map.get(&Default::default()).unwrap().cumulative_risk.unwrap()
+        self.0[Pos::default()].cumulative_risk.unwrap()
     }
     pub fn print(&self, with_solution: bool) {
         let solution = if with_solution {
@@ -112,16 +102,15 @@ impl Network {
         } else {
             None
         };
-        for y in 0..self.len_y {
-            println!("{}", (0..self.len_x).map(|x| {
-                let coord = Coord { x, y };
-                let str = self.map.get(&coord).unwrap().risk.to_string();
+        for (y, row) in self.0.rows().enumerate() {
+            debug!("{}", row.iter().enumerate().map(|(x, node)| {
+                let str = node.risk.to_string();
                 match &solution {
                     None => {
                         str
                     }
                     Some(solution) => {
-                        if solution.contains(&Coord { x, y }) {
+                        if solution.contains(&Pos { x, y }) {
                             format!("\x1b[1;35m{}\x1b[0m", str)
                         } else {
                             str
@@ -132,49 +121,44 @@ impl Network {
         }
     }
     pub fn _print_debug(&self) {
-        for y in 0..self.len_y {
-            println!("{}", (0..self.len_x).map(|x| {
-                match self.map.get(&Coord { x, y }) {
-                    None => "(..,..)".to_string(),
-                    Some(node) => {
-                        format!("({:2},{})", node.risk, match node.cumulative_risk {
-                            None => "..".to_string(),
-                            Some(cum_risk) => format!("{:2}", cum_risk)
-                        })
-                    }
-                }
+        for row in self.0.rows() {
+            debug!("{}", row.iter().map(|node| {
+                format!("({:2},{})", node.risk, match node.cumulative_risk {
+                    None => "..".to_string(),
+                    Some(cum_risk) => format!("{:2}", cum_risk)
+                })
             }).collect::<Vec<String>>().join(""))
         }
     }
-    fn get_cum_risk(&self, node: &Coord) -> u32 {
-        self.map.get(node).unwrap().cumulative_risk.unwrap()
+    fn get_cum_risk(&self, node: Pos) -> u32 {
+        self.0[node].cumulative_risk.unwrap()
     }
-    fn get_final_coord(&self) -> Coord {
-        Coord { x: self.len_x - 1, y: self.len_y - 1 }
+    fn get_final_coord(&self) -> Pos {
+        Pos { x: self.0.width() - 1, y: self.0.height() - 1 }
     }
-    fn get_solution(&self) -> Vec<Coord> {
-        let mut result: Vec<Coord> = Default::default();
-        let mut coord: Coord = Default::default();
+    fn get_solution(&self) -> Vec<Pos> {
+        let mut result: Vec<Pos> = Default::default();
+        let mut coord: Pos = Default::default();
         let end = self.get_final_coord();
         while coord != end {
             if result.contains(&coord) {
                 panic!("invalid solution");
             }
             let last_coord = coord;
-            coord = self.neighbors(&last_coord).min_by(|a, b| self.get_cum_risk(a).cmp(&self.get_cum_risk(b))).unwrap();
+            coord = self.0.neighbors4(last_coord).min_by_key(|&neighbor| self.get_cum_risk(neighbor)).unwrap();
             result.push(last_coord);
         }
         result.push(end);
 
         result
     }
-    fn solve_node(&mut self, coord: &Coord) -> bool {
-        let min_neighbor = self.neighbors(coord).filter_map(|neighbor| {
-            let node = self.map.get(&neighbor).unwrap();
+    fn solve_node(&mut self, coord: Pos) -> bool {
+        let min_neighbor = self.0.neighbors4(coord).filter_map(|neighbor| {
+            let node = &self.0[neighbor];
             node.cumulative_risk.map(|val| val + node.risk)
         }).min();
         if let Some(min) = min_neighbor {
-            let current = self.map.get_mut(coord).unwrap();
+            let current = &mut self.0[coord];
             if current.cumulative_risk.is_none() || min < current.cumulative_risk.unwrap() {
                 current.cumulative_risk = Some(min);
                 return true;
@@ -184,16 +168,17 @@ impl Network {
         false
     }
     pub fn solve(&mut self) {
-        self.map.get_mut(&self.get_final_coord()).unwrap().cumulative_risk = Some(0);
+        let end = self.get_final_coord();
+        self.0[end].cumulative_risk = Some(0);
         let mut i = 0u32;
         loop {
-            println!("iteration {}", i);
+            trace!("iteration {}", i);
             // self._print_debug();
             let mut any_change = false;
 
-            for y in (0..self.len_y).rev() {
-                for x in (0..self.len_x).rev() {
-                    if self.solve_node(&Coord { x, y }) {
+            for y in (0..self.0.height()).rev() {
+                for x in (0..self.0.width()).rev() {
+                    if self.solve_node(Pos { x, y }) {
                         any_change = true;
                     }
                 }
@@ -205,145 +190,43 @@ impl Network {
             i += 1;
         }
     }
+    /// Tiles the map `times` times in each direction, each tile one risk level higher than
+    /// the one to its left or above, wrapping from 9 back to 1.
     pub fn expand(&mut self, times: usize) {
-        let get_next_num = |current_num: u32| current_num % 9 + 1;
-        let len_x = self.len_x;
-        let len_y = self.len_y;
-        self.len_x *= times;
-        self.len_y *= times;
-        for xi in 1..times {
-            for x in 0..len_x {
-                for y in 0..len_y {
-                    let next_x = x + xi * len_x;
-                    let last_x = x + (xi - 1) * len_x;
-
-                    let curr_num = self.map.get(&Coord { x: last_x, y }).unwrap().risk;
-                    let next_num = get_next_num(curr_num);
-                    self.map.insert(Coord { x: next_x, y }, Node {
-                        risk: next_num,
-                        cumulative_risk: None,
-                    });
-                }
-            }
-        }
-        for yi in 1..times {
-            for x in 0..self.len_x {
-                for y in 0..len_y {
-                    let next_y = y + yi * len_y;
-                    let last_y = y + (yi - 1) * len_y;
-
-                    let curr_num = self.map.get(&Coord { x, y: last_y }).unwrap().risk;
-                    let next_num = get_next_num(curr_num);
-                    self.map.insert(Coord { x, y: next_y }, Node {
-                        risk: next_num,
-                        cumulative_risk: None,
-                    });
-                }
-            }
-        }
+        let (len_x, len_y) = (self.0.width(), self.0.height());
+        self.0 = Grid::from_fn(len_x * times, len_y * times, |Pos { x, y }| {
+            let risk = self.0[Pos { x: x % len_x, y: y % len_y }].risk;
+            let steps = (x / len_x + y / len_y) as u32;
+            Node::new((risk - 1 + steps) % 9 + 1)
+        });
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input), 40);
+        assert_eq!(Day15::part2(&input), 315);
+    }
 
-fn _get_input() -> &'static str {
-    "6851182918317729984894479998919639588287499951229786985839869531985173849166964176451789434892199898
-9426994946481789516799194682986158574783332188959768397416981269349655657969713682881596193639995681
-3796886996869882629998132915973989548786867985998963691489853939899277919988858417991899981198719977
-9248185139261798695718986776781989149442673212774599795188279468121787972899969696979856585911949398
-6299473779566125911813915682136997199718287928169899397951248974185914193847568951998753279193952849
-8148286974115181837394769599139891971978995999535739268969879319467496912998861171928998691659198989
-7714687982854699756819437711488839929337718978941199979812279537259321914799597345927229986211994549
-1859191762117353799861888195973613898594811595799719427974391939471929349466985865972994437884976918
-4789924996582993992899958998398993587621899599782949299194867972848998592987992641836281142799987191
-9685322981798759174989138791499887198763891994992689973776397683579958996897917454769885649448846998
-6789191274213349898637897949987691642999121985912278669499497294631978879989687988772597899489398853
-8899541187936687986764981174139122881959961816983885493199685939951265698122877778946175159918528939
-9952699986439995635899891246126799752986783919995866921811257898893149769954463369478989997852998513
-9931425891581899192381949272615788327478998239344791911714828896265671432569198189774762995997816999
-8581618784927336497999229995697796195918393877818976787576616927699141958127928917996839499769899276
-8369858669942937955912211816442699772683941894396427761767385991988919159299218913385817898299451225
-6599864914998939412789949497399559686786199654269695968467612419638975799587182839689615354288517687
-4324765578179939112987917815945377869195454495959997291949517521699691991554697624199589939789426676
-8998943917666297991734661946185856692979675845962856849349289995835969527961564598586189299197239649
-2474349998759125929824899596169699498194929969789495971989693353319989798589959994997191896775279666
-9397823311992847298999315312637987993188796995292697771188653763819292898624997515579394598123986699
-2999757939894996746879469973889874978918536847939782945591488939679598979779585959621881889632813415
-3786879979379639997228893154199891799657145859599524224228186163438961996693568885878772218711991948
-6357119476898367292134799994558999999959123579815889388789382591263761963299898988162997119173898949
-3499971911939119191979976923829738974229559772757989872536949884296992794587989427754799689998947769
-7319799654652819812812891758592372613871898598499998998769988491199991938858991878959745381157531992
-1468684961945168589969992498649126855459472584987975891483519539678195959954998486675329191189898981
-5999796535732847747899499629559999481989991291183338213553138881392987929751589694818513519186515989
-8969971877279154679687599942839999789819924199219972613989945683512875795799895813819961923866923969
-7661989481759992932237691998849721793391539266789486647194998941891234931877977977291988998583745959
-7581858699779845989999894928667969596789187889414876715696938619495386625719891737397925889147875514
-7939582575855863146589222872971958448862199919156447969996578964676999478369392994395941769419715726
-7664919588599891289517773626649579918479785644186954919517715835999961894979829998196877229313779119
-9789939779919686938527689899911589971279559121991792659929991899988138467398979998119486996973985156
-1987113452341888331415788983939823974158899719369158953599328364987895258517579998193927999942926739
-4968168491589593364854994479548794995979997169937913916259791732978999679787499897141429969268954257
-2846767823761639989966699865459895813415497745932517967796994194889193889361771837818891946316824181
-7419953896616283991529356659738189899382679976716488911915929762836929928489468999824399891189396596
-5952664821957739692491929127834595859858331611949787357394798996537991479836188819992989786289969399
-1875298894394183776949938912979573795149279972888168285819492911812997337919989696879199478973129985
-5919286823859696987961954614228358927895892883936514998729698954459791996176462566948885569496951888
-8198742852798283579147798488755458898399659854999332299918738949995991969769999951928783929327999171
-9356681651112169821995754898943385719988988159815719149883958216777218975771961399992179627838993749
-9571678692979785159914996299396819198518738829982883267189677891687969791655296999744818979781699451
-4499977822999798831749658798996911398289176643292258219953597143758461199752382581496999738132666972
-9371498489873252352913928911896998598525174889888961698129512999956967836792982999299998439489166519
-4363251581111888995249439374596968896688369878747656498596794989464319829991922479788173765678766788
-2149531197725793856199668589529279498739416981259391319742193112799977998589916896736999211478384818
-6652741812929838829999115348994923982959168836989491996592899776771846988482819696478299997597368387
-4255583648819672318175149619998988681895817968598241979393478469969938159217895591146981673999473867
-4964555898411593799435321739362869694829778589898553641198115978192569798997991195198219994889942184
-9797366423999199172399876696993339889797973193816318914986338494449193894889268661898912981918519764
-6687998138292477915197983479277646898952986899794571647684979994699999899996393165938937915729788661
-5592847856869753492492988981958829593838157925698792597975879647828769919877219918928989182175476897
-8836169198218249896917891911584981355957979918799561597841964324997517581868875971228445214896956594
-9991979957665149888945894282198999633228697249992299394919785958996859286299898899826491976111378973
-8848142494911863639727694919999799681883967728699792269971997284185487197797785915559194647997869275
-7779759189379664999139782196969999463628798838258386616919985751671579842779877794976116569578825591
-6759492767112759386944996961961199796599149618819367999459973299889229748688186194846439389594989398
-9478522495191693824889482998593861917975499679652969194928977177298698588548892995776988755536949638
-2879488429758945861481941459959489291581529327199976734964826343186951997124229199439599789499381947
-9151999467195176382792787942529393148979898385319488971788696876995382432981616171947541994599784815
-9422899387497981899948996839296997955966599469284618798836617528898869693178818849992862393169691499
-3518359996372825883819125785878945215415985684479731169786945848979998959879287967949991364229895176
-8976542697288796999257999123221978979998993991724599398569918592973729173898896524714495376969849988
-8481395559655988985348879919869898925158361718297615446995721768592296998993331259749599564939998425
-7927133913879753889999999849936931818946496994884618913769899788998278717672924799738991955934679754
-7699577192967981896479118595952398684291929899991882868845894217211129138513385871694897411152572986
-9188998494989968854898371859371587419885768992189979865782478339813897498999329756549179599938818766
-9521753599829529719894566318278177879536194823136972369932987791734696899629168989926724859848578771
-5551889549448389591869749924679853719451949199598259995443847415337788294519337599819858357135158969
-7886393199129989719728316889859919596477729586982989933684389188469986916793178154679354724997978633
-9574965391199192981776471866138979925783199887871999639579151787959787986988925838982865489516921196
-3927797795488688693698845776289481997518191875929418751151995942699998749969776184299873985282792798
-9791559589867397896887412749658968449794651489979158963681479687148898199872939926788748841584889944
-5298496949878243647993929791757821993433319291398398984959365589597369893998489597335214676888568151
-4992986694879568239987681454396787984419592177669488577398821999236959943476185938293298929999691399
-7716811887965378991995647889458718917793892996471748846952649999915673968782252941673739691634491177
-3633766499994828892892398927719985744597875769174939892535589929837872989116313627768291599829479799
-9827698893491749981968648969283729997499897957413829241748399418793319183931281544259179926519539784
-5485613626795937686752924885647181816699936695496996619919897887995593964748977695826489175457928711
-8758592799529969633277938996489979686934788817756997869936958149649599491881641998481939955996939599
-7978479148937288659399182481174686939422862837694916899697299885375945865142389214689596229652185995
-1994663916588999417984895813968577357718789291129992993986921983972374167718984777911379615718952856
-8167148183785798577981975199849898879952959145788668868895228269442866271144898849192759218789396797
-1949781172329888625969136645967199561289712385582378579599119918929787938572139889725453891691963129
-9947178739249111493182923893511575582799998593964983133969459696693991798885993271629469499146876976
-9772972829559895899854497885948559996164996778838938289361994913976481619178529958211969726729538135
-8927428538918682496914684982756792873489511912321968398812989748695991579992269199467887892982259779
-2717218517917675896467119592763877315641496467478259218982588979597999878956958782928839691397643476
-8878998995379374928252616995177747994378369327465533881284867384792667719488999812139487971565957894
-9768786899684874839228498724461971799469899798671499289819194996996577858899886942968565187998919698
-8925519488828789299528988579885957558829879851119599829746182181117497663958391826259185277971643493
-7965716992984766446656439596378997979363981699398999919985811839662782937937946281177857997227269847
-1559379255299972668933386171592188218898432689787739638786199657415499966284812959999289193169124995
-7899899443897639915673626768648226882499987556984997981538887885979994649297111149491991818456198868
-5979961891291523299165957518168425311537399524361897896181687192954231889954771797459935798619599423
-2121416749725397396399792525295616969118499787997149336296239918996723985978919425198144297736819197
-7868796992696828855862952279981147679986829877138887191789385398989849196567297576723547696881529227
-8883726744936598689747689939932818828998719994999581122656889578249517477297783467688719959489399394"
-}
\ No newline at end of file
+    #[test]
+    fn rejects_risk_levels_of_zero() {
+        assert_eq!(Day15::parse("12\n30").err(), Some(ParseError::new(2, 2, "a risk level from 1 to 9")));
+        assert_eq!(Day15::parse("").err(), Some(ParseError::new(1, 1, "a risk level from 1 to 9")));
+    }
+}
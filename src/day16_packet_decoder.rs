@@ -1,27 +1,117 @@
 use std::fmt::{Debug, Formatter};
+use std::ops::Range;
 use std::str::FromStr;
 use self::PacketInfo::{Literal, Operator};
+use crate::error::ParseError;
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    // let _input = "D2FE28";
-    // let _input = "38006F45291200";
-    // let _input = "EE00D40C823060";
-    // let _input = "8A004A801A8002F478";
-    // let _input = "620080001611562C8802118E34";
-    // let _input = "C0015000016115A2E0802F182340";
-    // let _input = "A0016C880162017C3686B18A3D4780";
-    let _input = "C200B40A82";
-    // let _input = "04005AC33890";
-    // let _input = "880086C3E88112";
-    // let _input = "CE00C43D881120";
-    // let _input = _get_input();
-
-    let bits: Bits = _input.parse().unwrap();
-    println!("input: {:?}", bits);
-    let packet: Packet = parse_packet(&bits.0).0;
-    println!("{:?}", packet);
-    println!("sum versions: {}", packet.sum_versions());
-    println!("result: {}", packet.calculate_result());
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+
+    type Input<'a> = Transmission;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let bits: Bits = input.parse()?;
+        debug!("input: {:?}", bits);
+        let packet: Packet = parse_packet(&bits.0, 0)?.0;
+        debug!("{:?}", packet);
+        Ok(Transmission { packet, result: None })
+    }
+
+    fn prepare(transmission: &mut Transmission) -> Result<(), ParseError> {
+        transmission.result = Some(transmission.packet.calculate_result()?);
+        Ok(())
+    }
+
+    fn part1(transmission: &Transmission) -> Self::Part1 {
+        info!("sum versions: {}", transmission.packet.sum_versions());
+        transmission.packet.sum_versions()
+    }
+
+    fn part2(transmission: &Transmission) -> Self::Part2 {
+        let result = transmission.result.expect("the packets are evaluated by Day16::prepare");
+        info!("result: {}", result);
+        result
+    }
+}
+
+/// The outermost packet, and once prepared, the value it works out to.
+#[derive(Debug)]
+pub struct Transmission {
+    packet: Packet,
+    result: Option<u64>,
+}
+
+impl Generate for Day16 {
+    const SIZE: Option<Size> = Some(Size::new("packets", 250));
+
+    /// A random tree of packets as hexadecimal, padded with zeros to whole bytes.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut bits = generate_packet(rng, size).0;
+        bits.resize(bits.len().div_ceil(8) * 8, false);
+        bits.chunks(4)
+            .map(|nibble| char::from_digit(nibble.iter().fold(0, |n, &b| n * 2 + b as u32), 16).unwrap().to_ascii_uppercase())
+            .collect()
+    }
+}
+
+/// Values stay below this, so that sums and products of them can't overflow.
+const VALUE_LIMIT: u64 = 1 << 48;
+
+/// The bits of a packet holding `size` packets in all, including itself, and its value.
+fn generate_packet(rng: &mut Rng, size: usize) -> (Vec<bool>, u64) {
+    let push_number = |bits: &mut Vec<bool>, value: u64, width: usize| bits.extend((0..width).rev().map(|i| value >> i & 1 == 1));
+    let mut bits = vec![];
+    push_number(&mut bits, rng.below(8) as u64, 3);
+    if size == 1 {
+        let groups = rng.range(1..=8) as usize;
+        let value = rng.next_u64() % (1 << (4 * groups));
+        push_number(&mut bits, 4, 3);
+        for group in (0..groups).rev() {
+            bits.push(group > 0);
+            push_number(&mut bits, value >> (4 * group) & 0xf, 4);
+        }
+        return (bits, value);
+    }
+
+    // comparisons take exactly two sub-packets, the others one or more
+    let mut packet_type = if size >= 3 && rng.chance(0.2) { rng.range(5..=7) as u64 } else { rng.range(0..=3) as u64 };
+    let count = if packet_type >= 5 { 2 } else { rng.range(1..=(size as i64 - 1).min(5)) as usize };
+    let mut sizes = vec![1; count];
+    for _ in count..size - 1 {
+        sizes[rng.below(count)] += 1;
+    }
+    let (children, values): (Vec<Vec<bool>>, Vec<u64>) = sizes.into_iter().map(|size| generate_packet(rng, size)).unzip();
+    let mut value = match packet_type {
+        0 => values.iter().sum(),
+        1 => values.iter().try_fold(1u64, |product, &v| product.checked_mul(v)).unwrap_or(u64::MAX),
+        2 => *values.iter().min().unwrap(),
+        3 => *values.iter().max().unwrap(),
+        5 => (values[0] > values[1]) as u64,
+        6 => (values[0] < values[1]) as u64,
+        _ => (values[0] == values[1]) as u64,
+    };
+    if value >= VALUE_LIMIT {
+        packet_type = 3;
+        value = *values.iter().max().unwrap();
+    }
+    push_number(&mut bits, packet_type, 3);
+
+    let child_bits: Vec<bool> = children.concat();
+    if child_bits.len() < 1 << 15 && rng.chance(0.5) {
+        bits.push(false);
+        push_number(&mut bits, child_bits.len() as u64, 15);
+    } else {
+        bits.push(true);
+        push_number(&mut bits, count as u64, 11);
+    }
+    bits.extend(child_bits);
+    (bits, value)
 }
 
 struct Bits(Vec<bool>);
@@ -42,7 +132,9 @@ enum PacketInfo {
 }
 
 #[derive(Debug)]
-struct Packet {
+pub struct Packet {
+    /// How many bits into the transmission the packet starts.
+    offset: usize,
     version: u64,
     packet_type: u64,
     packet_info: PacketInfo
@@ -56,77 +148,97 @@ impl Packet {
             self.version
         }
     }
-    pub fn calculate_result(&self) -> u64 {
+    /// The packet's value; a sum or product beyond 64 bits is reported at the packet that works it out.
+    pub fn calculate_result(&self) -> Result<u64, ParseError> {
         match &self.packet_info {
-            Literal(val) => *val,
+            Literal(val) => Ok(*val),
             Operator(children) => {
-                let mut values = children.iter().map(|p| p.calculate_result());
-                match &self.packet_type {
-                    0 => values.sum(),
-                    1 => values.product(),
-                    2 => values.min().unwrap(),
-                    3 => values.max().unwrap(),
-                    5 => if values.next().unwrap() > values.next().unwrap() { 1 } else { 0 },
-                    6 => if values.next().unwrap() < values.next().unwrap() { 1 } else { 0 },
-                    7 => if values.next().unwrap() == values.next().unwrap() { 1 } else { 0 },
+                let values = children.iter().map(|p| p.calculate_result()).collect::<Result<Vec<u64>, _>>()?;
+                let overflow = |operation: &str| ParseError::new(1, self.offset / 4 + 1, format!("a {} that fits in 64 bits", operation));
+                Ok(match &self.packet_type {
+                    0 => values.into_iter().try_fold(0u64, u64::checked_add).ok_or_else(|| overflow("sum"))?,
+                    1 => values.into_iter().try_fold(1u64, u64::checked_mul).ok_or_else(|| overflow("product"))?,
+                    2 => *values.iter().min().unwrap(),
+                    3 => *values.iter().max().unwrap(),
+                    5 => if values[0] > values[1] { 1 } else { 0 },
+                    6 => if values[0] < values[1] { 1 } else { 0 },
+                    7 => if values[0] == values[1] { 1 } else { 0 },
                     _ => panic!("out of range")
-                }
+                })
             }
         }
     }
 }
 
-fn parse_packet(slice: &[bool]) -> (Packet, usize) {
-    let version: u64 = parse_bits(&slice[0..3]);
-    let packet_type: u64 = parse_bits(&slice[3..6]);
+/// Parses the packet at the start of `slice`, which lies `offset` bits into the transmission, returning it along with
+/// the number of bits it takes up.
+fn parse_packet(slice: &[bool], offset: usize) -> Result<(Packet, usize), ParseError> {
+    // errors point at the hexadecimal digit holding the offending bit
+    let error = |bit: usize, expected: &str| ParseError::new(1, (offset + bit) / 4 + 1, expected);
+    let take = |range: Range<usize>, expected: &str| slice.get(range).ok_or_else(|| error(slice.len(), expected));
+
+    let version: u64 = parse_bits(take(0..3, "a packet version")?);
+    let packet_type: u64 = parse_bits(take(3..6, "a packet type ID")?);
     if packet_type == 4 {
         let mut i = 6;
         let mut bits: Vec<bool> = Default::default();
         loop {
-            bits.extend(&slice[i + 1..=i + 4]);
-            if !slice[i] {
+            let group = take(i..i + 5, "a group of the literal value")?;
+            if bits.len() == 64 {
+                return Err(error(i, "a literal value of at most 64 bits"));
+            }
+            bits.extend(&group[1..]);
+            if !group[0] {
                 break;
             }
             i += 5;
         }
         let value: u64 = parse_bits(&bits);
 
-        (Packet {
+        Ok((Packet {
+            offset,
             version,
             packet_type,
             packet_info: Literal(value)
-        }, i + 5)
+        }, i + 5))
     } else {
         let mut sub_packets: Vec<Packet> = Default::default();
         let mut construction_bits_used = 7;
 
-        if slice[6] {
+        if take(6..7, "a length type ID")?[0] {
             // 11-bit number representing the number of sub-packets
             construction_bits_used += 11;
-            let num_packets = parse_bits(&slice[7..18]);
+            let num_packets = parse_bits(take(7..18, "a number of sub-packets")?);
             for _ in 0..num_packets {
-                let (packet, used) = parse_packet(&slice[construction_bits_used..]);
+                let (packet, used) = parse_packet(&slice[construction_bits_used..], offset + construction_bits_used)?;
                 construction_bits_used += used;
                 sub_packets.push(packet);
             }
         } else {
             // 15-bit number representing the number of bits in the sub-packets
             construction_bits_used += 15;
-            let num_bits = parse_bits(&slice[7..22]) as usize;
+            let num_bits = parse_bits(take(7..22, "a length of the sub-packets")?) as usize;
+            let sub_slice = take(construction_bits_used..construction_bits_used + num_bits, "sub-packets as long as their length")?;
             let mut num_bits_used = 0usize;
             while num_bits_used < num_bits {
-                let (packet, used) = parse_packet(&slice[construction_bits_used + num_bits_used..construction_bits_used + num_bits]);
+                let (packet, used) = parse_packet(&sub_slice[num_bits_used..], offset + construction_bits_used + num_bits_used)?;
                 num_bits_used += used;
                 sub_packets.push(packet);
             }
             construction_bits_used += num_bits_used;
         }
 
-        (Packet {
+        match (packet_type, sub_packets.len()) {
+            (_, 0) => return Err(error(construction_bits_used, "at least one sub-packet")),
+            (5..=7, count) if count != 2 => return Err(error(construction_bits_used, "exactly two sub-packets for a comparison")),
+            _ => {}
+        }
+        Ok((Packet {
+            offset,
             version,
             packet_type,
             packet_info: Operator(sub_packets)
-        }, construction_bits_used)
+        }, construction_bits_used))
     }
 }
 
@@ -135,13 +247,13 @@ fn parse_bits(bits: &[bool]) -> u64 {
 }
 
 impl FromStr for Bits {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut bits: Vec<bool> = Vec::with_capacity(s.len() * 4);
 
-        for c in s.chars() {
-            let val = c.to_digit(16).map_or(Err(()), |val| Ok(val))? as u64;
+        for (i, c) in s.chars().enumerate() {
+            let val = c.to_digit(16).ok_or_else(|| ParseError::new(1, i + 1, "a hexadecimal digit"))? as u64;
             for i in (0..4).rev() {
                 bits.push(2u64.pow(i) & val != 0);
             }
@@ -151,6 +263,67 @@ impl FromStr for Bits {
     }
 }
 
-fn _get_input() -> &'static str {
-    "005473C9244483004B001F79A9CE75FF9065446725685F1223600542661B7A9F4D001428C01D8C30C61210021F0663043A20042616C75868800BAC9CB59F4BC3A40232680220008542D89B114401886F1EA2DCF16CFE3BE6281060104B00C9994B83C13200AD3C0169B85FA7D3BE0A91356004824A32E6C94803A1D005E6701B2B49D76A1257EC7310C2015E7C0151006E0843F8D000086C4284910A47518CF7DD04380553C2F2D4BFEE67350DE2C9331FEFAFAD24CB282004F328C73F4E8B49C34AF094802B2B004E76762F9D9D8BA500653EEA4016CD802126B72D8F004C5F9975200C924B5065C00686467E58919F960C017F00466BB3B6B4B135D9DB5A5A93C2210050B32A9400A9497D524BEA660084EEA8EF600849E21EFB7C9F07E5C34C014C009067794BCC527794BCC424F12A67DCBC905C01B97BF8DE5ED9F7C865A4051F50024F9B9EAFA93ECE1A49A2C2E20128E4CA30037100042612C6F8B600084C1C8850BC400B8DAA01547197D6370BC8422C4A72051291E2A0803B0E2094D4BB5FDBEF6A0094F3CCC9A0002FD38E1350E7500C01A1006E3CC24884200C46389312C401F8551C63D4CC9D08035293FD6FCAFF1468B0056780A45D0C01498FBED0039925B82CCDCA7F4E20021A692CC012B00440010B8691761E0002190E21244C98EE0B0C0139297660B401A80002150E20A43C1006A0E44582A400C04A81CD994B9A1004BB1625D0648CE440E49DC402D8612BB6C9F5E97A5AC193F589A100505800ABCF5205138BD2EB527EA130008611167331AEA9B8BDCC4752B78165B39DAA1004C906740139EB0148D3CEC80662B801E60041015EE6006801364E007B801C003F1A801880350100BEC002A3000920E0079801CA00500046A800C0A001A73DFE9830059D29B5E8A51865777DCA1A2820040E4C7A49F88028B9F92DF80292E592B6B840"
-}
\ No newline at end of file
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::solve;
+    use super::*;
+
+    #[test]
+    fn sums_versions() {
+        for (input, sum) in [
+            ("D2FE28", 6),
+            ("38006F45291200", 9),
+            ("EE00D40C823060", 14),
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            let transmission = Day16::parse(input).unwrap();
+            assert_eq!(Day16::part1(&transmission), sum, "{}", input);
+        }
+    }
+
+    #[test]
+    fn calculates_results() {
+        for (input, result) in [
+            ("D2FE28", 2021),
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            let mut transmission = Day16::parse(input).unwrap();
+            Day16::prepare(&mut transmission).unwrap();
+            assert_eq!(Day16::part2(&transmission), result, "{}", input);
+        }
+    }
+
+    #[test]
+    fn reports_results_beyond_64_bits() {
+        let sum = "020084FFFFFFFFFFFFFFFFFFEF13FFFFFFFFFFFFFFFFFFBC";
+        assert!(Day16::parse(sum).is_ok());
+        assert_eq!(solve::<Day16>(sum).err(), Some(ParseError::new(1, 1, "a sum that fits in 64 bits").on_day(16)));
+        assert_eq!(solve::<Day16>(&format!("06{}", &sum[2..])).err(),
+                   Some(ParseError::new(1, 1, "a product that fits in 64 bits").on_day(16)));
+    }
+
+    #[test]
+    fn reports_malformed_packets() {
+        for (input, error) in [
+            ("", ParseError::new(1, 1, "a packet version")),
+            ("D2FE", ParseError::new(1, 5, "a group of the literal value")),
+            ("12318C6318C6318C6318C621", ParseError::new(1, 22, "a literal value of at most 64 bits")),
+            ("380000", ParseError::new(1, 6, "at least one sub-packet")),
+            ("3A0074BF8A", ParseError::new(1, 10, "exactly two sub-packets for a comparison")),
+            ("38006F4529", ParseError::new(1, 11, "sub-packets as long as their length")),
+        ] {
+            assert_eq!(Day16::parse(input).unwrap_err(), error, "{}", input);
+        }
+    }
+}
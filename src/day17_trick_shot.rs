@@ -1,18 +1,51 @@
-use std::ops::{AddAssign};
 use regex::Regex;
 use lazy_static::lazy_static;
 use std::str::FromStr;
+use crate::error::{parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
+use crate::vector::{Bounds, Vec2};
 
-pub(crate) fn run() {
-    let _input = "target area: x=20..30, y=-10..-5";
-    // let _input = _get_input();
+pub struct Day17;
 
-    let area: Area = _input.parse().unwrap();
+impl Solution for Day17 {
+    const DAY: usize = 17;
 
-    let best_launcher = find_best_launcher(&area);
-    println!("best launcher: {:?}", best_launcher);
-    println!("max height: {}", best_launcher.max_height());
-    println!("valid initial velocities: {}", find_velocity_count(&area));
+    type Input<'a> = Area;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn part1(area: &Area) -> Self::Part1 {
+        let best_launcher = find_best_launcher(area);
+        debug!("best launcher: {:?}", best_launcher);
+        info!("max height: {}", best_launcher.max_height());
+        best_launcher.max_height()
+    }
+
+    fn part2(area: &Area) -> Self::Part2 {
+        let count = find_velocity_count(area);
+        info!("valid initial velocities: {}", count);
+        count
+    }
+}
+
+impl Generate for Day17 {
+    const SIZE: Option<Size> = None;
+
+    /// A target below and to the right of the launcher, wide enough that some horizontal speed drops
+    /// to zero over it, which is what makes the highest shots possible.
+    fn generate(rng: &mut Rng, _size: usize) -> String {
+        let speed = rng.range(8..=25);
+        let stop = speed * (speed + 1) / 2;
+        let (x_min, x_max) = (stop - rng.range(0..=20).min(stop - 1), stop + rng.range(0..=40));
+        let y_max = -rng.range(5..=80);
+        let y_min = y_max - rng.range(5..=50);
+        format!("target area: x={}..{}, y={}..{}", x_min, x_max, y_min, y_max)
+    }
 }
 
 fn find_best_launcher(area: &Area) -> Launcher {
@@ -21,7 +54,7 @@ fn find_best_launcher(area: &Area) -> Launcher {
     loop {
         if let Some(launcher) = try_get_launcher(area, initial_y) {
             working_launcher = Some(launcher);
-        } else if initial_y > area.y_min.abs() * 5 {
+        } else if initial_y > area.0.min.y.abs() * 5 {
             break;
         }
         initial_y += 1;
@@ -32,8 +65,8 @@ fn find_best_launcher(area: &Area) -> Launcher {
 
 fn find_velocity_count(area: &Area) -> usize {
     let mut count = 0usize;
-    for x in 0i32..area.x_max * 10 {
-        for y in -area.y_min.abs() * 10..area.y_min.abs() * 10 {
+    for x in 0i32..area.0.max.x * 10 {
+        for y in -area.0.min.y.abs() * 10..area.0.min.y.abs() * 10 {
             let launcher = Launcher { initial_x: x, initial_y: y };
             if launcher.reaches_area(area) {
                 count += 1;
@@ -45,7 +78,7 @@ fn find_velocity_count(area: &Area) -> usize {
 }
 
 fn try_get_launcher(area: &Area, initial_y: i32) -> Option<Launcher> {
-    for initial_x in 1..area.x_max {
+    for initial_x in 1..area.0.max.x {
         let launcher = Launcher { initial_x, initial_y };
         if launcher.reaches_area(area) {
             return Some(launcher);
@@ -62,28 +95,22 @@ struct Launcher {
 }
 
 struct Probe {
-    speed: Coord,
-    position: Coord,
+    speed: Vec2,
+    position: Vec2,
 }
 
 impl Probe {
     pub fn step(&mut self) {
-        self.position += self.speed.clone();
-        self.speed.x += if self.speed.x > 0 {
-            -1
-        } else if self.speed.x < 0 {
-            1
-        } else {
-            0
-        };
-        self.speed.y -= 1;
+        self.position += self.speed;
+        // drag pulls the horizontal speed towards 0, gravity pulls the vertical speed down
+        self.speed -= Vec2::new(self.speed.x.signum(), 1);
     }
 }
 
 impl Launcher {
     fn launch(&self) -> Probe {
         Probe {
-            speed: Coord { x: self.initial_x, y: self.initial_y },
+            speed: Vec2::new(self.initial_x, self.initial_y),
             position: Default::default(),
         }
     }
@@ -102,12 +129,12 @@ impl Launcher {
     }
     pub fn reaches_area(&self, area: &Area) -> bool {
         let mut probe = self.launch();
-        let lowest_height = area.y_min;
+        let lowest_height = area.0.min.y;
         loop {
             if probe.position.y < lowest_height {
                 return false;
             }
-            if area.contains(&probe.position) {
+            if area.0.contains(probe.position) {
                 return true;
             }
             probe.step();
@@ -115,56 +142,49 @@ impl Launcher {
     }
 }
 
-#[derive(Default, Debug, Clone)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl AddAssign for Coord {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
-struct Area {
-    x_min: i32,
-    x_max: i32,
-    y_min: i32,
-    y_max: i32,
-}
-
-impl Area {
-    pub fn contains(&self, coord: &Coord) -> bool {
-        coord.x <= self.x_max && coord.x >= self.x_min &&
-            coord.y <= self.y_max && coord.y >= self.y_min
-    }
-}
+pub struct Area(Bounds<Vec2>);
 
 impl FromStr for Area {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
             static ref REG: Regex = Regex::new(r"target area: x=(?P<x1>\-?\d+)..(?P<x2>\-?\d+), y=(?P<y1>\-?\d+)..(?P<y2>\-?\d+)$").unwrap();
         }
-        let caps = REG.captures(s).unwrap();
-        let x_min: i32 = caps["x1"].parse().unwrap();
-        let x_max: i32 = caps["x2"].parse().unwrap();
-        let y_min: i32 = caps["y1"].parse().unwrap();
-        let y_max: i32 = caps["y2"].parse().unwrap();
-
-        Ok(Self {
-            x_min,
-            x_max,
-            y_min,
-            y_max,
-        })
+        let caps = REG.captures(s).ok_or_else(|| ParseError::new(1, 1, "'target area: x=<min>..<max>, y=<min>..<max>'"))?;
+        // the solver only shoots right and down, which is where every puzzle puts the target
+        let value = |name: &str, in_reach: fn(i32) -> bool, expected: &str| {
+            let token = caps.name(name).unwrap().as_str();
+            match parse_token::<i32>(s, token, "a 32-bit integer")? {
+                value if in_reach(value) => Ok(value),
+                _ => Err(ParseError::at(s, token, expected)),
+            }
+        };
+        let x_min: i32 = value("x1", |x| x > 0, "a positive x, right of the launcher")?;
+        let x_max: i32 = value("x2", |x| x > 0, "a positive x, right of the launcher")?;
+        let y_min: i32 = value("y1", |y| y < 0, "a negative y, below the launcher")?;
+        let y_max: i32 = value("y2", |y| y < 0, "a negative y, below the launcher")?;
+
+        Ok(Self(Bounds::new(Vec2::new(x_min, y_min), Vec2::new(x_max, y_max))))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn example() {
+        let input = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part1(&input), 45);
+        assert_eq!(Day17::part2(&input), 112);
+    }
 
-fn _get_input() -> &'static str {
-    "target area: x=230..283, y=-107..-57"
-}
\ No newline at end of file
+    #[test]
+    fn rejects_targets_out_of_reach() {
+        assert_eq!(Day17::parse("target area: x=20..30, y=5..10").err(), Some(ParseError::new(1, 26, "a negative y, below the launcher")));
+        assert_eq!(Day17::parse("target area: x=-30..-20, y=-10..-5").err(), Some(ParseError::new(1, 16, "a positive x, right of the launcher")));
+    }
+}
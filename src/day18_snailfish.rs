@@ -2,53 +2,73 @@ use std::fmt::{Debug, Formatter};
 use std::ops::Add;
 use std::str::FromStr;
 use self::SnailfishNumberPosition::{Left, Right};
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
-[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
-[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
-[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
-[7,[5,[[3,8],[1,4]]]]
-[[2,[2,2]],[8,[8,1]]]
-[2,9]
-[1,[[[9,3],9],[[9,0],[0,7]]]]
-[[[5,[7,4]],7],1]
-[[[[4,2],2],6],[8,7]]";
-    let _input = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
-[[[5,[2,8]],4],[5,[[9,9],0]]]
-[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
-[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
-[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
-[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
-[[[[5,4],[7,7]],8],[[8,3],8]]
-[[9,3],[[9,9],[6,[4,9]]]]
-[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
-[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
-    // let _input = _get_input();
-
-    let get_lines = || _input.split('\n').map(|line| line.parse::<SnailfishNumber>().unwrap());
-    let mut lines = get_lines();
-    let mut number = lines.next().unwrap();
-
-    for rhs in lines {
-        println!("  {:?}", number);
-        println!("+ {:?}", rhs);
-        number = number + rhs;
-        println!("= {:?}", number);
-        println!();
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+
+    type Input<'a> = Vec<SnailfishNumber>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, str::parse::<SnailfishNumber>)
+    }
+
+    fn part1(numbers: &Vec<SnailfishNumber>) -> Self::Part1 {
+        let mut lines = numbers.iter().cloned();
+        let mut number = lines.next().unwrap();
+
+        for rhs in lines {
+            debug!("  {:?}", number);
+            debug!("+ {:?}", rhs);
+            number = number + rhs;
+            debug!("= {:?}", number);
+            debug!();
+        }
+        info!("magnitude: {}", number.magnitude());
+        number.magnitude()
+    }
+
+    fn part2(numbers: &Vec<SnailfishNumber>) -> Self::Part2 {
+        let max = (0..numbers.len())
+            .flat_map(|i| (0..numbers.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (&numbers[i] + &numbers[j]).magnitude())
+            .max().unwrap();
+        info!("max magnitude between 2: {}", max);
+        max
+    }
+}
+
+impl Generate for Day18 {
+    const SIZE: Option<Size> = Some(Size::new("snailfish numbers", 100));
+
+    /// Reduced numbers: pairs at most four deep, holding digits.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        fn number(rng: &mut Rng, depth: usize) -> String {
+            if depth == 0 || (depth < 4 && rng.chance(0.4)) {
+                format!("[{},{}]", element(rng, depth + 1), element(rng, depth + 1))
+            } else {
+                rng.range(0..=9).to_string()
+            }
+        }
+        fn element(rng: &mut Rng, depth: usize) -> String {
+            if depth < 4 && rng.chance(0.6) {
+                format!("[{},{}]", element(rng, depth + 1), element(rng, depth + 1))
+            } else {
+                number(rng, 4)
+            }
+        }
+        (0..size).map(|_| number(rng, 0)).collect::<Vec<String>>().join("\n")
     }
-    println!("magnitude: {}", number.magnitude());
-
-    let numbers: Vec<SnailfishNumber> = get_lines().collect();
-    let max = (0..numbers.len())
-        .flat_map(|i| (0..numbers.len()).map(move |j| (i, j)))
-        .map(|(i, j)| (&numbers[i] + &numbers[j]).magnitude())
-        .max().unwrap();
-    println!("max magnitude between 2: {}", max);
 }
 
 #[derive(Clone)]
-struct SnailfishNumber {
+pub struct SnailfishNumber {
     left: SnailfishValue,
     right: SnailfishValue,
 }
@@ -247,9 +267,12 @@ impl SnailfishNumber {
 }
 
 impl FromStr for SnailfishNumber {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.starts_with('[') {
+            return Err(ParseError::at(s, s, "'['"));
+        }
         let mut chars = s.chars();
         chars.next();
 
@@ -274,7 +297,7 @@ impl FromStr for SnailfishNumber {
                     }
                     _ => {}
                 }
-                size += 1;
+                size += c.len_utf8();
             }
 
             size
@@ -283,20 +306,31 @@ impl FromStr for SnailfishNumber {
         let size_left = get_size(&mut chars);
         let size_right = get_size(&mut chars);
 
-        let get_next_value = |slice: &str| {
+        let get_next_value = |slice: &str| -> Result<SnailfishValue, ParseError> {
             if slice.chars().all(|c| c.is_numeric()) {
-                SnailfishValue::Numeric(slice.parse().unwrap())
+                Ok(SnailfishValue::Numeric(parse_token(s, slice, "a number or a pair")?))
             } else {
-                SnailfishValue::Child(Box::new(slice.parse().unwrap()))
+                Ok(SnailfishValue::Child(Box::new(slice.parse().map_err(|e: ParseError| e.relative_to(s, slice))?)))
             }
         };
 
         let mut i = 1usize;
         let slice = &s[i..i + size_left];
-        let left = get_next_value(slice);
-        i += size_left + 1;
+        let left = get_next_value(slice)?;
+        i += size_left;
+        if !s[i..].starts_with(',') {
+            return Err(ParseError::at(s, &s[i..], "','"));
+        }
+        i += 1;
         let slice = &s[i..i + size_right];
-        let right = get_next_value(slice);
+        let right = get_next_value(slice)?;
+        i += size_right;
+        if !s[i..].starts_with(']') {
+            return Err(ParseError::at(s, &s[i..], "']'"));
+        }
+        if i + 1 < s.len() {
+            return Err(ParseError::at(s, &s[i + 1..], "the end of the pair"));
+        }
 
         Ok(Self { left, right })
     }
@@ -338,106 +372,97 @@ enum SnailfishValue {
     Child(Box<SnailfishNumber>),
 }
 
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    const EXAMPLE: &str = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]";
 
-fn _get_input() -> &'static str {
-    "[3,[5,[7,[3,9]]]]
-[[[[7,0],0],[2,[2,8]]],[[[7,8],1],3]]
-[[[[2,7],0],7],4]
-[[2,1],[9,0]]
-[[[[7,1],[3,2]],[[9,8],5]],[2,7]]
-[[[8,9],[[8,7],0]],[[[8,7],[6,3]],[[1,7],[8,9]]]]
-[[8,6],[[9,[1,7]],[6,[3,9]]]]
-[[2,[[5,6],6]],[[4,[5,9]],[3,[4,5]]]]
-[[[[2,0],[1,1]],[6,6]],[[1,9],[[2,7],[6,8]]]]
-[[[4,6],[[6,3],[3,9]]],[[[2,6],[6,1]],[[9,9],[1,5]]]]
-[[[4,[3,1]],3],6]
-[[0,[[5,2],8]],[1,[9,[4,3]]]]
-[[[[8,6],[2,1]],[2,[8,6]]],[[[7,1],[3,9]],0]]
-[[[[4,7],[2,7]],[[8,9],2]],[[[2,4],[7,2]],[3,7]]]
-[[5,[2,2]],[[1,6],[[9,1],[5,0]]]]
-[[5,[[1,2],[6,4]]],[6,8]]
-[[[5,[1,7]],7],[7,[8,1]]]
-[[1,9],[[0,3],[[6,7],[2,4]]]]
-[1,[7,[[0,6],0]]]
-[[[[5,7],9],[[3,2],7]],[[5,1],[9,9]]]
-[[[[0,4],[9,6]],[[8,3],[7,4]]],[7,[6,2]]]
-[[[[1,6],0],[[8,0],[3,4]]],[[3,[0,3]],4]]
-[4,[[7,8],[4,[9,7]]]]
-[[[2,[3,7]],5],[0,[9,9]]]
-[[[2,0],[[5,8],[7,6]]],[[9,[6,2]],[3,2]]]
-[[[3,1],3],[[[3,7],6],[9,8]]]
-[[7,[[2,5],5]],[5,[3,[4,5]]]]
-[[[6,7],6],[2,[[9,3],9]]]
-[[[[5,6],7],[[3,2],5]],[[9,[4,3]],[3,8]]]
-[0,7]
-[[[4,6],[2,9]],[[[7,6],[5,1]],7]]
-[[0,5],[[1,[4,1]],[[7,3],9]]]
-[[[2,[3,8]],5],[[[5,9],8],[7,0]]]
-[[[6,[8,6]],[[3,6],7]],[[2,1],[6,[7,5]]]]
-[[2,[[6,3],[8,9]]],[[[5,6],4],[[7,0],1]]]
-[[[[7,1],[5,6]],8],[[[8,9],4],[8,3]]]
-[[[9,2],[1,0]],0]
-[[5,[5,[8,5]]],4]
-[[3,[5,[4,9]]],3]
-[[8,[[7,7],6]],5]
-[[4,[[5,1],1]],[1,[1,[9,8]]]]
-[[[7,[3,6]],[[2,8],[4,7]]],[[[8,8],[4,0]],[2,4]]]
-[[[[3,6],3],[0,9]],2]
-[[2,8],[[8,[8,6]],[[1,1],[4,5]]]]
-[[2,[1,[1,0]]],[[[6,2],[7,4]],[[7,1],6]]]
-[3,[8,[7,[8,6]]]]
-[[1,0],[[[0,4],[0,5]],[1,5]]]
-[[[[5,0],4],[[7,8],[8,8]]],[[1,7],0]]
-[1,[[[4,1],7],[6,[9,0]]]]
-[[[1,8],2],[[5,5],[8,5]]]
-[[4,[9,[0,6]]],[[[8,9],[4,5]],4]]
-[[[[5,4],[1,7]],[[3,1],[7,9]]],[[[0,8],[4,7]],[[5,9],6]]]
-[[[[8,0],9],4],[[7,[1,3]],5]]
-[[[[5,0],6],[[6,1],8]],[[9,1],7]]
-[[9,[6,[8,8]]],[7,[[7,1],6]]]
-[[[5,[1,5]],[3,[4,2]]],[[[5,2],7],[[6,9],[2,8]]]]
-[[[5,[5,5]],[5,7]],[4,[[2,9],7]]]
-[[[[0,4],0],[[0,6],[3,0]]],[0,[[8,1],2]]]
-[[[7,[4,6]],[[7,2],[4,6]]],[[[9,3],[4,9]],6]]
-[[6,7],7]
-[[[4,1],[8,[1,5]]],[[4,6],0]]
-[[[4,[5,5]],5],[[0,[2,7]],[1,1]]]
-[[[[0,1],3],[6,7]],[4,7]]
-[[4,[6,4]],[[[9,8],1],[9,3]]]
-[[[4,9],0],[[[7,0],[0,9]],[1,[1,0]]]]
-[[[7,9],[[9,5],[6,9]]],[[0,[3,0]],[0,[5,9]]]]
-[9,[[0,0],[[1,9],9]]]
-[[[5,[0,5]],[[9,8],[9,5]]],[[0,[2,5]],7]]
-[[[[5,8],6],9],[[[2,7],7],[[7,8],5]]]
-[[8,[[4,7],6]],2]
-[[[[7,1],[9,0]],[9,[1,7]]],[[8,[6,7]],[2,5]]]
-[[4,[2,9]],8]
-[[[[7,6],[5,3]],[5,[9,7]]],[[6,[8,1]],[[6,4],9]]]
-[[7,[[7,8],4]],[[1,3],[4,[9,7]]]]
-[[[6,[6,7]],[[2,8],3]],[7,[6,[0,3]]]]
-[[9,8],[[0,[4,8]],[[9,1],1]]]
-[[[[4,0],[5,9]],7],[6,[[5,9],[9,6]]]]
-[[8,1],[1,[9,[8,3]]]]
-[[[1,[5,1]],[6,7]],[[5,9],[2,[6,7]]]]
-[[[3,7],[[7,8],1]],[[0,[6,3]],[8,0]]]
-[[5,[[9,3],[1,2]]],7]
-[[[1,[9,9]],3],[[6,4],[4,1]]]
-[[6,[1,[3,6]]],[2,9]]
-[[2,[0,2]],[5,[[9,4],[5,0]]]]
-[[4,[[3,1],[7,0]]],[[9,1],[[5,5],[6,7]]]]
-[[3,[[7,1],[3,4]]],[7,[9,[9,4]]]]
-[[9,9],[[5,4],[[9,7],4]]]
-[[[5,1],8],[[6,7],9]]
-[[[0,[9,5]],[4,3]],[3,2]]
-[[[6,[4,1]],[[8,7],[5,3]]],[[[1,2],5],[[9,2],5]]]
-[[[[7,4],[9,0]],[[1,8],[2,9]]],[[5,[1,9]],[4,0]]]
-[[[4,[3,8]],[[3,3],[2,8]]],[[[1,3],9],[[8,5],6]]]
-[[[[6,4],[7,9]],[[7,6],8]],[7,[9,8]]]
-[[7,[3,5]],7]
-[[[[5,0],[2,3]],[3,7]],[[4,[6,3]],[7,[4,4]]]]
-[[6,[3,[7,6]]],[[[5,8],[8,1]],[3,[1,5]]]]
-[[8,[9,[5,2]]],2]
-[[1,[5,4]],[[7,[8,0]],8]]
-[[[[2,7],4],3],[[1,4],[8,4]]]
-[3,[9,2]]"
-}
\ No newline at end of file
+    const LARGER_EXAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    #[test]
+    fn example() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&input), 3488);
+    }
+
+    #[test]
+    fn larger_example() {
+        let input = Day18::parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&input), 4140);
+        assert_eq!(Day18::part2(&input), 3993);
+    }
+
+    /// Reduced snailfish numbers: no pair inside four others, and no number above 9.
+    fn reduced_number() -> impl Strategy<Value=SnailfishNumber> {
+        let element = (0..10u32).prop_map(|n| n.to_string())
+            .prop_recursive(3, 16, 2, |inner| (inner.clone(), inner).prop_map(|(left, right)| format!("[{},{}]", left, right)));
+        (element.clone(), element).prop_map(|(left, right)| format!("[{},{}]", left, right).parse().unwrap())
+    }
+
+    fn is_reduced(number: &SnailfishNumber) -> bool {
+        let text = format!("{:?}", number);
+        let mut depth = 0;
+        let too_deep = text.chars().any(|c| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            depth > 4
+        });
+        let too_large = text.split(|c: char| !c.is_ascii_digit()).any(|n| n.len() > 1);
+        !too_deep && !too_large
+    }
+
+    proptest! {
+        #[test]
+        fn numbers_print_as_they_parse(number in reduced_number()) {
+            let text = format!("{:?}", number);
+            prop_assert_eq!(format!("{:?}", text.parse::<SnailfishNumber>().unwrap()), text);
+        }
+
+        #[test]
+        fn reducing_twice_changes_nothing(number in reduced_number()) {
+            prop_assert!(is_reduced(&number));
+            let mut reduced = number.clone();
+            reduced.reduce();
+            prop_assert_eq!(format!("{:?}", reduced), format!("{:?}", number));
+        }
+
+        #[test]
+        fn sums_are_reduced(a in reduced_number(), b in reduced_number()) {
+            let sum = &a + &b;
+            prop_assert!(is_reduced(&sum));
+            let mut again = sum.clone();
+            again.reduce();
+            prop_assert_eq!(format!("{:?}", again), format!("{:?}", sum));
+            prop_assert_eq!(format!("{:?}", a + b), format!("{:?}", sum));
+        }
+
+        #[test]
+        fn magnitude_weighs_the_left_three_times(a in reduced_number(), b in reduced_number()) {
+            let pair: SnailfishNumber = format!("[{:?},{:?}]", a, b).parse().unwrap();
+            prop_assert_eq!(pair.magnitude(), 3 * a.magnitude() + 2 * b.magnitude());
+        }
+    }
+}
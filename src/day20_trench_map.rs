@@ -1,228 +1,190 @@
 use std::str::FromStr;
+use crate::animation::{Animate, Recorder};
+use crate::error::{split_at_blank_line, ParseError};
+use crate::generate::{Generate, grid_lines, Rng, Size};
+use crate::grid::{Grid, Pos};
+use crate::picture::{Export, Picture, Rgb};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+pub struct Day20;
 
-#..#.
-#....
-##..#
-..#..
-..###";
-    // let _input = _get_input();
+impl Solution for Day20 {
+    const DAY: usize = 20;
 
-    let mut lines = _input.split('\n');
+    type Input<'a> = (ImageEnhancingAlgorithm, Image);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let algo: ImageEnhancingAlgorithm = lines.next().unwrap().parse().unwrap();
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (algo, image) = split_at_blank_line(input, "a blank line after the algorithm")?;
 
-    lines.next();
-    let mut image: Image = Image::from_iter(lines);
+        let algo: ImageEnhancingAlgorithm = algo.parse()?;
 
-    println!();
-    println!("original:");
-    image.print();
-    println!("lit pixels: {:?}", image.lit_pixel_count());
+        let image: Image = image.parse().map_err(|e: ParseError| e.relative_to(input, image))?;
 
-    for i in 1..=50 {
-        image.enhance(&algo);
-        println!();
-        println!("enhance {}:", i);
+        debug!();
+        debug!("original:");
         image.print();
-        println!("lit pixels: {:?}", image.lit_pixel_count());
+        debug!("lit pixels: {:?}", image.lit_pixel_count());
+        Ok((algo, image))
+    }
+
+    fn part1((algo, image): &Self::Input<'_>) -> Self::Part1 {
+        lit_pixels_after(algo, image, 2)
+    }
+
+    fn part2((algo, image): &Self::Input<'_>) -> Self::Part2 {
+        lit_pixels_after(algo, image, 50)
     }
 }
 
-struct ImageEnhancingAlgorithm([bool; 512]);
+impl Generate for Day20 {
+    const SIZE: Option<Size> = Some(Size::new("rows and columns of the image", 100));
+
+    /// A random algorithm that never lights infinitely many pixels after an even number of steps.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut algo: Vec<bool> = (0..512).map(|_| rng.chance(0.5)).collect();
+        if algo[0] {
+            algo[511] = false;
+        }
+        let pixel = |lit: bool| if lit { '#' } else { '.' };
+        let algo: String = algo.into_iter().map(pixel).collect();
+        format!("{}\n\n{}", algo, grid_lines(size, size, |_, _| pixel(rng.chance(0.5))))
+    }
+}
+
+impl Export for Day20 {
+    /// The image after the 50 enhancements of part 2; the unlit pixels beyond it are left out.
+    fn picture((algo, image): &Self::Input<'_>) -> Picture {
+        Picture::Bitmap(enhanced(algo, image, 50, |_| {}).0)
+    }
+}
+
+impl Animate for Day20 {
+    /// The 50 enhancements of part 2, each drawn as large as the last so that the image grows in place.
+    fn animate((algo, image): &Self::Input<'_>, recorder: &mut Recorder) {
+        const ROUNDS: usize = 50;
+        let (width, height) = (image.0.width() + 2 * ROUNDS, image.0.height() + 2 * ROUNDS);
+        let frame = |image: &Image| {
+            // each round adds a pixel on every side, so earlier images sit further in
+            let margin = ((width - image.0.width()) / 2) as isize;
+            Grid::from_fn(width, height, |pos| {
+                if image.get_pixel(pos.x as isize - margin, pos.y as isize - margin) { Rgb::WHITE } else { Rgb::BLACK }
+            })
+        };
+        recorder.record(|| frame(image));
+        enhanced(algo, image, ROUNDS as u32, |image| recorder.record(|| frame(image)));
+    }
+}
+
+fn lit_pixels_after(algo: &ImageEnhancingAlgorithm, image: &Image, times: u32) -> usize {
+    enhanced(algo, image, times, |_| {}).lit_pixel_count().expect("infinitely many pixels are lit")
+}
+
+fn enhanced(algo: &ImageEnhancingAlgorithm, image: &Image, times: u32, mut on_round: impl FnMut(&Image)) -> Image {
+    let mut image = image.clone();
+    for i in 1..=times {
+        image.enhance(algo);
+        on_round(&image);
+        debug!();
+        debug!("enhance {}:", i);
+        image.print();
+        debug!("lit pixels: {:?}", image.lit_pixel_count());
+    }
+    image
+}
+
+pub struct ImageEnhancingAlgorithm([bool; 512]);
 
 impl FromStr for ImageEnhancingAlgorithm {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut algo = [false; 512];
-        for i in s.chars().enumerate().filter(|(_, c)| *c == '#').map(|(i, _)| i) {
-            algo[i] = true;
+        for (i, c) in s.chars().enumerate() {
+            if i >= algo.len() {
+                return Err(ParseError::new(1, i + 1, "the end of the 512 character algorithm"));
+            }
+            algo[i] = match c {
+                '#' => true,
+                '.' => false,
+                _ => return Err(ParseError::new(1, i + 1, "'#' or '.'"))
+            };
+        }
+        if s.chars().count() < algo.len() {
+            return Err(ParseError::after(s, "512 characters of '#' or '.'"));
         }
         Ok(Self(algo))
     }
 }
 
-struct Image(Vec<Vec<bool>>, bool);
+/// The pixels around the origin, and whether the infinitely many pixels beyond them are lit.
+#[derive(Clone)]
+pub struct Image(Grid<bool>, bool);
 
 impl Image {
     pub fn print(&self) {
-        for line in self.0.iter() {
-            println!("{}", line.iter().map(|&c| if c { '#' } else { '.' }).collect::<String>())
-        }
-    }
-    fn get_neighbors<'a>(&'a self, i: isize, j: isize) -> impl Iterator<Item=bool> + 'a {
-        (j - 1..=j + 1)
-            .into_iter().flat_map(move |j| (i - 1..=i + 1).into_iter().map(move |i| (i, j)))
-            .map(|(i, j)| {
-                if i < 0 || j < 0 {
-                    return self.1;
-                }
-                let i = i as usize;
-                let j = j as usize;
-                if j >= self.0.len() || i >= self.0[j].len() {
-                    self.1
-                } else {
-                    self.0[j][i]
-                }
-            })
+        debug!("{}", self.0.render(|&c| if c { '#' } else { '.' }));
     }
-    fn enlarge_image(&self, image: &mut Vec<Vec<bool>>) {
-        let default_value = self.1;
-        for line in image.iter_mut() {
-            line.insert(0, default_value);
-            line.push(default_value);
+    fn get_pixel(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 {
+            return self.1;
         }
-        image.insert(0, vec![default_value; image[0].len()]);
-        image.push(vec![default_value; image[0].len()]);
+        self.0.get(Pos::new(x as usize, y as usize)).copied().unwrap_or(self.1)
+    }
+    fn get_neighbors(&self, x: isize, y: isize) -> impl Iterator<Item=bool> + '_ {
+        (y - 1..=y + 1)
+            .flat_map(move |y| (x - 1..=x + 1).map(move |x| (x, y)))
+            .map(|(x, y)| self.get_pixel(x, y))
     }
     pub fn enhance(&mut self, algo: &ImageEnhancingAlgorithm) {
-        let mut new_image: Vec<Vec<bool>> = self.0.clone();
-        self.enlarge_image(&mut new_image);
-        for (j, line) in new_image.iter_mut().enumerate() {
-            for (i, cell) in line.iter_mut().enumerate() {
-                let sum: usize = self.get_neighbors(i as isize - 1, j as isize - 1)
-                    .enumerate().filter(|(_, val)| *val)
-                    .map(|(index, _)| 2usize.pow(8 - index as u32))
-                    .sum();
-                *cell = algo.0[sum];
-            }
-        }
+        // the enhanced image grows by one pixel on every side
+        let new_image = Grid::from_fn(self.0.width() + 2, self.0.height() + 2, |pos| {
+            let index = self.get_neighbors(pos.x as isize - 1, pos.y as isize - 1)
+                .fold(0usize, |index, lit| index * 2 + lit as usize);
+            algo.0[index]
+        });
         self.0 = new_image;
-        let sum = if self.1 {
-            (0..9).map(|index| 2usize.pow(8 - index as u32)).sum()
-        } else { 0 };
-        self.1 = algo.0[sum];
+        let index = if self.1 { 511 } else { 0 };
+        self.1 = algo.0[index];
     }
     pub fn lit_pixel_count(&self) -> Option<usize> {
         if self.1 {
             return None;
         }
-        Some(self.0.iter().flat_map(|row| row.iter()).filter(|&&c| c).count())
+        Some(self.0.values().filter(|&&c| c).count())
     }
 }
 
-impl<'a> FromIterator<&'a str> for Image {
-    fn from_iter<T: IntoIterator<Item=&'a str>>(iter: T) -> Self {
-        let mut image: Vec<Vec<bool>> = Default::default();
-        for line in iter {
-            let mut row: Vec<bool> = Vec::with_capacity(line.len());
-            for c in line.chars() {
-                row.push(match c {
-                    '#' => true,
-                    '.' => false,
-                    _ => panic!("invalid image")
-                })
-            }
-            image.push(row)
-        }
-        Self(image, false)
+impl FromStr for Image {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let image = Grid::parse(s, "'#' or '.'", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None
+        })?;
+        Ok(Self(image, false))
     }
 }
 
-fn _get_input() -> &'static str {
-    "####....##..####..##.#.#.##..##...##.####....####.##..#.######.#####..##.#..#..#..###.#..###........#...########...#.#........#...####...#...##...####..###..#..#..##..#####...##.######...###.##.##..#...##....#.#...#.######.##.##.#..#.#..#..####.#####....#.##.####..#.##..#...##.#.#####.#..##......###.#..###.##.#.#..##...###..#..#..#.###...##.#....#.....##...##..#.##.#.#.....###..#.#...####..#..##.####...###...##...###..#....####..####..#.###.###.##.#.#.#.###.##.#.#.#..#.#...##...#.#.##..#..###.##..##.###.##.
-
-#.#....#...#..#.....###.....#...##.#####.#.#.##.#.###.###.##.##.........########......##...#....##..
-..#.#..#...#.###...#..#..#.....###.#.#.##.#.##.#..###.###.#..#.##.#.##.#.#.#...####.#.......#..##...
-##..##...#.#########.###..#####..###.###.####....###.#.#.###.#.#..#..#.#...####..###.#.##.#####.##..
-.#.##....##..#.###..#....#.#..##..####.#..#.#####.#.#...#.....#.##.#.#.#...#..#......#.#.#.##..#.#.#
-##..#.##.#.#.#.##....####.#.#.....#.....#....#....#...###..###..######.#...#.#.....#.#.##...##..##..
-###..#......##.....#..##.#.#...#...#.##..##..###.####...###.#...##.#.#.#.##.#....###...######...#.##
-...##.#..#.#.#.####.####..#.#.#.##.#...#.....#.#..#.#####..###.#...#..#...####...#....##..##..#....#
-.#.#.....##...#.##..#.##..##.#######.##..#...#..#.######.#.##..#..#......###.#..##.#.#...###.##..#..
-.##.#######.##..#...#..###.###...##.##..#.#...#..#.#..###.##.#..#.##.#.#.##.####..#..###.###.##.#.#.
-#..###...#.####.#...##.###...##..#.######..#.#........##.#...#..#.##.#....#####...#..#.#.##.#....###
-#..####.#.#.##..#.#.##...#...###....#...#.....###..##...##.#.##.#....##..##.##.#####.#.#.##.###..##.
-####.####..#.#..###.#..#####......#...####.##.#..##.##..#...#..#.#####...#...#...###.##.##........##
-#.....###..#.###.#...##.#####...###...###.#.##.###.###.##.####..#..#.....#...##.#.....#.##..#..#....
-.##..#.######..#.#.#.....#.#..###.#.####.##.#...##..#..#.#.##..#.....#.####.#.#.#...##.##..#......##
-#.##...###.##.##.#...####.#..#...#.#..#.###.#.#.##.##..###.##.##.#..##..#.#.#.#..#.###.#...###..#.##
-###...#.#.##.#.#.#.##.#.###.##..#.#.##..#.##..####.##..#...##.#.###....###..###..###.#..#.##..#.#.##
-.......###...#####.#####.#.####..#....##.##.###.#.#.#.#.#...#.#.###.####.#..###.####.#.#..#..#######
-#.#........###..###..#...####..##.#..####....#.#...##.#....#..###..##......#..###....###.#.###...#.#
-...#.#....#...##..###.##.####.....####.........#..#..#.#.#.###.#..##.####........###.###.#..#.#...#.
-##.######...###.....#.##..#..##..#.#.#..#...#.#...##.#.##...######.#.#...###..#.########.#.##.###...
-##..#..#..#.##...#.##.........##..#####.#.##.#.####...#.#.#...#...##.####..#...######..#.....##..#..
-...#..#.###..#.##.###...#....#..#...#.###..####.#..######.##..#....##...####...##..#.#.#.###.###....
-.##.###..####.##....#....######...#..####.##...###.##.#.#.##..##.....####.#..#.###.##...#....####.##
-####.######.#..#...###.#.#.#.....####....#.#..#....##...##.#######.#..#..#.######.##......#...##...#
-.#####..#..##...###.###.#.#....###.###.##...#.#...#..#..#..#.........#.##..#.....#....####..####.###
-#..##.#.###.#......##...#.###..##.##.#.#..#.#.##..###.#..........#..#...#.###.###..###.#.#..#..#....
-......#.####..#.####..#....##..####.##..#....##.#.#........#.#####..###....###..###.#..#.#.#.#.##.##
-#..#.....####.#.##.#..#..#...#.#..##..##.#.#.####..#...#..#.#.#.#....#.###.###..##.#.#...###.###.#..
-.##..#..####.##.#...####...#.#.#.#.#.#..#.#...##..#....#.#..#..#.#.##..#.#..##..#.#.#...##..###.##..
-..#.#..#.....##.###.##.#####.......###..##.##..#..###..##..#.....##.#.#.....##.#########.####.#.####
-##....####..#....#.########...##.###....#......#..###..###.#.####.##.#######.#############...#.##.#.
-##..##....#..#..#...#.#.#.##..##..#.###....##..#..##.#.#...#.....#....#..#....#.###.#...#...#.##..##
-##.#..##..#..###.##.....#..#.##...##....#.#..##.#.....#.#...##......##########.....#....#.####......
-.###.#....##..#..##..#####.###.#.##.#.####..###.#.###..#.##....####..#.#...#.#.#..#####...####.##..#
-....#..####..#.......#.#.......##...##.#.###..##.........##..##...##.#..###..#.#....#.####.#...#.###
-#.#.#.####.#.#.#.#....##.#...#..##.#.##..##.#.##..#.#..#.#.#..#####..###.#..#..#.#..##.#...#..#..#.#
-.#..##....##.#...#.......###.#####.#........#.###.###.###..##.###...#.#....#...####.###.##..###.##..
-.#.##.##.#.#..####.#..##.###..##..##.##.#.#####..#..#.#..#.##.#.####.#.##.#...##....#.....#.####.##.
-##.##.#.#########.#....##.##.#####....#..#..###.#..##.#..##.#.#.#######.##.#......##.##..#.####...#.
-#.....#.##...##...##.#.#...###.#####.#.##.####.##.#..#..##.#####.##..###.......#.#...##...#..###.#.#
-.#.#####.##.##.#.#..##...#..#.#####...#.##.####.##.#.#.##.#.##.#..#....#.....###..##.##.##.#.#.#.###
-##.##.#.#.#..#..#..##.#####.#...##.#...##..#.#..#..#.#...#.###..###.#...#..##...###.......#...#.#.#.
-.#.....#..##......#.#..###.#..#.##..##.#.....##.##...#....#.##.#......#.#..#..###..#.#..####.##..###
-...###.....#.##..####.##.#.#...#.......##..###.###..##.#.#....#...#.#..#.###.####..###...#....#...#.
-..#.....#...###.##.##.#....#.##..#.#####..###.####.##..#.##.##.##..#.#.###.#.#.#...##....###.#.##...
-.#########...#...##..###.#.###.#...#...#.###.###..###....###..#.##...##.#.#...#.#.####..#..#...#####
-##..##..#..#....#....#####....###...#.##.###.#.#.#.....#..##..###.#.#..##.#.#..##....#.###.##..#..##
-.....#.##...##......#..#.####....#.##.#####...#..#..#.###..####..#.####.##..#.####.#.#.....###..#..#
-###....##.#..##....#####..#.#####.####....#....##.......#####.#...#.....#..#.###...##.#..#.###..#.##
-#.##..#...##...##.#.###.###.#.#...#...#..#####.#..#.###.#.##.###.####...#.#.##..##.#.######.###..#..
-.#.####...##.###.#.....#.##########.###.#####...###.#...###.#....#..##..##....##.##....#.##...#.##.#
-###..#.#######.##.#####..######......#.#..#.#..#....######.##.###.###.#.##.#.##..#..##..###..##..###
-....###.######.###.....##...#..#...##..###..########.#.##...####....##.###.#.##..##.#.....##.#.###..
-#...#....#..##.#.#.#..#..#.#...#.#..#.###.######.##.####....#.##....##.###.##.##.......#.##..#.##...
-###.#.#.#...#.#.#.#.#..#.####..##..##.##..##.#.##..##.###.####.#####..#.####...##.....#.#.##...#.##.
-###.....#####.#...#.#....##...#..####...##.##.#..#.....#.##.#....######.##.##..#.#..#####.#...##...#
-.#..##.###.###..#######.#.#.###...##.###.#.#.###.#.#####.##......#.##.....#..####.#.##.##.....#..###
-##....##.#.##.#..#.##.###.##.####..#.##...#.#.##..##.#.#...##.#.#.##....#.#.#.....##....######.#.#.#
-...#.##.#.#.#....#.###.#.###...##....#..#..##...#.##.#.....##.#.###...###...#.#...###.#.##.##..##.##
-.#.#..#..#....##....#######..#...##.##.....#..###.##.#..#....#..##.##.#...#####.#....##....######.##
-#.#.##..##..###.....#.##.##.###...###.##.#.#...#....####.......#....#..##.##...##....#...#.####..###
-.#.##..#.....######.##..##.###....#####..#...#.####..##..#.#.###...####....#.##....###.##..######...
-..###.######..###.....#...#.#..##....##..#...######.#.#.#...###.##....#.#..####.#######..##.....#..#
-#...#.#####...#.###..#....#.#....#..#...##.#...#.#..#.##..##..#..##..#.#.###.#.#.##.#.##.......#####
-###..#.##.#.#.#.....##.##.###..####....##..#..#..#.###..##.....#.....######.....#.###.#####.##.#####
-.##..#.#.###.##.#.#.#..#......##...#..##.###..#.###.#.#.#...##.###...#.....#.##.#.#..##.##.#....##..
-##.#..#.#####.##..#.#.##.###....###.......###......#..#...###.##.#.##########.##.##.##.###.#.##.....
-.###.#..#####..##..#.####..#.#..######....####.###..#.##.####..#..#...##.#.#.####.####.#.##..####...
-###.###.#.#.#..##..###..#....##.#..##.###.##.#.#......###....####.#......#.#.#...#.....##......####.
-###.#.####.######..###..##.#.#....#####.#..#....#.##.##..#####.#......#...##....#..#.#.##..####.####
-#..#...####..####.####.#..#.##..#.#.#.####..#.####...##....#..####.#.#........#.##......#.....##.#.#
-..#..#.#####.#...#.###..##.#..#..#..#...#...#...###.#####.#.##..#.######...##.###....#######....#.##
-.#.#........#.##.#.######.###.######..#..##.##.#...#.###.#..####.#.##.######.###..###..........#..##
-..###.#.#..#.####...#.##...#....##############.#.#..####.###.#..##...#####.#.##.#..#####.#..#...###.
-.#####.######.##...#.#.#..###.##..###.#.##.##....####..#.###...#.#.#...#..#..#.#..##......#..##.#...
-.#.#..####.#.###.##..#.#.#.##..##.#..#.########..#..###...##....###.##.#...#.#.#.##.#..#.#..#..#..#.
-.##...#.#.#####......##.####.####...#.#######..#..#...#..#####.#.........#####..#.###.#.#.##...##..#
-#..#.#..#.#..##.#.##.###.#..##.#..#..####.###.##.########.#.##..#.....#..#....#.##..#..######..#####
-.#.#...####..##..####....#.#.###.#.#...##.#.#..####.######..#.#.#.#...#..##...#....#####....####.##.
-###...#.#..##..#.#..###.###...##.#..#.###..#.###..#.#...#.#..#.#..##...##..###.#.#....####..#....#.#
-##....#.###.####..#.###.#..#######.###.....###...#.##....######.#.##..#####...###..#....##...#..###.
-###....####......####.##.#..#...###.##..#...##..#.##..####..###.###..#..#..###..#.###...#.####..#..#
-#...#...##..#...###...#..#####...#####.##...#..#.#####.....#####.......##..####....##.....##..#..##.
-####..####.##..#.#..#####...#.#..##.#..##.###..#..###..#.....##.##..#..#.####.#..#..###.##..##..##..
-####..#......##.#.#.#....#..###..####.##..##.###.##.....#......#.###...#...###.#..#..#.###.##.###...
-..##.####..#..........###.#..##.####..#..##.##..##.####.#.##.####.....#.#.#..####.#####.##...#..####
-..#....#..#.....#.##.#####.####...##.#.#.##..###..#.######.#.....##.######..#..#.##.##.#...#####.###
-........#.#.....#.#.###...#######.#........#.#####.#.####...##.#.##..###...#......#.##.#.#.#..###.##
-....#..##.#..#.###.....#...#..###.####.###.##..##...#.#..#....#.#.###.##.#..#...##.##..##..####..#.#
-.#.#.##...#...#...#.#.#.####.####.#...#.#....#....##..#......#.#####.#.##.##.##.###...#.#####...#..#
-###..##....#......########.##.##.##.##.#....####.###..#..##.##...#..##.#...##..##.####.####.##.#..##
-#......###.###.##.....#.........#....###.#...##.###......##.#.....##..###.....##.##.###....##.#.#..#
-.##.#.#.##.....#..#.#....#####.#.#...#.##..###.#####.###..###.##.....#.#...#..###..##..#...#.#..##..
-########.#.#.##.#.##..#..#.#.#..#.###.#.##.######..#.####...##.##....###..#....#...#..#.#...##...#..
-#....#.#####.#....######...##.#.###.#..####.##.###.###...####..#.#.##...#...##...##....####.#.......
-..###..#..####.##.#.#####...###.#...###...#.#..##..##...##.##.#.##.#.#...##.....#..#.#.######.####.#
-#..###.#..#.#..#.#.....##.#.#.#.#....#.#..#..#.###.#.##...#...##..###.#...#...#######..####..#.....#
-#..####.#####....####.#.#.#.#.##.#.#...#####...##.###..###.#.#............###.####..##..##.###..###.
-...#..#..#...#.#######.#.##..##..#..#.#..###.###..#.#####....#..#...####.#..##..###.##..######.##.##
-##.#..#....#.#..###...#.#########.#####..#...#.####..#.#..#######.#....#.#.#.#..###.#.#..#...#.####."
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn example() {
+        let input = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part1(&input), 35);
+        assert_eq!(Day20::part2(&input), 3351);
+    }
+}
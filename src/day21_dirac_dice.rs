@@ -1,30 +1,57 @@
 use std::collections::HashMap;
+use crate::error::{next_token, parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+
+    type Input<'a> = (Player, Player);
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let mut lines = input.split('\n');
+        let mut next_player = |name: &'static str| -> Result<Player, ParseError> {
+            let line = next_token(input, &mut lines, "a starting position")?;
+            let position = next_token(input, &mut line.split_whitespace().rev(), "a starting position")?;
+            match parse_token(input, position, "a starting position between 1 and 10")? {
+                position @ 1..=10 => Ok(Player::starting_at(position, name)),
+                _ => Err(ParseError::at(input, position, "a starting position between 1 and 10"))
+            }
+        };
+        let player1 = next_player("Player1")?;
+        let player2 = next_player("Player2")?;
+        Ok((player1, player2))
+    }
 
-pub(crate) fn run() {
-    let _input = "Player 1 starting position: 4
-Player 2 starting position: 8";
-    let _input = _get_input();
+    fn part1((player1, player2): &Self::Input<'_>) -> Self::Part1 {
+        let mut player1 = player1.clone();
+        let mut player2 = player2.clone();
+        let mut die: DeterministicDie = Default::default();
 
-    let mut lines = _input.split('\n');
-    let position: u32 = lines.next().unwrap().split_whitespace().last().unwrap().parse().unwrap();
-    let mut player1 = Player::starting_at(position, "Player1");
-    let position: u32 = lines.next().unwrap().split_whitespace().last().unwrap().parse().unwrap();
-    let mut player2 = Player::starting_at(position, "Player2");
-    let mut die: DeterministicDie = Default::default();
+        let result = play(&mut player1, &mut player2, &mut die, 1000);
+        info!("losing player {} had {} points and the die had been rolled a total of {} times: {}", result.loser.name, result.loser.score, result.die.roll_count(), result.loser.score * result.die.roll_count());
+        result.loser.score * result.die.roll_count()
+    }
 
-    let result = play(&mut player1, &mut player2, &mut die, 1000);
-    println!("losing player {} had {} points and the die had been rolled a total of {} times: {}", result.loser.name, result.loser.score, result.die.roll_count(), result.loser.score * result.die.roll_count());
+    fn part2((player1, player2): &Self::Input<'_>) -> Self::Part2 {
+        debug!();
+        let result = play_dirac(player1.clone(), player2.clone());
+        debug!();
+        info!("winning player {} won in {} universes", result.winner, result.universes_won);
+        result.universes_won
+    }
+}
 
-    let mut lines = _input.split('\n');
-    let position: u32 = lines.next().unwrap().split_whitespace().last().unwrap().parse().unwrap();
-    let player1 = Player::starting_at(position, "Player1");
-    let position: u32 = lines.next().unwrap().split_whitespace().last().unwrap().parse().unwrap();
-    let player2 = Player::starting_at(position, "Player2");
+impl Generate for Day21 {
+    const SIZE: Option<Size> = None;
 
-    println!();
-    let result = play_dirac(player1, player2);
-    println!();
-    println!("winning player {} won in {} universes", result.winner, result.universes_won);
+    fn generate(rng: &mut Rng, _size: usize) -> String {
+        format!("Player 1 starting position: {}\nPlayer 2 starting position: {}", rng.range(1..=10), rng.range(1..=10))
+    }
 }
 
 fn play_dirac(player1: Player, player2: Player) -> DiracPlayResults {
@@ -38,7 +65,7 @@ fn play_dirac(player1: Player, player2: Player) -> DiracPlayResults {
             *new_games.entry(game).or_insert(0) += count;
         }
         games = new_games;
-        println!("universes: {:10.3e}", games.values().sum::<usize>());
+        debug!("universes: {:10.3e}", games.values().sum::<usize>());
     }
 
 
@@ -118,7 +145,7 @@ fn play<'a>(player1: &'a mut Player, player2: &'a mut Player, die: &'a mut dyn D
         let rolls: Vec<u32> = (0..3).map(|_| die.roll()).collect();
         let roll_sum: u32 = rolls.iter().sum();
         player.move_forward(roll_sum);
-        println!("{} rolls {} and moves to space {} for a total score of {}", player.name, rolls.iter().map(|v| v.to_string()).collect::<Vec<String>>().join("+"), player.position, player.score);
+        trace!("{} rolls {} and moves to space {} for a total score of {}", player.name, rolls.iter().map(|v| v.to_string()).collect::<Vec<String>>().join("+"), player.position, player.score);
         player.score >= winning_score
     };
     loop {
@@ -173,7 +200,7 @@ trait Die {
 }
 
 #[derive(Clone, Hash, PartialEq, Eq)]
-struct Player {
+pub struct Player {
     position: u32,
     score: u32,
     name: &'static str,
@@ -193,7 +220,17 @@ impl Player {
     }
 }
 
-fn _get_input() -> &'static str {
-    "Player 1 starting position: 2
-Player 2 starting position: 8"
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn example() {
+        let input = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part1(&input), 739785);
+        assert_eq!(Day21::part2(&input), 444356092776315);
+    }
+}
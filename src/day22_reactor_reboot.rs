@@ -1,89 +1,88 @@
 use std::str::FromStr;
+use crate::error::{next_token, parse_lines, parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::reference::Reference;
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "on x=-5..47,y=-31..22,z=-19..33
-on x=-44..5,y=-27..21,z=-14..35
-on x=-49..-1,y=-11..42,z=-10..38
-on x=-20..34,y=-40..6,z=-44..1
-off x=26..39,y=40..50,z=-2..11
-on x=-41..5,y=-41..6,z=-36..8
-off x=-43..-33,y=-45..-28,z=7..25
-on x=-33..15,y=-32..19,z=-34..11
-off x=35..47,y=-46..-34,z=-11..5
-on x=-14..36,y=-6..44,z=-16..29
-on x=-57795..-6158,y=29564..72030,z=20435..90618
-on x=36731..105352,y=-21140..28532,z=16094..90401
-on x=30999..107136,y=-53464..15513,z=8553..71215
-on x=13528..83982,y=-99403..-27377,z=-24141..23996
-on x=-72682..-12347,y=18159..111354,z=7391..80950
-on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
-on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
-on x=-52752..22273,y=-49450..9096,z=54442..119054
-on x=-29982..40483,y=-108474..-28371,z=-24328..38471
-on x=-4958..62750,y=40422..118853,z=-7672..65583
-on x=55694..108686,y=-43367..46958,z=-26781..48729
-on x=-98497..-18186,y=-63569..3412,z=1232..88485
-on x=-726..56291,y=-62629..13224,z=18033..85226
-on x=-110886..-34664,y=-81338..-8658,z=8914..63723
-on x=-55829..24974,y=-16897..54165,z=-121762..-28058
-on x=-65152..-11147,y=22489..91432,z=-58782..1780
-on x=-120100..-32970,y=-46592..27473,z=-11695..61039
-on x=-18631..37533,y=-124565..-50804,z=-35667..28308
-on x=-57817..18248,y=49321..117703,z=5745..55881
-on x=14781..98692,y=-1341..70827,z=15753..70151
-on x=-34419..55919,y=-19626..40991,z=39015..114138
-on x=-60785..11593,y=-56135..2999,z=-95368..-26915
-on x=-32178..58085,y=17647..101866,z=-91405..-8878
-on x=-53655..12091,y=50097..105568,z=-75335..-4862
-on x=-111166..-40997,y=-71714..2688,z=5609..50954
-on x=-16602..70118,y=-98693..-44401,z=5197..76897
-on x=16383..101554,y=4615..83635,z=-44907..18747
-off x=-95822..-15171,y=-19987..48940,z=10804..104439
-on x=-89813..-14614,y=16069..88491,z=-3297..45228
-on x=41075..99376,y=-20427..49978,z=-52012..13762
-on x=-21330..50085,y=-17944..62733,z=-112280..-30197
-on x=-16478..35915,y=36008..118594,z=-7885..47086
-off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
-off x=2032..69770,y=-71013..4824,z=7471..94418
-on x=43670..120875,y=-42068..12382,z=-24787..38892
-off x=37514..111226,y=-45862..25743,z=-16714..54663
-off x=25699..97951,y=-30668..59918,z=-15349..69697
-off x=-44271..17935,y=-9516..60759,z=49131..112598
-on x=-61695..-5813,y=40978..94975,z=8655..80240
-off x=-101086..-9439,y=-7088..67543,z=33935..83858
-off x=18020..114017,y=-48931..32606,z=21474..89843
-off x=-77139..10506,y=-89994..-18797,z=-80..59318
-off x=8476..79288,y=-75520..11602,z=-96624..-24783
-on x=-47488..-1262,y=24338..100707,z=16292..72967
-off x=-84341..13987,y=2429..92914,z=-90671..-1318
-off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
-off x=-27365..46395,y=31009..98017,z=15428..76570
-off x=-70369..-16548,y=22648..78696,z=-1892..86821
-on x=-53470..21291,y=-120233..-33476,z=-44150..38147
-off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
-    let _input = _get_input();
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+
+    type Input<'a> = Vec<Cuboid<i32>>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let instructions: Vec<Cuboid<i32>> = _input.split('\n').map(|line| line.parse::<Cuboid<i32>>().unwrap()).collect();
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, str::parse::<Cuboid<i32>>)
+    }
+
+    fn part1(instructions: &Vec<Cuboid<i32>>) -> Self::Part1 {
+        let mut core: ReactorCoreV2 = Default::default();
+
+        for instruction in instructions.iter().filter(|instruction| instruction.is_within(INITIALIZATION_REACH)).cloned() {
+            core.send_instruction(instruction);
+        }
+        info!("there are {} cubes on", core.on_count());
+        core.on_count()
+    }
+
+    fn part2(instructions: &Vec<Cuboid<i32>>) -> Self::Part2 {
+        let mut core: ReactorCoreV2 = Default::default();
+
+        for instruction in instructions.iter().cloned() {
+            core.send_instruction(instruction);
+        }
+        info!("there are {} cubes on", core.on_count());
+        core.on_count()
+    }
+}
 
-    // let max_n = instructions.iter().flat_map(|i| [i.x_min, i.x_max, i.y_min, i.y_max, i.z_min, i.z_max].into_iter())
-    //     .map(|val| val.abs() as usize)
-    //     .max().unwrap();
-    // let mut core: ReactorCore = ReactorCore::of_size(max_n);
-    //
-    // for instruction in instructions {
-    //     core.send_instruction(instruction);
-    // }
-    //
-    // println!("there are {} cubes on", core.on_count());
+impl Generate for Day22 {
+    const SIZE: Option<Size> = Some(Size::new("reboot steps", 420));
 
-    let mut core: ReactorCoreV2 = Default::default();
+    /// About one step in 20 inside the initialization region, and after those large cuboids far out.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let small = (size / 20).max(1);
+        (0..size)
+            .map(|i| {
+                let (reach, extent) = if i < small { (50, 50) } else { (100_000, 40_000) };
+                let on = i < small.min(10) || rng.chance(0.6);
+                let mut range = |axis: char| {
+                    let min = rng.range(-reach..=reach - 1);
+                    let max = rng.range(min..=(min + extent).min(reach));
+                    format!("{}={}..{}", axis, min, max)
+                };
+                let cuboid = [range('x'), range('y'), range('z')].join(",");
+                format!("{} {}", if on { "on" } else { "off" }, cuboid)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Reference for Day22 {
+    /// Every cube of the initialization region switched one by one; the full reactor is far too large for that.
+    fn reference_part1(instructions: &Vec<Cuboid<i32>>) -> Option<Self::Part1> {
+        let mut core: ReactorCore = ReactorCore::of_size(INITIALIZATION_REACH as usize);
+
+        for instruction in instructions.iter().cloned() {
+            core.send_instruction(instruction);
+        }
+        Some(core.on_count())
+    }
 
-    for instruction in instructions {
-        core.send_instruction(instruction);
+    fn reference_part2(_instructions: &Vec<Cuboid<i32>>) -> Option<Self::Part2> {
+        None
     }
-    println!("there are {} cubes on", core.on_count());
 }
 
+/// How far the reactor reaches from its centre along each axis, which keeps the count of cubes within a `usize`.
+const REACTOR_REACH: i32 = 1 << 20;
+
+/// How far the initialization region reaches from the centre of the reactor along each axis.
+const INITIALIZATION_REACH: i32 = 50;
+
 #[derive(Clone)]
 struct SubSection {
     min: i32,
@@ -190,7 +189,7 @@ enum InstructionLevel {
     Z,
 }
 
-struct ReactorCoreV2(Vec<SubSection>);
+pub struct ReactorCoreV2(Vec<SubSection>);
 
 impl Default for ReactorCoreV2 {
     fn default() -> Self {
@@ -207,9 +206,8 @@ impl ReactorCoreV2 {
     }
 }
 
-struct ReactorCore(Vec<Vec<Vec<ReactorCell>>>);
+pub struct ReactorCore(Vec<Vec<Vec<ReactorCell>>>);
 
-#[allow(dead_code)]
 impl ReactorCore {
     pub fn of_size(n: usize) -> Self {
         let n = 2 * n + 1;
@@ -246,16 +244,19 @@ impl ReactorCore {
     }
 }
 
+#[derive(Debug, Clone)]
 enum OnOff {
     On,
     Off,
 }
 
+#[derive(Debug, Clone)]
 enum Instruction {
     TurnOnOff(OnOff)
 }
 
-struct Cuboid<T> {
+#[derive(Debug, Clone)]
+pub struct Cuboid<T> {
     instruction: Instruction,
     x_min: T,
     x_max: T,
@@ -265,30 +266,45 @@ struct Cuboid<T> {
     z_max: T,
 }
 
+impl Cuboid<i32> {
+    fn is_within(&self, reach: i32) -> bool {
+        [self.x_min, self.x_max, self.y_min, self.y_max, self.z_min, self.z_max].iter().all(|value| value.abs() <= reach)
+    }
+}
+
 impl FromStr for Cuboid<i32> {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split_whitespace();
-        let instruction: Instruction = match parts.next().ok_or(())? {
+        let instruction: Instruction = match next_token(s, &mut parts, "'on' or 'off'")? {
             "on" => Instruction::TurnOnOff(OnOff::On),
             "off" => Instruction::TurnOnOff(OnOff::Off),
-            _ => return Err(())
+            other => return Err(ParseError::at(s, other, "'on' or 'off'"))
         };
-        let mut parts = parts.next().ok_or(())?.split(',');
-        let mut parse = || {
-            let text = parts.next().ok_or(())?;
-            let equals_index = text.find('=').ok_or(())?;
-            let mut parts = text[equals_index + 1..].split("..");
-            let min: i32 = parts.next().ok_or(())?.parse().or(Err(()))?;
-            let max: i32 = parts.next().ok_or(())?.parse().or(Err(()))?;
+        let mut parts = next_token(s, &mut parts, "a cuboid")?.split(',');
+        let mut parse = |axis: &str| -> Result<(i32, i32), ParseError> {
+            let text = next_token(s, &mut parts, &format!("'{}=<min>..<max>'", axis))?;
+            let range = text.strip_prefix(axis).and_then(|text| text.strip_prefix('='))
+                .ok_or_else(|| ParseError::at(s, text, format!("'{}='", axis)))?;
+            let mut parts = range.split("..");
+            let min_token = next_token(s, &mut parts, "a 32-bit integer")?;
+            let max_token = next_token(s, &mut parts, "'..' and a 32-bit integer")?;
+            let coordinate = |token: &str| match parse_token::<i32>(s, token, "a 32-bit integer")? {
+                value if (-REACTOR_REACH..=REACTOR_REACH).contains(&value) => Ok(value),
+                _ => Err(ParseError::at(s, token, format!("a coordinate within {} of the centre", REACTOR_REACH))),
+            };
+            let (min, max) = (coordinate(min_token)?, coordinate(max_token)?);
+            if max < min {
+                return Err(ParseError::at(s, max_token, "a maximum no less than the minimum"));
+            }
 
             Ok((min, max))
         };
 
-        let (x_min, x_max) = parse()?;
-        let (y_min, y_max) = parse()?;
-        let (z_min, z_max) = parse()?;
+        let (x_min, x_max) = parse("x")?;
+        let (y_min, y_max) = parse("y")?;
+        let (z_min, z_max) = parse("z")?;
 
         Ok(Self {
             instruction,
@@ -328,425 +344,125 @@ impl ReactorCell {
     }
 }
 
-fn _get_input() -> &'static str {
-    "on x=-26..26,y=-40..10,z=-12..42
-on x=-8..45,y=-39..12,z=-3..48
-on x=-32..16,y=-17..29,z=-46..5
-on x=-37..13,y=-18..29,z=-29..24
-on x=-6..40,y=-14..32,z=-30..15
-on x=-19..32,y=-1..45,z=-46..5
-on x=-43..5,y=-15..34,z=-29..15
-on x=-8..44,y=-30..20,z=-19..29
-on x=0..49,y=-19..28,z=-10..37
-on x=2..47,y=-29..25,z=-31..22
-off x=-27..-9,y=7..23,z=-21..-12
-on x=-21..27,y=-17..35,z=-25..27
-off x=-7..2,y=-7..11,z=30..45
-on x=-47..-3,y=-45..0,z=-40..9
-off x=-45..-27,y=5..19,z=6..17
-on x=-12..38,y=4..49,z=-23..26
-off x=-1..18,y=-5..5,z=23..41
-on x=-49..-3,y=-38..11,z=-2..47
-off x=-42..-28,y=26..36,z=-16..2
-on x=-25..27,y=-32..19,z=-29..25
-on x=-13519..23757,y=-74578..-63294,z=28613..42331
-on x=-73883..-62685,y=25486..36572,z=29381..36985
-on x=34666..50755,y=39751..58608,z=-64293..-48481
-on x=25323..32697,y=-27159..-7771,z=-76638..-55113
-on x=-24870..-4475,y=74100..80623,z=-20353..-4874
-on x=-4372..10486,y=-77039..-59174,z=45466..66326
-on x=8338..34031,y=56264..66755,z=42418..65522
-on x=54927..76019,y=20218..43744,z=-25434..-5284
-on x=-10216..-3803,y=-78013..-46248,z=43661..64676
-on x=-16197..8130,y=-86568..-70560,z=-32171..-18366
-on x=3686..14825,y=36366..56596,z=64859..86585
-on x=15267..36496,y=-36221..-16245,z=-83648..-54644
-on x=18857..35557,y=-42236..-10692,z=64926..86659
-on x=-35346..-21700,y=-70946..-53033,z=-64398..-44682
-on x=24876..54140,y=-63509..-44757,z=-50694..-42584
-on x=13497..33559,y=-32529..-26587,z=-76777..-64069
-on x=20864..35840,y=-73622..-49591,z=31178..56962
-on x=56411..83939,y=33438..51234,z=-33279..-2361
-on x=628..9508,y=-4233..29197,z=76576..81569
-on x=-26696..2126,y=-53972..-42314,z=-76998..-51018
-on x=-72946..-68388,y=12200..33446,z=22310..35870
-on x=-47225..-26296,y=-73984..-51888,z=-15363..6512
-on x=-20965..-7917,y=-84727..-60725,z=-32120..-15589
-on x=-62661..-32698,y=-24126..-10011,z=42978..67870
-on x=39267..72031,y=-61785..-47360,z=524..27273
-on x=-6412..32239,y=47133..67782,z=-67141..-45882
-on x=21338..41800,y=-92024..-59673,z=5827..33231
-on x=20483..41277,y=-32580..-12168,z=60513..76175
-on x=-79227..-56314,y=-28586..-4072,z=-49415..-16693
-on x=-41738..-23211,y=3200..21233,z=-91936..-57956
-on x=13414..35398,y=65132..74486,z=22194..28323
-on x=-69860..-60785,y=20666..42875,z=26414..49975
-on x=-69213..-58197,y=-20455..-1639,z=41438..63419
-on x=34471..48719,y=55773..61442,z=41135..53326
-on x=-20628..13679,y=-23014..-7671,z=61056..93020
-on x=-93588..-73321,y=7679..27991,z=-8516..12731
-on x=-86126..-55180,y=22505..34683,z=18737..38994
-on x=12878..34741,y=-31053..-17055,z=57651..92140
-on x=44021..54879,y=-50088..-12146,z=47263..55368
-on x=-44454..-27516,y=67984..87845,z=7142..22662
-on x=-59315..-33129,y=-2540..11872,z=57915..73149
-on x=44566..71491,y=23377..49196,z=-54876..-18335
-on x=39713..47849,y=48490..72788,z=-47712..-11916
-on x=-13060..-4111,y=57727..93672,z=8513..22413
-on x=23260..50240,y=17804..20873,z=48186..65039
-on x=-10510..22914,y=35508..45000,z=60081..88614
-on x=-66399..-36020,y=-56468..-48151,z=-36076..-19560
-on x=-74315..-58239,y=-8624..21398,z=-48310..-37982
-on x=37225..52978,y=-64801..-43756,z=-62507..-50179
-on x=-25353..-10516,y=-3431..8888,z=-77211..-73854
-on x=-57431..-27265,y=-47055..-32580,z=-66155..-51567
-on x=-44254..-21847,y=1606..17576,z=53954..83742
-on x=2047..20168,y=-10271..10650,z=-91449..-76503
-on x=21683..47779,y=24890..40463,z=-69570..-56376
-on x=28619..33887,y=-78408..-61584,z=29423..36478
-on x=-25486..-3491,y=-7016..10326,z=67097..80046
-on x=64191..85687,y=32349..45622,z=-4823..22661
-on x=-21397..-2651,y=3855..32663,z=58414..94759
-on x=23469..55992,y=-25300..7495,z=62405..78295
-on x=49750..86123,y=-3091..12051,z=34774..53219
-on x=60658..80413,y=-3260..18135,z=-49987..-30199
-on x=-94219..-58278,y=-11510..13920,z=-39438..-7083
-on x=-43150..-23316,y=55646..89037,z=-37058..-11591
-on x=57069..81390,y=-28842..-3424,z=-37243..-10887
-on x=-7876..-2961,y=-47236..-25662,z=-87109..-67102
-on x=32897..44014,y=7361..25398,z=57379..83590
-on x=-38635..-6599,y=-32595..-24530,z=69582..73343
-on x=44680..66891,y=-55384..-47217,z=32347..51868
-on x=-11124..14063,y=20776..48879,z=-90258..-69305
-on x=22643..48759,y=-69794..-54977,z=-17345..5242
-on x=54406..70867,y=24988..50088,z=-34379..-7153
-on x=-45897..-28510,y=41646..67119,z=-52039..-27756
-on x=-22485..-11585,y=-79530..-54983,z=-46867..-26838
-on x=14366..28137,y=2717..18944,z=-81773..-72876
-on x=-16710..6059,y=59336..88106,z=-49887..-18114
-on x=70123..82453,y=-20453..-8426,z=-33022..-21880
-on x=-94606..-69565,y=318..20151,z=-6976..6621
-on x=-57863..-38410,y=-51157..-39033,z=-52247..-44957
-on x=-35003..-13517,y=-22808..7188,z=76131..81885
-on x=-60249..-39378,y=-29738..-15932,z=44078..57615
-on x=37945..60890,y=-59535..-29338,z=-51418..-45929
-on x=52009..58594,y=29236..68310,z=18084..34525
-on x=-53669..-35869,y=47932..61831,z=-35799..-27756
-on x=-42132..-5879,y=-84409..-65175,z=-2071..24883
-on x=56235..72937,y=2650..9508,z=-49191..-19515
-on x=25417..47508,y=37979..57189,z=39425..61617
-on x=21597..44382,y=-69717..-47779,z=21364..52466
-on x=-92324..-61541,y=-16028..3651,z=-28134..-1487
-on x=-31098..-3773,y=-11347..-3398,z=-93987..-66116
-on x=-91564..-66319,y=-22010..-4521,z=-6302..11088
-on x=-44170..-22816,y=-995..29543,z=56348..83803
-on x=-53940..-27125,y=-70718..-50062,z=-22192..2200
-on x=64001..67816,y=23077..40753,z=10406..30301
-on x=-76658..-59921,y=1189..22964,z=21684..52563
-on x=-61198..-44647,y=-70705..-46490,z=-19059..904
-on x=-22333..-2125,y=-51062..-27407,z=53393..78355
-on x=-19725..-12113,y=-77837..-68113,z=25146..54628
-on x=50197..80342,y=-42401..-20483,z=17692..44063
-on x=8704..39490,y=20095..44486,z=-73043..-55759
-on x=-48072..-13228,y=-21433..-3058,z=-77361..-55746
-on x=-63644..-38019,y=37696..73705,z=-37409..-13182
-on x=-23197..2412,y=-79509..-69819,z=-36808..-27314
-on x=-14237..9950,y=-65420..-51220,z=48650..61798
-on x=-19775..-10083,y=-13310..11877,z=69801..80517
-on x=74940..83787,y=3595..25951,z=-26788..-17792
-on x=60388..84425,y=-13138..5845,z=9156..33763
-on x=12748..41359,y=-18837..-857,z=61501..91111
-on x=-16629..-667,y=52359..80962,z=-43407..-37827
-on x=38915..56085,y=-54759..-47315,z=-56579..-22609
-on x=-54987..-30889,y=-1085..26094,z=-64589..-43106
-on x=-37146..-17481,y=-90050..-72986,z=-13175..-7861
-on x=-38855..-30575,y=56581..77246,z=-46924..-37196
-on x=-26280..6280,y=-6035..22930,z=73945..91785
-on x=-37937..-17500,y=-85311..-69125,z=-19847..9834
-on x=-4624..19664,y=-61468..-41205,z=-67721..-41463
-on x=-69773..-64314,y=-46431..-31480,z=11523..40702
-on x=56409..74144,y=20319..55461,z=10115..33072
-on x=-65182..-61481,y=-52315..-38277,z=6087..29761
-on x=-14969..11073,y=-46944..-36515,z=-82393..-58959
-on x=-55995..-31790,y=48285..59582,z=33655..57910
-on x=-78396..-57065,y=-60694..-40115,z=-8486..18734
-on x=-74719..-52749,y=-60685..-43120,z=-23744..12212
-on x=-21860..4495,y=71910..77838,z=17046..29043
-on x=-88119..-63622,y=-22087..-11349,z=1738..21548
-on x=9969..21104,y=27249..55516,z=53624..82142
-on x=-38219..-13219,y=60739..95661,z=-12457..8170
-on x=6280..29183,y=-91729..-73647,z=-32513..-16302
-on x=62448..88272,y=8035..28993,z=22362..38054
-on x=-61506..-48982,y=2316..20022,z=-50246..-46132
-on x=-47334..-38659,y=-16156..-6964,z=-67289..-62550
-on x=-9413..15094,y=-44488..-20463,z=71604..90097
-on x=-90687..-74510,y=-37412..-5048,z=-6015..9794
-on x=-10708..23963,y=-82687..-65591,z=-55560..-32072
-on x=-7658..14219,y=65724..86784,z=20793..30821
-on x=69546..92531,y=25869..40499,z=-31673..394
-on x=-78429..-66418,y=-28526..-23955,z=7852..24488
-on x=-8867..14482,y=-58276..-36025,z=46543..83551
-on x=-66718..-50865,y=24912..39245,z=25718..49321
-on x=45635..67063,y=19004..37049,z=-42406..-23808
-on x=-73205..-62750,y=-33871..-11357,z=31976..46807
-on x=-79095..-59681,y=-34906..-14545,z=-45373..-28303
-on x=52360..85963,y=18968..38417,z=-6500..11124
-on x=-72195..-52776,y=-16549..2659,z=32427..61377
-on x=-18081..-5187,y=-50310..-45056,z=-71100..-57016
-on x=61713..87192,y=6081..25276,z=-37797..-23924
-on x=-64041..-41170,y=42911..62150,z=-42150..-24208
-on x=62700..97477,y=-7120..26940,z=-14481..15227
-on x=-58207..-37804,y=-10694..11540,z=-83680..-52501
-on x=-46546..-27987,y=-72903..-50860,z=34384..60729
-on x=-25194..-16141,y=72634..86799,z=-26349..-3633
-on x=60680..83227,y=-1815..18227,z=2818..15339
-on x=37226..58169,y=-34126..-11216,z=-58604..-36919
-on x=51411..60635,y=-50930..-22923,z=-55296..-28784
-on x=34717..65728,y=52564..80876,z=-527..29174
-on x=-11724..-2109,y=-68389..-47488,z=-67585..-39537
-on x=47687..55806,y=-61274..-46636,z=-41200..-17425
-on x=-12079..15734,y=-14688..3132,z=-96275..-78347
-on x=38345..57236,y=20312..56566,z=-42156..-29151
-on x=22981..50055,y=67205..75663,z=-40930..-15861
-on x=-24383..7122,y=-18321..-6165,z=60980..83630
-on x=-87043..-49290,y=15538..20360,z=27724..56360
-on x=-30611..1259,y=-70391..-60599,z=-55369..-26936
-on x=-28054..-8658,y=-37769..-19369,z=55102..87236
-on x=8585..28386,y=-48115..-30734,z=-67144..-53941
-on x=-56049..-21404,y=-33242..-21479,z=58781..77568
-on x=-48215..-28227,y=-70744..-65796,z=-24578..4903
-on x=58925..68001,y=25452..48287,z=36935..42014
-on x=-47836..-14239,y=-62874..-53243,z=39703..61804
-on x=3874..16628,y=26720..39982,z=65415..86718
-on x=22490..48003,y=56338..86867,z=2002..12924
-on x=-37900..-18714,y=5246..22509,z=-82396..-65335
-on x=-13857..18392,y=22074..47939,z=-82191..-52611
-on x=-22632..-4859,y=37521..60508,z=-85956..-47963
-on x=-14529..13274,y=-49281..-32626,z=66015..68373
-on x=-69184..-56620,y=-56013..-32595,z=-35389..-519
-on x=-20976..-10331,y=66517..79041,z=-21008..-11131
-on x=-79515..-72247,y=2765..29867,z=-38609..-9204
-on x=1425..10201,y=-28142..-25478,z=-78863..-67620
-on x=12213..19071,y=72910..80923,z=-25638..-7782
-on x=29655..57886,y=38312..64422,z=-41403..-30723
-on x=37830..54151,y=-60131..-37497,z=-41890..-23551
-on x=-23172..-3063,y=36224..43297,z=-85980..-51199
-on x=-58790..-31709,y=-69770..-53894,z=-47773..-26724
-on x=49078..79851,y=14797..41060,z=-43639..-30748
-on x=-72281..-60835,y=8560..30057,z=-47343..-18944
-on x=-68877..-35662,y=8881..26284,z=52972..74260
-on x=37161..52280,y=33575..53544,z=-52103..-28232
-on x=-96809..-74703,y=-25319..-7752,z=945..9471
-on x=11864..21766,y=-21953..-1816,z=-93723..-76927
-on x=-68486..-58623,y=3699..23979,z=36087..55936
-on x=36746..51309,y=-16614..8997,z=57761..79511
-on x=-29966..-1334,y=73655..76418,z=7943..45973
-on x=24632..51209,y=-62693..-28343,z=48948..69244
-on x=9277..23574,y=58329..70429,z=26307..47028
-on x=-11922..21967,y=44991..75872,z=41876..64312
-on x=38760..54534,y=27485..53471,z=40659..68468
-on x=14478..42047,y=55594..75698,z=-33661..3063
-on x=62886..71531,y=-7248..2904,z=25754..49578
-on x=72792..78643,y=-27114..-4796,z=7473..14174
-on x=-96609..-61607,y=786..8296,z=-34653..-9646
-on x=-506..9341,y=45889..64625,z=46807..66903
-off x=1984..18319,y=-48433..-24631,z=-80625..-66473
-off x=-86165..-60351,y=24555..37208,z=14111..45959
-on x=-47262..-35147,y=17941..27505,z=-71821..-55595
-off x=-51604..-42274,y=-21524..2506,z=61530..81449
-on x=-91073..-57762,y=-15167..-4535,z=-27342..-13382
-off x=-74561..-63672,y=-27238..-8918,z=27716..46221
-off x=25341..62966,y=-76066..-52116,z=-7642..16126
-on x=41519..62877,y=14535..33182,z=-58712..-43556
-off x=22568..44776,y=-68916..-40772,z=-56050..-34852
-off x=-54321..-37000,y=32306..60237,z=-53323..-37932
-on x=9625..42221,y=-70339..-49822,z=34430..57944
-on x=-47905..-21905,y=-25258..-9081,z=-84499..-62605
-off x=-87852..-66882,y=12586..23894,z=16932..24653
-on x=-43115..-21917,y=-63911..-40538,z=36018..62651
-on x=17862..30911,y=-94669..-71276,z=-12445..18211
-off x=64737..74277,y=-27561..1668,z=27357..43922
-off x=-39230..-28120,y=-70713..-53434,z=26259..43463
-off x=-55623..-36859,y=-78253..-47666,z=-9782..15831
-on x=-50854..-44363,y=22977..33813,z=-54996..-48166
-on x=-79894..-54464,y=-46377..-19156,z=-43855..-14311
-on x=-13767..-3563,y=25279..56950,z=52067..71452
-off x=56851..81429,y=-36688..-18344,z=-28057..-5410
-off x=-63341..-56233,y=18014..35391,z=-56860..-21676
-on x=69294..79328,y=-13667..3283,z=-26354..-9500
-on x=16699..35577,y=34977..51568,z=48651..78311
-off x=29475..48517,y=-19780..-673,z=-91209..-69519
-on x=-40655..-8638,y=-81654..-69383,z=-32826..-24283
-on x=67480..68769,y=-53780..-33219,z=-28866..-6525
-off x=-75339..-73461,y=-15221..12524,z=18952..28946
-off x=-76632..-52812,y=32186..65418,z=-20210..-4148
-off x=-87842..-66545,y=-36246..-16452,z=274..28818
-off x=45032..60550,y=-54783..-33188,z=25296..44769
-off x=-67421..-45392,y=-14289..-3698,z=-60087..-47158
-off x=-15096..15642,y=68857..95152,z=-35857..-19572
-off x=17902..27919,y=-23967..-16698,z=-83826..-68169
-on x=-74182..-52348,y=-44011..-23916,z=9713..42442
-off x=58874..67954,y=-69506..-39465,z=-25816..-3950
-on x=-20760..17275,y=-88661..-58348,z=-40391..-8270
-off x=-15085..-3519,y=50655..66350,z=-70350..-53771
-off x=-63999..-39238,y=-49678..-22826,z=-56234..-43548
-off x=20570..49648,y=32153..47746,z=39801..66099
-on x=-55896..-37608,y=-62433..-60596,z=-4824..24170
-on x=-91524..-63749,y=-25251..-14223,z=-29743..-14582
-on x=21472..37624,y=6459..27750,z=62878..84428
-off x=23493..57655,y=-81898..-50146,z=2419..25867
-on x=-64440..-39976,y=-9298..-7292,z=-61904..-55218
-on x=31569..39076,y=-32249..-10362,z=50181..76170
-off x=-36075..-8766,y=55286..82751,z=-46897..-27260
-off x=-31211..-1214,y=-79338..-60326,z=11975..21540
-on x=-30212..-11722,y=56466..66714,z=-51607..-36530
-on x=-83558..-58583,y=30973..58983,z=8655..24684
-off x=44760..69423,y=-1342..23987,z=-52775..-48184
-on x=-9084..8796,y=-73345..-42008,z=-70806..-36173
-off x=-80320..-70922,y=-3822..25792,z=2311..29479
-off x=-32720..-2779,y=-19520..1900,z=-81983..-68606
-off x=21588..35370,y=-45612..-32419,z=49708..74037
-off x=6479..33735,y=-25221..-4512,z=66029..75935
-off x=42906..75513,y=-70087..-35867,z=-37864..-12995
-on x=-2480..28177,y=66965..87725,z=-9747..21114
-on x=13327..40824,y=60965..81883,z=-17092..-1309
-off x=-76556..-63064,y=-34461..-18538,z=3011..22827
-off x=544..16664,y=-82490..-53175,z=-46628..-33863
-on x=19044..37713,y=-31581..-16845,z=-72051..-61437
-on x=26945..40177,y=-37235..-8846,z=52909..81877
-on x=8558..15979,y=24185..37690,z=-79834..-54590
-off x=72604..86558,y=-21019..-8842,z=-1663..1885
-off x=56126..64573,y=43725..53285,z=-16826..-5200
-on x=72638..82310,y=15712..30392,z=11125..30991
-off x=-38056..-17305,y=-86013..-56830,z=-3548..24863
-off x=-41269..-21571,y=-75314..-51809,z=4692..18543
-off x=59438..77891,y=13318..40543,z=-10187..4776
-off x=2693..27227,y=-22388..10240,z=-85138..-72970
-off x=25912..29234,y=12033..38925,z=-83107..-58863
-off x=45142..53244,y=21490..43065,z=-70075..-42136
-off x=-71642..-46815,y=-58141..-35368,z=-35165..-12300
-on x=10549..43558,y=-84283..-51071,z=-44070..-33910
-off x=-25304..-19092,y=-89451..-72060,z=-38624..-14621
-on x=-43695..-35609,y=67137..82584,z=-27802..-5300
-off x=-12627..8168,y=-59313..-33237,z=-88198..-60590
-on x=36034..41851,y=-31046..-18530,z=-76822..-56158
-off x=25930..35837,y=-64434..-47561,z=35159..42259
-off x=3778..24779,y=-14000..20847,z=63639..86995
-off x=38457..55508,y=-61740..-47905,z=10427..29668
-on x=-11805..13736,y=-93178..-68144,z=2401..29365
-on x=-20677..-5784,y=-22560..-3070,z=72819..85201
-on x=-2160..20480,y=-63933..-41300,z=53880..75386
-off x=-66227..-38926,y=52457..62685,z=-7389..8361
-off x=-1798..16798,y=-84497..-53996,z=-36914..-27462
-on x=15830..40185,y=14978..42495,z=59645..70083
-off x=-91303..-65630,y=5699..40492,z=-1926..12922
-off x=-80987..-68119,y=9483..26402,z=-33198..-18149
-off x=585..28734,y=-78562..-61178,z=16886..39061
-off x=-28951..4957,y=35020..53581,z=-78323..-60548
-on x=24069..51670,y=-20815..5930,z=67685..80019
-on x=31437..39085,y=-62877..-51824,z=40495..48939
-off x=54683..67049,y=32323..44127,z=-22887..-9553
-off x=52415..64465,y=27659..57226,z=-1061..36269
-off x=36976..40873,y=-7871..14992,z=53737..75747
-off x=-48546..-34046,y=-42629..-9852,z=-81953..-65063
-off x=-77295..-43159,y=-17641..2087,z=37001..54928
-on x=25599..43643,y=-30555..-10741,z=-89347..-63221
-on x=-4970..10535,y=-91237..-62910,z=-15491..17748
-on x=60621..74354,y=15823..25840,z=-42994..-38143
-on x=-74711..-45936,y=-5755..19459,z=43766..49457
-off x=-24751..643,y=-74778..-55797,z=-40289..-30584
-on x=49983..75771,y=-22161..-16891,z=30087..58908
-off x=47438..61445,y=24228..30467,z=32644..63904
-on x=-67903..-56061,y=-57120..-39133,z=4608..15176
-off x=-59075..-37658,y=28446..55968,z=-63117..-37598
-off x=27755..50677,y=17943..40083,z=-69367..-54222
-on x=-70601..-55258,y=-6681..20402,z=-51672..-46544
-on x=-27991..-2066,y=-66503..-40799,z=44765..59433
-off x=-20336..-8310,y=-66966..-51875,z=-63908..-48788
-off x=-59503..-56749,y=15868..25769,z=-67317..-32644
-off x=-16438..3281,y=19222..30427,z=-95397..-60415
-on x=-29061..-9813,y=3090..28544,z=72361..91251
-off x=-59232..-47520,y=-8844..-6101,z=55887..60968
-on x=53981..71162,y=-25767..1640,z=-70702..-51620
-on x=-49157..-42332,y=-18205..5845,z=-83797..-44096
-on x=-55305..-52045,y=-73889..-50046,z=-13168..-6786
-off x=-45499..-17320,y=-67559..-30716,z=-57446..-37730
-on x=52992..70450,y=-71813..-42336,z=-10051..19928
-off x=-13330..-1697,y=-64355..-49902,z=-69749..-51695
-on x=-35231..-24993,y=-21063..-109,z=73186..78088
-off x=-46047..-38409,y=49728..73004,z=26708..47898
-on x=59459..83483,y=-20533..1615,z=-35229..-26205
-on x=-16599..-65,y=65387..74988,z=12150..37237
-off x=49723..63745,y=-2668..23147,z=-75785..-47908
-on x=-90064..-65811,y=-21717..3989,z=15281..38512
-on x=1925..25128,y=42874..63589,z=-67553..-49133
-off x=-40577..-31066,y=4470..17605,z=54338..75298
-off x=-2524..7219,y=61141..91424,z=12686..24875
-on x=5100..19123,y=-59047..-38501,z=52394..61828
-on x=-34348..-8896,y=-79309..-63169,z=-21605..-9038
-off x=30191..45773,y=-67036..-45735,z=-60897..-29358
-on x=-75285..-70000,y=4621..22310,z=20730..50215
-off x=-5887..18091,y=-56059..-35445,z=-68668..-56762
-on x=57879..93417,y=-31503..-12810,z=9332..22116
-off x=-36938..-21562,y=-55965..-39235,z=-62057..-52929
-off x=28318..46549,y=-34295..-13076,z=-82263..-57640
-off x=12478..44363,y=-14283..3861,z=-88411..-66423
-on x=6409..18053,y=29926..50471,z=59867..79948
-on x=-12031..4182,y=-55055..-53263,z=42941..67952
-off x=-6360..4883,y=-27198..-147,z=62816..78442
-on x=43775..70882,y=-66138..-44388,z=-49622..-18793
-off x=35444..49622,y=-49865..-39154,z=31319..49830
-off x=-47868..-33167,y=-40473..-11566,z=-79023..-45748
-off x=-80769..-69349,y=16405..30336,z=-1789..28858
-on x=56221..84537,y=-30708..-18518,z=-30445..-27632
-on x=-90129..-54034,y=3234..28428,z=7087..29325
-off x=45057..62340,y=22065..48792,z=46148..61198
-on x=56643..70369,y=-5484..12987,z=41088..69017
-on x=69114..89474,y=-28757..1907,z=-31242..-21152
-on x=17914..33905,y=-19902..-13312,z=-83216..-52872
-on x=-24883..9976,y=-9681..2770,z=-81286..-73623
-off x=-71439..-64177,y=-21184..10097,z=-53129..-30009
-on x=-61375..-47310,y=23678..45682,z=-60793..-52888
-off x=-62935..-32979,y=-75580..-52055,z=7442..14393
-on x=74536..78934,y=-18628..11064,z=5210..20188
-off x=-46598..-12816,y=-76609..-54809,z=24888..49581
-on x=-56241..-33971,y=54620..77102,z=8399..30355
-on x=-47254..-28374,y=35028..51481,z=-64024..-41147
-on x=5313..8174,y=-74638..-49669,z=42812..59795
-off x=-9400..1543,y=71748..85457,z=-21809..-4025
-off x=-68866..-47327,y=33907..61591,z=-28562..373
-off x=31934..49216,y=64459..72449,z=4823..22188
-on x=24901..39620,y=70279..90439,z=-12951..-328
-off x=42799..55765,y=-49253..-23103,z=-65499..-49538
-off x=-29362..-23867,y=55528..72881,z=-53296..-32054
-on x=59512..86893,y=-4752..24530,z=-2764..11365
-on x=49170..80052,y=8640..29516,z=-49125..-41051
-off x=65305..83799,y=-24837..-16069,z=-29938..-7211
-on x=-56885..-20392,y=60715..82107,z=-2354..10395
-off x=-77153..-63663,y=-23426..-9373,z=-38772..-18788
-on x=64958..73071,y=-58119..-40116,z=-16919..-1665
-on x=28807..50453,y=-74233..-52897,z=-44354..-16607
-on x=-57754..-43745,y=52976..75347,z=-34117..-16957
-on x=26876..43650,y=25858..49631,z=-75420..-50249
-off x=38265..44698,y=45248..77425,z=8305..23616
-on x=76870..83189,y=-27066..-6904,z=2131..15011
-off x=28838..45716,y=-25189..-2647,z=-85484..-53210
-on x=-46877..-25517,y=-72501..-50454,z=36420..54610
-off x=27340..48960,y=-68030..-47882,z=-1570..5262
-off x=-9167..6333,y=-84627..-56264,z=-45284..-14868
-on x=32505..43903,y=-46439..-22374,z=42632..59955
-on x=-44942..-11741,y=-53928..-39048,z=-74536..-50637
-on x=-75001..-55747,y=20779..30323,z=-68842..-37470
-on x=64118..76333,y=12148..41495,z=-39987..-20565
-on x=60929..73149,y=-56166..-39315,z=-19870..5937
-off x=-70940..-48134,y=-57369..-30147,z=-1122..22519
-on x=45037..62408,y=18812..31490,z=47472..63563
-off x=68285..92461,y=-5779..15071,z=-11886..11919
-on x=-45196..-20058,y=49310..81448,z=28625..51556
-on x=-2849..16442,y=56092..61544,z=-60845..-50048
-off x=-58186..-48426,y=-65792..-47074,z=-16475..13246
-on x=-81796..-53500,y=-13549..-9691,z=18570..34387
-on x=-1418..15471,y=15928..32934,z=-78873..-67183
-off x=-34327..-5353,y=-59426..-31052,z=56942..69491
-off x=-24230..385,y=31318..48054,z=53608..80757"
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    const EXAMPLE: &str = "on x=-5..47,y=-31..22,z=-19..33
+on x=-44..5,y=-27..21,z=-14..35
+on x=-49..-1,y=-11..42,z=-10..38
+on x=-20..34,y=-40..6,z=-44..1
+off x=26..39,y=40..50,z=-2..11
+on x=-41..5,y=-41..6,z=-36..8
+off x=-43..-33,y=-45..-28,z=7..25
+on x=-33..15,y=-32..19,z=-34..11
+off x=35..47,y=-46..-34,z=-11..5
+on x=-14..36,y=-6..44,z=-16..29
+on x=-57795..-6158,y=29564..72030,z=20435..90618
+on x=36731..105352,y=-21140..28532,z=16094..90401
+on x=30999..107136,y=-53464..15513,z=8553..71215
+on x=13528..83982,y=-99403..-27377,z=-24141..23996
+on x=-72682..-12347,y=18159..111354,z=7391..80950
+on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
+on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
+on x=-52752..22273,y=-49450..9096,z=54442..119054
+on x=-29982..40483,y=-108474..-28371,z=-24328..38471
+on x=-4958..62750,y=40422..118853,z=-7672..65583
+on x=55694..108686,y=-43367..46958,z=-26781..48729
+on x=-98497..-18186,y=-63569..3412,z=1232..88485
+on x=-726..56291,y=-62629..13224,z=18033..85226
+on x=-110886..-34664,y=-81338..-8658,z=8914..63723
+on x=-55829..24974,y=-16897..54165,z=-121762..-28058
+on x=-65152..-11147,y=22489..91432,z=-58782..1780
+on x=-120100..-32970,y=-46592..27473,z=-11695..61039
+on x=-18631..37533,y=-124565..-50804,z=-35667..28308
+on x=-57817..18248,y=49321..117703,z=5745..55881
+on x=14781..98692,y=-1341..70827,z=15753..70151
+on x=-34419..55919,y=-19626..40991,z=39015..114138
+on x=-60785..11593,y=-56135..2999,z=-95368..-26915
+on x=-32178..58085,y=17647..101866,z=-91405..-8878
+on x=-53655..12091,y=50097..105568,z=-75335..-4862
+on x=-111166..-40997,y=-71714..2688,z=5609..50954
+on x=-16602..70118,y=-98693..-44401,z=5197..76897
+on x=16383..101554,y=4615..83635,z=-44907..18747
+off x=-95822..-15171,y=-19987..48940,z=10804..104439
+on x=-89813..-14614,y=16069..88491,z=-3297..45228
+on x=41075..99376,y=-20427..49978,z=-52012..13762
+on x=-21330..50085,y=-17944..62733,z=-112280..-30197
+on x=-16478..35915,y=36008..118594,z=-7885..47086
+off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
+off x=2032..69770,y=-71013..4824,z=7471..94418
+on x=43670..120875,y=-42068..12382,z=-24787..38892
+off x=37514..111226,y=-45862..25743,z=-16714..54663
+off x=25699..97951,y=-30668..59918,z=-15349..69697
+off x=-44271..17935,y=-9516..60759,z=49131..112598
+on x=-61695..-5813,y=40978..94975,z=8655..80240
+off x=-101086..-9439,y=-7088..67543,z=33935..83858
+off x=18020..114017,y=-48931..32606,z=21474..89843
+off x=-77139..10506,y=-89994..-18797,z=-80..59318
+off x=8476..79288,y=-75520..11602,z=-96624..-24783
+on x=-47488..-1262,y=24338..100707,z=16292..72967
+off x=-84341..13987,y=2429..92914,z=-90671..-1318
+off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
+off x=-27365..46395,y=31009..98017,z=15428..76570
+off x=-70369..-16548,y=22648..78696,z=-1892..86821
+on x=-53470..21291,y=-120233..-33476,z=-44150..38147
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
+
+    #[test]
+    fn example() {
+        let input = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(Day22::part1(&input), 474140);
+        assert_eq!(Day22::part2(&input), 2758514936282235);
+        assert_eq!(Day22::reference_part1(&input), Some(474140));
+    }
+
+    #[test]
+    fn keeps_cuboids_within_the_reactor() {
+        assert_eq!(Day22::parse("on x=0..1048577,y=0..1,z=0..1").err(), Some(ParseError::new(1, 9, "a coordinate within 1048576 of the centre")));
+        assert_eq!(Day22::parse("on x=0..1,y=3..2,z=0..1").err(), Some(ParseError::new(1, 16, "a maximum no less than the minimum")));
+    }
+
+    const REACH: i32 = 10;
+
+    fn range(min: i32, max: i32) -> impl Strategy<Value=(i32, i32)> {
+        (min..=max, min..=max).prop_map(|(a, b)| (a.min(b), a.max(b)))
+    }
+
+    fn cuboid(on: bool, x: (i32, i32), y: (i32, i32), z: (i32, i32)) -> Cuboid<i32> {
+        format!("{} x={}..{},y={}..{},z={}..{}", if on { "on" } else { "off" }, x.0, x.1, y.0, y.1, z.0, z.1).parse().unwrap()
+    }
+
+    fn step() -> impl Strategy<Value=Cuboid<i32>> {
+        (any::<bool>(), range(-REACH, REACH), range(-REACH, REACH), range(-REACH, REACH))
+            .prop_map(|(on, x, y, z)| cuboid(on, x, y, z))
+    }
+
+    proptest! {
+        #[test]
+        fn apart_cuboids_add_up(left in range(-REACH, -1), right in range(0, REACH), y in range(-REACH, REACH), z in range(-REACH, REACH)) {
+            let count = |cuboids: &[Cuboid<i32>]| {
+                let mut core = ReactorCoreV2::default();
+                for cuboid in cuboids.iter().cloned() {
+                    core.send_instruction(cuboid);
+                }
+                core.on_count()
+            };
+            let left = cuboid(true, left, y, z);
+            let right = cuboid(true, right, y, z);
+            prop_assert_eq!(count(&[left.clone(), right.clone()]), count(&[left]) + count(&[right]));
+        }
+
+        #[test]
+        fn sections_count_like_single_cubes(steps in prop::collection::vec(step(), 1..12)) {
+            let mut sections = ReactorCoreV2::default();
+            let mut cubes = ReactorCore::of_size(REACH as usize);
+            for step in steps {
+                sections.send_instruction(step.clone());
+                cubes.send_instruction(step);
+            }
+            prop_assert_eq!(sections.on_count(), cubes.on_count());
+        }
+    }
+}
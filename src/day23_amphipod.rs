@@ -1,35 +1,140 @@
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use crate::error::{next_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::reference::Reference;
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "#############
-#...........#
-###B#C#B#D###
-  #A#D#C#A#
-  #########";
-    let _input = _get_input();
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+
+    type Input<'a> = Burrows<'a>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        // the rooms go down as far as the bottom wall, below the first row of amphipods
+        let depth = input.split('\n').skip(3).position(|line| line.trim() == "#########").map_or(1, |i| i + 1);
+        check_burrow(input, depth)?;
+        Ok(Burrows { text: input, energies: None })
+    }
+
+    fn prepare(burrows: &mut Burrows<'_>) -> Result<(), ParseError> {
+        let part1 = minimum_energy(Burrow::from_amphipods(get_amphipods(burrows.text)))
+            .ok_or_else(|| ParseError::new(3, 4, "amphipods that can be organized"))?;
+        let part2 = minimum_energy(Burrow::from_amphipods(get_amphipods(&modify_input(burrows.text))))
+            .ok_or_else(|| ParseError::new(3, 4, "amphipods that can be organized with part 2's rows added"))?;
+        burrows.energies = Some((part1, part2));
+        Ok(())
+    }
+
+    fn part1(burrows: &Burrows<'_>) -> Self::Part1 {
+        burrows.energies().0
+    }
 
-    let _input = _modify_input(_input);
-    let _input = &_input;
+    fn part2(burrows: &Burrows<'_>) -> Self::Part2 {
+        burrows.energies().1
+    }
+}
+
+/// The burrow as written, and once prepared, the least energy that organizes it with and without part 2's rows;
+/// some orders of amphipods can't be organized at all, which only a search finds out.
+pub struct Burrows<'a> {
+    text: &'a str,
+    energies: Option<(usize, usize)>,
+}
 
-    // let amphipods = get_amphipods(_input);
-    // let mut burrow = Burrow::new();
+impl Burrows<'_> {
+    fn energies(&self) -> (usize, usize) {
+        self.energies.expect("the amphipods are organized by Day23::prepare")
+    }
+}
 
-    let amphipods = get_amphipods_v2(_input);
-    let mut burrow = BurrowV2::new();
+impl Generate for Day23 {
+    // checking a burrow of 4 rows, and 6 with part 2's, takes minutes
+    const SIZE: Option<Size> = Some(Size::new("rows of amphipods", 2).up_to(3));
+
+    /// As many amphipods of each type as there are rows, shuffled over the rooms again until they can be organized
+    /// both as they are and with part 2's rows added, as some orders leave no way to do that.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut amphipods: Vec<char> = "ABCD".chars().flat_map(|amphipod| std::iter::repeat_n(amphipod, size)).collect();
+        let can_be_organized = |input: &str| cheapest_first(Burrow::from_amphipods(get_amphipods(input))).is_some();
+        loop {
+            rng.shuffle(&mut amphipods);
+            let mut next = amphipods.iter();
+            let input: String = burrow_rows(size).join("\n").chars()
+                .map(|c| if c == '?' { *next.next().unwrap() } else { c })
+                .collect();
+            if can_be_organized(&input) && can_be_organized(&modify_input(&input)) {
+                return input;
+            }
+        }
+    }
+}
 
-    burrow.init(amphipods.into_iter());
+impl Reference for Day23 {
+    /// The same moves, searched cheapest first on a single thread instead of layer by layer. Both searches share
+    /// `BurrowCore`'s rules for moving, so this checks the search and not the rules.
+    fn reference_part1(burrows: &Burrows<'_>) -> Option<Self::Part1> {
+        cheapest_first(Burrow::from_amphipods(get_amphipods(burrows.text)))
+    }
 
-    let burrow = get_min_cost(burrow);
-    println!();
-    println!();
+    fn reference_part2(burrows: &Burrows<'_>) -> Option<Self::Part2> {
+        cheapest_first(Burrow::from_amphipods(get_amphipods(&modify_input(burrows.text))))
+    }
+}
+
+/// The rows of a burrow whose rooms are `depth` amphipods deep, with a `?` for each amphipod.
+fn burrow_rows(depth: usize) -> Vec<&'static str> {
+    let mut rows = vec!["#############", "#...........#", "###?#?#?#?###"];
+    rows.extend(std::iter::repeat_n("  #?#?#?#?#", depth - 1));
+    rows.push("  #########");
+    rows
+}
+
+/// Checks that `input` is laid out as [`burrow_rows`], give or take trailing spaces, with as many amphipods of each
+/// type as there is room for, which is what the solver takes.
+fn check_burrow(input: &str, depth: usize) -> Result<(), ParseError> {
+    let mut lines = input.split('\n');
+    for (y, row) in burrow_rows(depth).into_iter().enumerate() {
+        let line = next_token(input, &mut lines, "another row of the burrow")?;
+        let mut found = line.trim_end().chars();
+        for (x, expected) in row.chars().enumerate() {
+            match (found.next(), expected) {
+                (Some('A'..='D'), '?') => {}
+                (_, '?') => return Err(ParseError::new(y + 1, x + 1, "an amphipod 'A' to 'D'")),
+                (Some(c), _) if c == expected => {}
+                _ => return Err(ParseError::new(y + 1, x + 1, format!("'{}'", expected))),
+            }
+        }
+        if found.next().is_some() {
+            return Err(ParseError::new(y + 1, row.len() + 1, "the end of the row"));
+        }
+    }
+    if let Some(line) = lines.next() {
+        return Err(ParseError::at(input, line, "the end of the burrow"));
+    }
+    if "ABCD".chars().any(|amphipod| input.matches(amphipod).count() != depth) {
+        return Err(ParseError::after(input, format!("a burrow with {} amphipods of each type", depth)));
+    }
+    Ok(())
+}
+
+fn minimum_energy<T: AmphipodBurrow>(burrow: T) -> Option<usize> {
+    let burrow = get_min_cost(burrow)?;
+    debug!();
+    debug!();
     burrow.print();
-    println!();
-    println!("minimum energy is {}", burrow.get_cost());
+    debug!();
+    info!("minimum energy is {}", burrow.get_cost());
+    Some(burrow.get_cost())
 }
 
-fn _modify_input(input: &str) -> String {
+fn modify_input(input: &str) -> String {
     let mut output = String::with_capacity(input.len() + 28);
     let mut lines = input.split('\n');
     output.push_str(lines.next().unwrap());
@@ -49,20 +154,21 @@ fn _modify_input(input: &str) -> String {
     output
 }
 
-fn get_min_cost<T: AmphipodBurrow>(burrow: T) -> T {
+/// The cheapest organized burrow, searched layer by layer; `None` if every order of moves gets stuck.
+fn get_min_cost<T: AmphipodBurrow>(burrow: T) -> Option<T> {
     const PARALLEL_THREADS: usize = 12;
     let mut states = HashSet::new();
     states.insert(burrow);
     let mut i = 0usize;
     while states.iter().any(|s| !s.is_complete()) {
-        println!("step {}", i);
+        debug!("step {}", i);
         i += 1;
         // for s in states.iter() {
-        //     println!();
+        //     info!();
         //     s.print();
         // }
-        // println!();
-        // println!();
+        // info!();
+        // info!();
 
         let result: Arc<Mutex<HashSet<T>>> = Arc::new(Mutex::new(Default::default()));
         let mut threads = Vec::new();
@@ -99,46 +205,57 @@ fn get_min_cost<T: AmphipodBurrow>(burrow: T) -> T {
         states = lock.into_inner().expect("mutex cannot be locked");
     }
 
-    states.into_iter().min_by(|a, b| a.get_cost().cmp(&b.get_cost())).unwrap()
+    states.into_iter().min_by(|a, b| a.get_cost().cmp(&b.get_cost()))
 }
 
-#[allow(unused)]
-fn get_amphipods(s: &str) -> [Amphipod; 8] {
-    const VALID_CHARS: [char; 4] = ['A', 'B', 'C', 'D'];
-    let chars: Vec<char> = s.chars().filter(|c| VALID_CHARS.contains(c))
-        .collect();
-    [Amphipod::new(chars[0]),
-        Amphipod::new(chars[1]),
-        Amphipod::new(chars[2]),
-        Amphipod::new(chars[3]),
-        Amphipod::new(chars[4]),
-        Amphipod::new(chars[5]),
-        Amphipod::new(chars[6]),
-        Amphipod::new(chars[7])]
+/// The least energy that organizes the amphipods; `None` if no order of moves does.
+fn cheapest_first<T: AmphipodBurrow>(burrow: T) -> Option<usize> {
+    /// Orders burrows so that the cheapest one comes out of a [`BinaryHeap`] first.
+    struct ByCost<T>(T);
+
+    impl<T: AmphipodBurrow> PartialEq for ByCost<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.get_cost() == other.0.get_cost()
+        }
+    }
+
+    impl<T: AmphipodBurrow> Eq for ByCost<T> {}
+
+    impl<T: AmphipodBurrow> PartialOrd for ByCost<T> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl<T: AmphipodBurrow> Ord for ByCost<T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.0.get_cost().cmp(&self.0.get_cost())
+        }
+    }
+
+    // the cost is part of a burrow's hash, so burrows are told apart by their spaces alone
+    let spaces = |burrow: &T| -> Vec<Block> {
+        burrow.get_move_coordinates().iter().map(|coord| burrow.get_space(coord).clone()).collect()
+    };
+    let mut visited: HashSet<Vec<Block>> = HashSet::new();
+    let mut queue = BinaryHeap::from([ByCost(burrow)]);
+    while let Some(ByCost(burrow)) = queue.pop() {
+        if burrow.is_complete() {
+            return Some(burrow.get_cost());
+        }
+        if !visited.insert(spaces(&burrow)) {
+            continue;
+        }
+        queue.extend(burrow.get_next_possible_states().into_iter().map(ByCost));
+    }
+    None
 }
 
-#[allow(unused)]
-fn get_amphipods_v2(s: &str) -> [Amphipod; 16] {
+fn get_amphipods(s: &str) -> Vec<Amphipod> {
     const VALID_CHARS: [char; 4] = ['A', 'B', 'C', 'D'];
-    let chars: Vec<char> = s.chars().filter(|c| VALID_CHARS.contains(c))
-        .collect();
-    [Amphipod::new(chars[0]),
-        Amphipod::new(chars[1]),
-        Amphipod::new(chars[2]),
-        Amphipod::new(chars[3]),
-        Amphipod::new(chars[4]),
-        Amphipod::new(chars[5]),
-        Amphipod::new(chars[6]),
-        Amphipod::new(chars[7]),
-        Amphipod::new(chars[8]),
-        Amphipod::new(chars[9]),
-        Amphipod::new(chars[10]),
-        Amphipod::new(chars[11]),
-        Amphipod::new(chars[12]),
-        Amphipod::new(chars[13]),
-        Amphipod::new(chars[14]),
-        Amphipod::new(chars[15])
-    ]
+    s.chars().filter(|c| VALID_CHARS.contains(c))
+        .map(Amphipod::new)
+        .collect()
 }
 
 trait AmphipodBurrow<T = Self>: std::clone::Clone + core::hash::Hash + Eq + std::fmt::Debug + std::marker::Send + 'static {
@@ -146,16 +263,17 @@ trait AmphipodBurrow<T = Self>: std::clone::Clone + core::hash::Hash + Eq + std:
     fn get_space(&self, coord: &Coord) -> &Block;
     fn get_space_mut(&mut self, coord: &Coord) -> &mut Block;
     fn increase_cost(&mut self, amount: usize);
-    const AMPHIPOD_ROW_COUNT: usize;
-    fn new() -> T;
+    fn row_count(&self) -> usize;
+    fn new(row_count: usize) -> T;
 }
 
 trait BurrowCore<T = Self> {
-    fn new_base() -> Vec<Vec<Block>>;
+    fn new_base(row_count: usize) -> Vec<Vec<Block>>;
+    fn from_amphipods(amphipods: Vec<Amphipod>) -> T;
     fn get_amphipod_space(&self, i: usize) -> &Block;
     fn get_amphipod_space_mut(&mut self, i: usize) -> &mut Block;
     fn init(&mut self, amphipods: impl ExactSizeIterator<Item=Amphipod>);
-    fn get_move_coordinates() -> Vec<Coord>;
+    fn get_move_coordinates(&self) -> Vec<Coord>;
     fn is_locked(&self, coord: &Coord) -> bool;
     fn next_possible_amphipod_moves(&self) -> Vec<Coord>;
     fn move_amphipod(&mut self, from_coord: &Coord, to_coord: &Coord);
@@ -166,7 +284,7 @@ trait BurrowCore<T = Self> {
 }
 
 impl<T: AmphipodBurrow + Clone> BurrowCore for T {
-    fn new_base() -> Vec<Vec<Block>> {
+    fn new_base(row_count: usize) -> Vec<Vec<Block>> {
         let wall = Block::wall();
         let non_stop_empty = Block::empty_space(false);
         let valid_stop_empty = Block::empty_space(true);
@@ -186,7 +304,7 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
         row.push(wall.clone());
         map.push(row);
 
-        for _ in 0..T::AMPHIPOD_ROW_COUNT {
+        for _ in 0..row_count {
             let mut row = get_row();
             row.extend(vec![Block::wall(); 3]);
             for i in 0..4 {
@@ -208,21 +326,26 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
 
         map
     }
+    fn from_amphipods(amphipods: Vec<Amphipod>) -> T {
+        let mut burrow = T::new(amphipods.len() / 4);
+        burrow.init(amphipods.into_iter());
+        burrow
+    }
     fn get_amphipod_space(&self, i: usize) -> &Block {
         // #############
         // #...........#
         // ###0#1#2#3###
         //   #4#5#6#7#
         //   #8#9#10#11#
-        //   #12#13#14#15#
+        //   ...
         //   #########
-        let first = if i < 4 { 2 } else if i < 8 { 3 } else if i < 12 { 4 } else { 5 };
+        let first = i / 4 + 2;
         let second = (i % 4) * 2 + 3;
         let coord = Coord(first, second);
         self.get_space(&coord)
     }
     fn get_amphipod_space_mut(&mut self, i: usize) -> &mut Block {
-        let first = if i < 4 { 2 } else if i < 8 { 3 } else if i < 12 { 4 } else { 5 };
+        let first = i / 4 + 2;
         let second = (i % 4) * 2 + 3;
         let coord = Coord(first, second);
         self.get_space_mut(&coord)
@@ -231,54 +354,21 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
         for (i, amphipod) in amphipods.into_iter().enumerate() {
             self.get_amphipod_space_mut(i).contents = BlockContents::Amphipod(amphipod);
         }
-        if T::AMPHIPOD_ROW_COUNT == 4 {
-            for i in 12..=15 {
-                let space = self.get_amphipod_space(i);
-                let amphipod = space.get_amphipod().unwrap();
-                if amphipod.has_destination(space) {
-                    self.get_amphipod_space_mut(i).get_amphipod_mut().unwrap().moves_left = 0;
-                }
-            }
-            for i in 8..=11 {
-                let space = self.get_amphipod_space(i);
-                let amphipod = space.get_amphipod().unwrap();
-                let space_lower = self.get_amphipod_space(i + 4);
-                let amphipod_lower = space_lower.get_amphipod().unwrap();
-                if amphipod_lower.moves_left == 0 && amphipod.has_destination(space) {
-                    self.get_amphipod_space_mut(i).get_amphipod_mut().unwrap().moves_left = 0;
-                }
-            }
-        }
-        for i in 4..=7 {
-            let space = self.get_amphipod_space(i);
-            let amphipod = space.get_amphipod().unwrap();
-
-            if T::AMPHIPOD_ROW_COUNT == 4 {
-                let space_lower = self.get_amphipod_space(i + 4);
-                let amphipod_lower = space_lower.get_amphipod().unwrap();
-                if amphipod_lower.moves_left == 0 && amphipod.has_destination(space) {
-                    self.get_amphipod_space_mut(i).get_amphipod_mut().unwrap().moves_left = 0;
-                }
-            } else {
-                if amphipod.has_destination(space) {
-                    self.get_amphipod_space_mut(i).get_amphipod_mut().unwrap().moves_left = 0;
-                }
-            }
-        }
-        for i in 0..=3 {
+        // from the bottom up, amphipods already home stay put unless they have to make way for one below
+        let count = self.row_count() * 4;
+        for i in (0..count).rev() {
             let space = self.get_amphipod_space(i);
             let amphipod = space.get_amphipod().unwrap();
-            let space_lower = self.get_amphipod_space(i + 4);
-            let amphipod_lower = space_lower.get_amphipod().unwrap();
-            if amphipod_lower.moves_left == 0 && amphipod.has_destination(space) {
+            let below_finalized = i + 4 >= count || self.get_amphipod_space(i + 4).is_finalized();
+            if below_finalized && amphipod.has_destination(space) {
                 self.get_amphipod_space_mut(i).get_amphipod_mut().unwrap().moves_left = 0;
             }
         }
     }
-    fn get_move_coordinates() -> Vec<Coord> {
-        let mut results = Vec::with_capacity(11 + 4 * T::AMPHIPOD_ROW_COUNT);
+    fn get_move_coordinates(&self) -> Vec<Coord> {
+        let mut results = Vec::with_capacity(11 + 4 * self.row_count());
         results.extend((1..=11).map(|i| Coord(1, i))
-            .chain((2..=T::AMPHIPOD_ROW_COUNT + 1)
+            .chain((2..=self.row_count() + 1)
                 .flat_map(move |first| (0..4)
                     .map(move |i| Coord(first, (i % 4) * 2 + 3)))));
         results
@@ -290,14 +380,14 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
                 neighbors.iter().all(|n| !self.get_space(n).is_empty())
             }
             2 => false,
-            3 | 4 | 5 => {
+            0 => panic!("out of range: is_locked"),
+            _ => {
                 (2..=coord.0 - 1).any(|i| !self.get_space(&Coord(i, coord.1)).is_empty())
             }
-            _ => panic!("out of range: is_locked")
         }
     }
     fn next_possible_amphipod_moves(&self) -> Vec<Coord> {
-        Self::get_move_coordinates()
+        self.get_move_coordinates()
             .into_iter()
             .filter(|coord| {
                 if let Some(amphipod) = self.get_space(&coord).get_amphipod() {
@@ -334,13 +424,13 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
             .collect()
     }
     fn is_complete(&self) -> bool {
-        Self::get_move_coordinates()
+        self.get_move_coordinates()
             .into_iter()
             .filter_map(|coord| self.get_space(&coord).get_amphipod())
             .all(|a| a.moves_left == 0)
     }
     fn print(&self) {
-        for first in 0..=2 + T::AMPHIPOD_ROW_COUNT {
+        for first in 0..=2 + self.row_count() {
             let line: String = (0..13).map(|second| {
                 let block = self.get_space(&Coord(first, second));
                 match &block.contents {
@@ -354,7 +444,7 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
                     }
                 }
             }).collect();
-            println!("{}", line);
+            debug!("{}", line);
         }
     }
     fn possible_destinations(&self, coord: &Coord) -> Vec<Coord> {
@@ -373,13 +463,13 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
                 AmphipodType::Copper => 7,
                 AmphipodType::Desert => 9
             };
-            let coords: Vec<Coord> = (2..=T::AMPHIPOD_ROW_COUNT + 1).map(|f| Coord(f, i)).collect();
+            let coords: Vec<Coord> = (2..=self.row_count() + 1).map(|f| Coord(f, i)).collect();
             let spaces: Vec<&Block> = coords.iter().map(|c| self.get_space(c)).collect();
 
-            (0..T::AMPHIPOD_ROW_COUNT)
+            (0..self.row_count())
                 .filter_map(|j| if !spaces[j].is_empty() {
                     Some(vec![])
-                } else if j == T::AMPHIPOD_ROW_COUNT - 1 || spaces[j + 1].is_finalized() {
+                } else if j == self.row_count() - 1 || spaces[j + 1].is_finalized() {
                     Some(vec![coords[j].clone()])
                 } else {
                     None
@@ -404,32 +494,6 @@ impl<T: AmphipodBurrow + Clone> BurrowCore for T {
     }
 }
 
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
-struct BurrowV2(Vec<Vec<Block>>, usize);
-
-impl AmphipodBurrow for BurrowV2 {
-    fn get_cost(&self) -> usize {
-        self.1
-    }
-    fn get_space(&self, coord: &Coord) -> &Block {
-        &self.0[coord.0][coord.1]
-    }
-
-    fn get_space_mut(&mut self, coord: &Coord) -> &mut Block {
-        &mut self.0[coord.0][coord.1]
-    }
-
-    fn increase_cost(&mut self, amount: usize) {
-        self.1 += amount
-    }
-
-    const AMPHIPOD_ROW_COUNT: usize = 4;
-
-    fn new() -> Self {
-        Self(Self::new_base(), 0)
-    }
-}
-
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 struct Burrow(Vec<Vec<Block>>, usize);
 
@@ -450,10 +514,13 @@ impl AmphipodBurrow for Burrow {
         self.1 += amount
     }
 
-    const AMPHIPOD_ROW_COUNT: usize = 2;
+    fn row_count(&self) -> usize {
+        // less the walls above and below and the hallway
+        self.0.len() - 3
+    }
 
-    fn new() -> Self {
-        Self(Self::new_base(), 0)
+    fn new(row_count: usize) -> Self {
+        Self(Self::new_base(row_count), 0)
     }
 }
 
@@ -586,10 +653,47 @@ impl Amphipod {
     }
 }
 
-fn _get_input() -> &'static str {
-    "#############
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#############
 #...........#
-###D#C#D#B###
-  #B#A#A#C#
-  #########"
-}
\ No newline at end of file
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn example() {
+        let mut input = Day23::parse(EXAMPLE).unwrap();
+        Day23::prepare(&mut input).unwrap();
+        assert_eq!(Day23::part1(&input), 12521);
+        assert_eq!(Day23::part2(&input), 44169);
+        assert_eq!(Day23::reference_part1(&input), Some(12521));
+        assert_eq!(Day23::reference_part2(&input), Some(44169));
+    }
+
+    #[test]
+    fn checks_the_layout_of_the_burrow() {
+        assert_eq!(Day23::parse("ABCDABCD").err(), Some(ParseError::new(1, 1, "'#'")));
+        assert_eq!(Day23::parse(&EXAMPLE.replace("#A#D", "#A.D")).err(), Some(ParseError::new(4, 5, "'#'")));
+        assert_eq!(Day23::parse(&EXAMPLE.replace("#A#D", "#.#D")).err(), Some(ParseError::new(4, 4, "an amphipod 'A' to 'D'")));
+        assert_eq!(Day23::parse(&EXAMPLE.replace("#A#D", "#B#D")).err(), Some(ParseError::new(5, 12, "a burrow with 2 amphipods of each type")));
+        assert_eq!(Day23::parse(&format!("{}\n#", EXAMPLE)).err(), Some(ParseError::new(6, 1, "the end of the burrow")));
+        assert_eq!(Day23::parse(&EXAMPLE.replace("  #A#D#C#A#\n", "")).err(), Some(ParseError::new(4, 12, "a burrow with 1 amphipods of each type")));
+    }
+
+    #[test]
+    fn organizes_burrows_of_any_depth() {
+        let mut shallow = Day23::parse("#############\n#...........#\n###B#A#C#D###\n  #########").unwrap();
+        Day23::prepare(&mut shallow).unwrap();
+        assert_eq!(Day23::part1(&shallow), 46);
+        assert_eq!(Day23::part2(&shallow), Day23::reference_part2(&shallow).unwrap());
+
+        let generated = Day23::generate(&mut Rng::new(23), 3);
+        assert_eq!(generated.lines().count(), 6);
+        let mut deep = Day23::parse(&generated).unwrap();
+        Day23::prepare(&mut deep).unwrap();
+        assert_eq!(Day23::part1(&deep), Day23::reference_part1(&deep).unwrap());
+    }
+}
@@ -4,59 +4,177 @@ use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::mem::take;
 use std::str::{SplitWhitespace};
+use crate::error::{next_token, parse_lines, parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::log::{self, Level};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "inp w
-add x 12
-eql x w
-inp y
-add y x
-add y 3
-eql w x
-add y 4
-add y w
-add z y";
-    let _input = _get_input();
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: usize = 24;
+
+    type Input<'a> = Program<'a>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(Program { text: input, instructions: parse_lines(input, Instruction::try_from)? })
+    }
+
+    fn prepare(program: &mut Program<'_>) -> Result<(), ParseError> {
+        check_monad(program.text, &program.instructions)
+    }
+
+    fn part1(program: &Program<'_>) -> Self::Part1 {
+        let instructions = &program.instructions;
+        // only its debug output needs the expression trees, and they take far longer than the answer
+        if log::enabled(Level::Debug) {
+            analyze(instructions);
+        }
+
+        let committed = get_committed_input_values(instructions.iter());
+        debug!("committed: {:?}", committed);
+
+        let max_input = get_max_input(&committed, &instructions[..], false);
+        info!("max input: {:?}", max_input);
+        max_input
+    }
+
+    fn part2(program: &Program<'_>) -> Self::Part2 {
+        let instructions = &program.instructions;
+        let committed = get_committed_input_values(instructions.iter());
+        debug!("committed: {:?}", committed);
+
+        let min_input = get_max_input(&committed, &instructions[..], true);
+        info!("min input: {:?}", min_input);
+        min_input
+    }
+}
+
+/// The instructions of an ALU program with the text they came from, which any program can be; only a MONAD passes
+/// [`Day24::prepare`].
+pub struct Program<'a> {
+    text: &'a str,
+    instructions: Vec<Instruction<'a>>,
+}
+
+impl Generate for Day24 {
+    const SIZE: Option<Size> = None;
+
+    /// A MONAD in the usual shape: fourteen blocks, half pushing a digit plus an offset onto z in base 26
+    /// and half popping one back off, where the pops are chosen so that some model numbers are valid.
+    fn generate(rng: &mut Rng, _size: usize) -> String {
+        // push blocks are true; any arrangement where pops never outnumber pushes
+        let mut pushes: Vec<bool> = vec![true; 7];
+        pushes.extend([false; 7]);
+        while {
+            rng.shuffle(&mut pushes);
+            pushes.iter().scan(0i32, |depth, &push| {
+                *depth += if push { 1 } else { -1 };
+                Some(*depth)
+            }).any(|depth| depth < 0)
+        } {}
+
+        let mut stack: Vec<i64> = vec![];
+        pushes.into_iter()
+            .map(|push| {
+                let (divisor, check, offset) = if push {
+                    let offset = rng.range(0..=16);
+                    stack.push(offset);
+                    (1, rng.range(10..=16), offset)
+                } else {
+                    // the popped digit has to equal the pushed one plus its offset plus this check
+                    let difference = rng.range(-8..=8);
+                    (26, difference - stack.pop().unwrap(), rng.range(0..=16))
+                };
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y",
+                    divisor, check, offset)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// One block of the MONAD for each digit, where `{}` stands for the numbers that differ between blocks: whether it
+/// pops z in base 26, what the digit is checked against and the offset pushed with it.
+const MONAD_BLOCK: [&str; 18] = ["inp w", "mul x 0", "add x z", "mod x 26", "div z {}", "add x {}", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y {}", "mul y x", "add z y"];
+
+/// Checks that the program is the MONAD the solver takes apart, with a model number that passes it: fourteen blocks
+/// of [`MONAD_BLOCK`], each pushing its digit or popping one back off.
+fn check_monad(input: &str, instructions: &[Instruction]) -> Result<(), ParseError> {
+    let mut pushed: Vec<i64> = vec![];
+    let mut numbers = vec![];
+    for (i, instruction) in instructions.iter().enumerate() {
+        if i == 14 * MONAD_BLOCK.len() {
+            return Err(ParseError::at(input, instruction._source, "the end of the MONAD after 14 blocks"));
+        }
+        let expected = MONAD_BLOCK[i % MONAD_BLOCK.len()];
+        let source = instruction._source.split_whitespace().collect::<Vec<&str>>().join(" ");
+        match expected.strip_suffix("{}") {
+            Some(prefix) => match source.strip_prefix(prefix).and_then(|n| n.parse::<i64>().ok()) {
+                Some(n) => numbers.push((n, instruction._source)),
+                None => return Err(ParseError::at(input, instruction._source, format!("'{}'", expected.replace("{}", "<n>")))),
+            },
+            None if source == expected => {}
+            None => return Err(ParseError::at(input, instruction._source, format!("'{}'", expected.replace("{}", "<n>")))),
+        }
+        if let [(divisor, div_source), (check, check_source), (offset, offset_source)] = numbers[..] {
+            let at = |source: &str, expected: &str| Err(ParseError::at(input, source, expected));
+            match divisor {
+                1 if check < 10 => return at(check_source, "a check of at least 10, so the digit is pushed"),
+                1 if !(-1..=16).contains(&offset) => return at(offset_source, "an offset from -1 to 16"),
+                1 => pushed.push(offset),
+                26 => match pushed.pop() {
+                    None => return at(div_source, "'div z 1', as no digit is left to pop"),
+                    Some(pushed) if (pushed + check).abs() > 8 => {
+                        return at(check_source, "a check within 8 of the popped offset, so some digit passes")
+                    }
+                    Some(_) => {}
+                },
+                _ => return at(div_source, "a divisor of 1 or 26"),
+            }
+            numbers.clear();
+        }
+    }
+    if instructions.len() < 14 * MONAD_BLOCK.len() || !pushed.is_empty() {
+        return Err(ParseError::after(input, "a MONAD of 14 blocks, popping every digit it pushes"));
+    }
+    Ok(())
+}
 
+fn analyze(instructions: &[Instruction]) {
     let mut alu: ALU<ExpressionSet> = Default::default();
-    let instructions: Vec<Instruction> = _input.split('\n').map(|line| line.try_into().unwrap()).collect();
     let mut input = (0..14).into_iter().map(|i| ExpressionValue::Input(i).into());
     let total = instructions.len();
     for (i, instruction) in instructions.iter().enumerate()/*.take(36)*/ {
-        println!("processing instruction {}/{} ({}%) {}", i + 1, total, (i + 1) * 100 / total, instruction._source);
+        trace!("processing instruction {}/{} ({}%) {}", i + 1, total, (i + 1) * 100 / total, instruction._source);
         alu.process(instruction, &mut input);
         // for c in ['w', 'x', 'y', 'z'].iter() {
-        //     println!("value {}:\n{:?}", c, alu.value_at(c));
+        //     info!("value {}:\n{:?}", c, alu.value_at(c));
         // }
     }
     alu.try_zero_out();
     alu.optimize();
     let z = alu.value_at(&'z');
-    println!("value at z:\n\n{:?}", z);
+    trace!("value at z:\n\n{:?}", z);
     let mut dependencies: Vec<_> = Vec::from_iter(z.get_dependencies().into_iter());
     dependencies.sort();
-    println!("dependencies of z: {:?}", dependencies);
+    debug!("dependencies of z: {:?}", dependencies);
     if let Some(committed) = alu.possible_input_values.iter().next() {
         let mut committed: Vec<_> = committed.iter().map(|(&i, &val)| (i, val)).collect();
         committed.sort_by(|(a, _), (b, _)| a.cmp(b));
         let committed: Vec<_> = committed.into_iter().map(|(_, val)| val).collect();
-        println!("committed values: {:?}", committed);
+        debug!("committed values: {:?}", committed);
     }
 
     let z: Equation = z.try_into().unwrap();
-    println!("equation:\n{:?}", z);
+    trace!("equation:\n{:?}", z);
 
     let max_input = z.try_solve(14);
-    println!("max input:{:?}", max_input);
-
-    let committed = get_committed_input_values(instructions.iter());
-    println!("committed: {:?}", committed);
-
-    let max_input = get_max_input(&committed, &instructions[..], false);
-    println!("max input: {:?}", max_input);
-
-    let min_input = get_max_input(&committed, &instructions[..], true);
-    println!("min input: {:?}", min_input);
+    info!("max input:{:?}", max_input);
 }
 
 fn get_max_input(committed: &HashMap<usize, Expression>, instructions: &[Instruction], get_min: bool) -> u64 {
@@ -73,7 +191,7 @@ fn get_max_input(committed: &HashMap<usize, Expression>, instructions: &[Instruc
     loop {
         let run_input = get_run_input(&input);
         if run_input.iter().all(|&n| n <= 9 && n > 0) {
-            println!("trying with {:?}", run_input);
+            debug!("trying with {:?}", run_input);
             let mut alu = AluV2::new(run_input);
             for instruction in instructions {
                 alu.process(instruction);
@@ -151,7 +269,7 @@ enum EquationValue {
 }
 
 impl EquationValue {
-    pub(crate) fn value_with(&self, input: &[u64]) -> i64 {
+    pub fn value_with(&self, input: &[u64]) -> i64 {
         match self {
             EquationValue::Numeric(n) => *n,
             EquationValue::Var(index) => input[*index] as i64,
@@ -204,7 +322,7 @@ impl EquationExpression {
             Operation::Mod => lhs % rhs
         }
     }
-    pub(crate) fn value_with(&self, input: &[u64]) -> i64 {
+    pub fn value_with(&self, input: &[u64]) -> i64 {
         Self::operate_with(self.lhs.value_with(input), &self.operation, self.rhs.value_with(input))
     }
 }
@@ -216,7 +334,7 @@ impl Equation {
         let expression = self.clone();
         expression.0.value_with(input) == 0
     }
-    pub(crate) fn try_solve(&self, total_input_len: usize) -> Option<u64> {
+    pub fn try_solve(&self, total_input_len: usize) -> Option<u64> {
         if total_input_len > 10 {
             return None;
         }
@@ -239,7 +357,7 @@ impl Equation {
                 }
             }
             if big_change {
-                println!("trying with {:?}", input);
+                debug!("trying with {:?}", input);
             }
         }
     }
@@ -368,7 +486,7 @@ impl From<ExpressionValue> for ExpressionSet {
 }
 
 impl ExpressionValue {
-    pub(crate) fn operate(&mut self, operation: Operation, rhs: Self) {
+    pub fn operate(&mut self, operation: Operation, rhs: Self) {
         if let Operation::Multiply = operation {
             if let ExpressionValue::Numeric(0) = rhs {
                 *self = Self::Numeric(0);
@@ -630,7 +748,7 @@ impl Operationable for ExpressionSet {
 }
 
 impl ExpressionSet {
-    pub(crate) fn try_get_single(&self) -> Option<&ExpressionValue> {
+    pub fn try_get_single(&self) -> Option<&ExpressionValue> {
         if self.values.len() == 1 {
             self.values.iter().next().map(|v| &v.value)
         } else {
@@ -662,7 +780,7 @@ impl ExpressionSet {
         })
             .collect()
     }
-    pub(crate) fn get_dependencies(&self) -> HashSet<usize> {
+    pub fn get_dependencies(&self) -> HashSet<usize> {
         HashSet::from_iter(self.values.iter().flat_map(|v| v.get_dependencies()))
     }
 }
@@ -680,7 +798,7 @@ struct ExpressionStatement {
 // }
 
 impl ExpressionStatement {
-    pub(crate) fn calculate_hash(&self) -> u64 {
+    pub fn calculate_hash(&self) -> u64 {
         let mut vec = Vec::from_iter(self.conditions.iter());
         vec.sort();
         let mut hasher = DefaultHasher::new();
@@ -690,14 +808,14 @@ impl ExpressionStatement {
         }
         hasher.finish()
     }
-    pub(crate) fn get_as_simple_value_move(self) -> Option<ExpressionValue> {
+    pub fn get_as_simple_value_move(self) -> Option<ExpressionValue> {
         if self.conditions.is_empty() {
             Some(self.value)
         } else {
             None
         }
     }
-    pub(crate) fn get_dependencies(&self) -> HashSet<usize> {
+    pub fn get_dependencies(&self) -> HashSet<usize> {
         let mut set = self.value.get_dependencies();
         set.extend(self.conditions.iter().flat_map(|v| v.get_dependencies()));
 
@@ -819,7 +937,7 @@ impl<T: Operationable> Default for ALU<T> {
 }
 
 impl<T: Operationable> ALU<T> {
-    pub(crate) fn try_zero_out(&mut self) {
+    pub fn try_zero_out(&mut self) {
         Self::try_zero_out_z(&mut self.registers, &mut self.possible_input_values);
     }
     fn try_zero_out_z(registers: &mut HashMap<char, T>, possible_input_values: &mut Vec<HashMap<usize, i64>>) {
@@ -832,18 +950,18 @@ impl<T: Operationable> ALU<T> {
             *val = fun(v);
         }
         let z = registers.get(&'z').or(Some(&Default::default())).unwrap().clone();
-        println!("value z:\n{:?}", z);
-        println!();
-        println!("possible inputs:\n{:?}", possible_input_values);
-        println!();
-        println!();
+        trace!("value z:\n{:?}", z);
+        trace!();
+        trace!("possible inputs:\n{:?}", possible_input_values);
+        trace!();
+        trace!();
     }
     pub fn process(&mut self, instruction: &Instruction, input: &mut impl Iterator<Item=T>) {
         process_alu(&mut self.registers, instruction, input, |registers, possible_input_values| {
             Self::try_zero_out_z(registers, possible_input_values);
         }, &mut self.possible_input_values);
     }
-    pub(crate) fn optimize(&mut self) {
+    pub fn optimize(&mut self) {
         for register in self.registers.values_mut() {
             register.cleanup();
         }
@@ -875,7 +993,7 @@ impl Debug for Expression {
 }
 
 impl Expression {
-    pub(crate) fn evaluate(&self, input_map: &HashMap<usize, i64>) -> i64 {
+    pub fn evaluate(&self, input_map: &HashMap<usize, i64>) -> i64 {
         let lhs = self.value.evaluate(input_map);
         match &self.modifier {
             None => lhs,
@@ -890,7 +1008,7 @@ impl Expression {
             }
         }
     }
-    pub(crate) fn any<TF: Fn(&ExpressionValue) -> bool>(&self, fun: &TF) -> bool {
+    pub fn any<TF: Fn(&ExpressionValue) -> bool>(&self, fun: &TF) -> bool {
         if self.value.any(fun) {
             return true;
         }
@@ -899,7 +1017,7 @@ impl Expression {
             Some(r) => (**r).1.any(fun)
         }
     }
-    pub(crate) fn get_dependencies(&self) -> HashSet<usize> {
+    pub fn get_dependencies(&self) -> HashSet<usize> {
         let mut set: HashSet<usize> = Default::default();
         match &self.value {
             ExpressionValue::Recursive(r) => {
@@ -957,9 +1075,9 @@ fn subtract(input: &mut Vec<i64>) -> bool {
 
 impl Equality {
     #[allow(unused)]
-    pub(crate) fn max_for_condition(&self, value_to_zero: &ExpressionValue, possible_input_values: &Vec<HashMap<usize, i64>>) -> Vec<HashMap<usize, i64>> {
-        println!("trying to get:\n{:?} = {:?}", self.left, self.right);
-        println!("and 0 = {:?}", value_to_zero);
+    pub fn max_for_condition(&self, value_to_zero: &ExpressionValue, possible_input_values: &Vec<HashMap<usize, i64>>) -> Vec<HashMap<usize, i64>> {
+        trace!("trying to get:\n{:?} = {:?}", self.left, self.right);
+        trace!("and 0 = {:?}", value_to_zero);
         let mut all_possible = Vec::new();
         let default_input_values: Vec<HashMap<usize, i64>> = vec![Default::default()];
         let possible_input_values = if possible_input_values.is_empty() {
@@ -1000,7 +1118,7 @@ impl Equality {
                 let rhs = self.right.evaluate(&input_map);
                 let lhs = self.left.evaluate(&input_map);
                 let zero = value_to_zero.evaluate(&input_map);
-                // println!("{:?}, {} == {}, {} == 0", _inn, lhs, rhs, zero);
+                // info!("{:?}, {} == {}, {} == 0", _inn, lhs, rhs, zero);
                 if lhs == rhs {
                     if zero == 0 {
                         return vec![input_map];
@@ -1023,7 +1141,7 @@ impl Equality {
             //     let _inn: Vec<_> = inn.into_iter().map(|(_, val)| val).collect();
             //     let rhs = self.right.evaluate(&input_map);
             //     let lhs = self.left.evaluate(&input_map);
-            //     // println!("{:?}, {} == {}, {} == 0", _inn, lhs, rhs, zero);
+            //     // info!("{:?}, {} == {}, {} == 0", _inn, lhs, rhs, zero);
             //     if lhs == rhs {
             //         all_possible.push(input_map);
             //         // return input_map;
@@ -1041,7 +1159,7 @@ impl Equality {
 
         all_possible
     }
-    pub(crate) fn is_feasible(&self) -> bool {
+    pub fn is_feasible(&self) -> bool {
         let is_negative = |val: &ExpressionValue| {
             if let ExpressionValue::Numeric(val) = val {
                 *val < 0
@@ -1060,7 +1178,7 @@ impl Equality {
 
         true
     }
-    pub(crate) fn is_incompatible(&self, other: &Self) -> bool {
+    pub fn is_incompatible(&self, other: &Self) -> bool {
         let left_equal = self.left == other.left;
         let right_equal = self.right == other.right;
         if left_equal || right_equal {
@@ -1073,14 +1191,14 @@ impl Equality {
             false
         }
     }
-    pub(crate) fn _new(left: ExpressionValue, right: ExpressionValue, inverse: bool) -> Self {
+    pub fn _new(left: ExpressionValue, right: ExpressionValue, inverse: bool) -> Self {
         Self {
             left: Expression { value: left, modifier: None },
             right: Expression { value: right, modifier: None },
             inverse,
         }
     }
-    pub(crate) fn get_dependencies(&self) -> HashSet<usize> {
+    pub fn get_dependencies(&self) -> HashSet<usize> {
         let mut set = self.left.get_dependencies();
         set.extend(self.right.get_dependencies());
         set
@@ -1119,14 +1237,14 @@ enum ExpressionValue {
 }
 
 impl ExpressionValue {
-    pub(crate) fn evaluate(&self, input_map: &HashMap<usize, i64>) -> i64 {
+    pub fn evaluate(&self, input_map: &HashMap<usize, i64>) -> i64 {
         match self {
             ExpressionValue::Numeric(n) => *n,
             ExpressionValue::Input(i) => *input_map.get(i).unwrap(),
             ExpressionValue::Recursive(r) => r.evaluate(input_map)
         }
     }
-    pub(crate) fn any<TF: Fn(&Self) -> bool>(&self, fun: &TF) -> bool {
+    pub fn any<TF: Fn(&Self) -> bool>(&self, fun: &TF) -> bool {
         if fun(self) {
             return true;
         }
@@ -1137,7 +1255,7 @@ impl ExpressionValue {
         }
         false
     }
-    pub(crate) fn get_dependencies(&self) -> HashSet<usize> {
+    pub fn get_dependencies(&self) -> HashSet<usize> {
         match self {
             ExpressionValue::Recursive(r) => r.get_dependencies(),
             _ => Default::default()
@@ -1184,7 +1302,7 @@ impl Into<Operation> for &InstructionOperation {
 }
 
 impl InstructionOperation {
-    pub(crate) fn get_numeric_value(&self) -> i64 {
+    pub fn get_numeric_value(&self) -> i64 {
         let get_value = |v: &NumberOrValue| {
             if let NumberOrValue::Number(n) = v {
                 *n
@@ -1204,7 +1322,7 @@ impl InstructionOperation {
 }
 
 #[derive(Clone)]
-struct Instruction<'a> {
+pub struct Instruction<'a> {
     operation: InstructionOperation,
     _source: &'a str,
 }
@@ -1214,25 +1332,29 @@ impl<'a> Instruction<'a> {
 }
 
 impl<'a> TryFrom<&'a str> for Instruction<'a> {
-    type Error = ();
+    type Error = ParseError;
 
     fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let mut parts = s.split_whitespace();
-        let get_single_char = |s: &str| if s.len() != 1 { Err(()) } else { Ok(s.chars().next().unwrap()) };
-        let get_values = |parts: &mut SplitWhitespace| -> Result<(char, NumberOrValue), ()> {
-            let c = get_single_char(parts.next().ok_or(())?)?;
-            let s = parts.next().ok_or(())?;
-            let n = if Instruction::VALID_CHARS.contains(&s) {
-                NumberOrValue::Value(get_single_char(s)?)
+        let get_register = |r: &str| if Instruction::VALID_CHARS.contains(&r) {
+            Ok(r.chars().next().unwrap())
+        } else {
+            Err(ParseError::at(s, r, "a register 'w', 'x', 'y' or 'z'"))
+        };
+        let get_values = |parts: &mut SplitWhitespace| -> Result<(char, NumberOrValue), ParseError> {
+            let c = get_register(next_token(s, parts, "a register")?)?;
+            let r = next_token(s, parts, "a register or a number")?;
+            let n = if Instruction::VALID_CHARS.contains(&r) {
+                NumberOrValue::Value(get_register(r)?)
             } else {
-                NumberOrValue::Number(s.parse().or(Err(()))?)
+                NumberOrValue::Number(parse_token(s, r, "a register or a number")?)
             };
 
             Ok((c, n))
         };
-        let instruct = parts.next().ok_or(())?;
+        let instruct = next_token(s, &mut parts, "an instruction")?;
         let operation = if instruct == "inp" {
-            InstructionOperation::Input(get_single_char(parts.next().ok_or(())?)?)
+            InstructionOperation::Input(get_register(next_token(s, &mut parts, "a register")?)?)
         } else {
             let values = get_values(&mut parts)?;
             match instruct {
@@ -1241,7 +1363,7 @@ impl<'a> TryFrom<&'a str> for Instruction<'a> {
                 "div" => InstructionOperation::Divide(values.0, values.1),
                 "mod" => InstructionOperation::Mod(values.0, values.1),
                 "eql" => InstructionOperation::Equals(values.0, values.1),
-                _ => return Err(())
+                _ => return Err(ParseError::at(s, instruct, "one of 'inp', 'add', 'mul', 'div', 'mod' or 'eql'"))
             }
         };
         Ok(Self {
@@ -1278,257 +1400,54 @@ mul y x
 add z y"
 }
 
-fn _get_input() -> &'static str {
-    "inp w
-mul x 0
-add x z
-mod x 26
-div z 1
-add x 12
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 7
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 1
-add x 12
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 8
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 1
-add x 13
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 2
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 1
-add x 12
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 11
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -3
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 6
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 1
-add x 10
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 12
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 1
-add x 14
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 14
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -16
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 13
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the puzzle has no worked example for a whole MONAD program, so this only covers parsing
+    const EXAMPLE: &str = "inp w
 add x 12
 eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 15
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -8
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 10
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -12
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 6
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -7
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 10
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -6
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
-add y w
-add y 8
-mul y x
-add z y
-inp w
-mul x 0
-add x z
-mod x 26
-div z 26
-add x -11
-eql x w
-eql x 0
-mul y 0
-add y 25
-mul y x
-add y 1
-mul z y
-mul y 0
+inp y
+add y x
+add y 3
+eql w x
+add y 4
 add y w
-add y 5
-mul y x
-add z y"
-}
\ No newline at end of file
+add z y";
+
+    #[test]
+    fn parses_example() {
+        let program = Day24::parse(EXAMPLE).unwrap();
+        assert_eq!(program.instructions.len(), 10);
+    }
+
+    fn prepared(text: &str) -> Option<ParseError> {
+        Day24::prepare(&mut Day24::parse(text).unwrap()).err()
+    }
+
+    #[test]
+    fn leaves_the_shape_of_the_monad_to_prepare() {
+        let monad = Day24::generate(&mut Rng::new(24), 0);
+        assert!(prepared(&monad).is_none());
+
+        let lines: Vec<&str> = monad.lines().collect();
+        let with_line = |n: usize, line: &str| {
+            let mut lines = lines.clone();
+            lines[n - 1] = line;
+            prepared(&lines.join("\n"))
+        };
+        assert_eq!(prepared(EXAMPLE), Some(ParseError::new(2, 1, "'mul x 0'")));
+        assert_eq!(with_line(5, "div z w"), Some(ParseError::new(5, 1, "'div z <n>'")));
+        assert_eq!(with_line(5, "div z 2"), Some(ParseError::new(5, 1, "a divisor of 1 or 26")));
+        assert_eq!(with_line(5, "div z 26"),
+            Some(ParseError::new(5, 1, "'div z 1', as no digit is left to pop")));
+        assert_eq!(with_line(6, "add x 9"),
+            Some(ParseError::new(6, 1, "a check of at least 10, so the digit is pushed")));
+        assert_eq!(with_line(16, "add y 17"), Some(ParseError::new(16, 1, "an offset from -1 to 16")));
+        assert_eq!(prepared(&lines[..lines.len() - 1].join("\n")),
+            Some(ParseError::new(251, 8, "a MONAD of 14 blocks, popping every digit it pushes")));
+        assert_eq!(prepared(&format!("{}\ninp w", monad)),
+            Some(ParseError::new(253, 1, "the end of the MONAD after 14 blocks")));
+    }
+}
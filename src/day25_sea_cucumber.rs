@@ -1,99 +1,160 @@
-use std::collections::HashMap;
 use std::str::FromStr;
+use crate::animation::{Animate, Recorder};
+use crate::error::ParseError;
+use crate::generate::{Generate, grid_lines, Rng, Size};
+use crate::grid::{Grid, Pos};
+use crate::picture::{Export, Picture, Rgb};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let _input = "v...>>.vv>
-.vv>>.vv..
->>.>v>...v
->>v>>.>.v.
-v>v.vv.v..
->.>>..v...
-.vv..>.>v.
-v.v..>>v.v
-....v..v.>";
-    let _input = _get_input();
+pub struct Day25;
 
-    let mut map = _input.parse().unwrap();
+impl Solution for Day25 {
+    const DAY: usize = 25;
+
+    type Input<'a> = Map;
+    type Part1 = u32;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn prepare(map: &mut Map) -> Result<(), ParseError> {
+        let mut stopped = map.clone();
+        if !move_all(&mut stopped, |_| {}) {
+            return Err(ParseError::new(1, 1, format!("sea cucumbers that stop within {} steps", MAX_STEPS_UNTIL_STOP)));
+        }
+        map.stopped_after = Some(stopped.step);
+        Ok(())
+    }
+
+    fn part1(map: &Map) -> Self::Part1 {
+        // the first step on which no sea cucumber moves
+        map.stopped_after.expect("the sea cucumbers are moved by Day25::prepare") + 1
+    }
 
-    move_all(&mut map);
+    fn part2(_map: &Map) -> Self::Part2 {
+        "Merry Christmas!"
+    }
+}
+
+impl Generate for Day25 {
+    const SIZE: Option<Size> = Some(Size::new("rows and columns", 139));
+
+    /// Random sea cucumbers, drawn again until they come to a stop; a herd with a row or column to
+    /// itself would circle around it forever.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        loop {
+            let input = grid_lines(size, size, |_, _| *rng.choose(&['.', '.', '>', 'v']));
+            if move_all(&mut input.parse().unwrap(), |_| {}) {
+                return input;
+            }
+        }
+    }
 }
 
-fn move_all(map: &mut Map) {
-    println!("initial state:");
+/// How long the sea cucumbers get to come to a stop, as some never do.
+const MAX_STEPS_UNTIL_STOP: u32 = 2000;
+
+impl Export for Day25 {
+    /// The sea floor once nothing moves any more.
+    fn picture(map: &Map) -> Picture {
+        let mut map = map.clone();
+        move_all(&mut map, |_| {});
+        Picture::Pixmap(map.to_pixels())
+    }
+}
+
+impl Animate for Day25 {
+    /// The herds moving until they are stuck.
+    fn animate(map: &Map, recorder: &mut Recorder) {
+        let mut map = map.clone();
+        recorder.record(|| map.to_pixels());
+        move_all(&mut map, |map| recorder.record(|| map.to_pixels()));
+    }
+}
+
+/// Moves the herds until they stop, returning whether they did within [`MAX_STEPS_UNTIL_STOP`].
+fn move_all(map: &mut Map, mut on_step: impl FnMut(&Map)) -> bool {
+    debug!("initial state:");
     map.print();
-    println!();
+    debug!();
     while map.try_step() {
+        if map.step == MAX_STEPS_UNTIL_STOP {
+            return false;
+        }
+        on_step(map);
         if map.step % 10 == 0 {
-            println!("After step {}:", map.step);
+            debug!("After step {}:", map.step);
             map.print();
-            println!();
+            debug!();
         }
     }
-    println!("After step {}:", map.step);
+    debug!("After step {}:", map.step);
     map.print();
-    println!();
-    println!("took {} steps", map.step);
+    debug!();
+    info!("took {} steps", map.step);
+    true
 }
 
-struct Map {
+/// The sea floor after `step` steps, and once prepared, the number of steps after which nothing moves any more.
+#[derive(Clone)]
+pub struct Map {
     step: u32,
-    len_x: usize,
-    len_y: usize,
-    spaces: HashMap<Coord, Space>,
+    spaces: Grid<Space>,
+    stopped_after: Option<u32>,
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map: HashMap<Coord, Space> = Default::default();
-        let mut len_y = 0usize;
-        let mut len_x = 0usize;
-        for line in s.split('\n') {
-            let mut l_x = 0usize;
-            for c in line.chars() {
-                let contents = match c {
-                    '.' => SpaceContents::Empty,
-                    '>' => SpaceContents::SeaCucumber(SeaCucumber {
-                        herd_type: HerdType::EastFacing
-                    }),
-                    'v' => SpaceContents::SeaCucumber(SeaCucumber {
-                        herd_type: HerdType::SouthFacing
-                    }),
-                    _ => return Err(())
-                };
-                let coord = Coord { x: l_x, y: len_y };
-                map.insert(coord, Space {
-                    contents
-                });
-                l_x += 1;
-            }
-            len_x = l_x;
-            len_y += 1;
-        }
+        let spaces = Grid::parse(s, "'.', '>' or 'v'", |c| {
+            let contents = match c {
+                '.' => SpaceContents::Empty,
+                '>' => SpaceContents::SeaCucumber(SeaCucumber {
+                    herd_type: HerdType::EastFacing
+                }),
+                'v' => SpaceContents::SeaCucumber(SeaCucumber {
+                    herd_type: HerdType::SouthFacing
+                }),
+                _ => return None
+            };
+            Some(Space {
+                contents
+            })
+        })?;
 
+        // sea cucumbers leaving one edge of the map reappear on the opposite edge
         Ok(Self {
             step: 0,
-            len_x,
-            len_y,
-            spaces: map,
+            spaces: spaces.wrapping(),
+            stopped_after: None,
         })
     }
 }
 
 impl Map {
-    pub(crate) fn print(&self) {
-        for y in 0..self.len_y {
-            println!("{}", (0..self.len_x).map(|x| match &self.spaces.get(&Coord { x, y }).unwrap().contents {
-                SpaceContents::Empty => '.',
-                SpaceContents::SeaCucumber(c) => match c.herd_type {
-                    HerdType::EastFacing => '>',
-                    HerdType::SouthFacing => 'v'
-                }
-            }).collect::<String>());
-        }
+    /// East-facing herds in orange and south-facing ones in green.
+    fn to_pixels(&self) -> Grid<Rgb> {
+        self.spaces.map(|space| match &space.contents {
+            SpaceContents::Empty => Rgb(0, 40, 80),
+            SpaceContents::SeaCucumber(c) => match c.herd_type {
+                HerdType::EastFacing => Rgb(245, 130, 48),
+                HerdType::SouthFacing => Rgb(60, 180, 75),
+            }
+        })
+    }
+    pub fn print(&self) {
+        debug!("{}", self.spaces.render(|space| match &space.contents {
+            SpaceContents::Empty => '.',
+            SpaceContents::SeaCucumber(c) => match c.herd_type {
+                HerdType::EastFacing => '>',
+                HerdType::SouthFacing => 'v'
+            }
+        }));
     }
-    pub(crate) fn try_step(&mut self) -> bool {
+    pub fn try_step(&mut self) -> bool {
         let east_moved = self.try_step_herd(HerdType::EastFacing);
         let south_moved = self.try_step_herd(HerdType::SouthFacing);
         if south_moved || east_moved {
@@ -104,41 +165,32 @@ impl Map {
         }
     }
     #[inline]
-    fn get_destination(&self, coord: &Coord, herd_type: &HerdType) -> Coord {
-        let mut coord = coord.clone();
-        if *herd_type == HerdType::EastFacing {
-            coord.x = (coord.x + 1) % self.len_x;
-        } else {
-            coord.y = (coord.y + 1) % self.len_y;
-        }
-        coord
+    fn get_destination(&self, coord: Pos, herd_type: &HerdType) -> Pos {
+        let (dx, dy) = if *herd_type == HerdType::EastFacing { (1, 0) } else { (0, 1) };
+        self.spaces.offset(coord, dx, dy).unwrap()
     }
-    fn space_is_empty(&self, coord: &Coord) -> bool {
-        if let SpaceContents::Empty = self.spaces.get(coord).unwrap().contents {
-            true
-        } else {
-            false
-        }
+    fn space_is_empty(&self, coord: Pos) -> bool {
+        matches!(self.spaces[coord].contents, SpaceContents::Empty)
     }
-    fn move_sea_cucumber(&mut self, from: &Coord, to: &Coord) {
-        let mut temp = std::mem::take(self.spaces.get_mut(from).unwrap());
-        std::mem::swap(self.spaces.get_mut(to).unwrap(), &mut temp);
-        std::mem::swap(self.spaces.get_mut(from).unwrap(), &mut temp);
+    fn move_sea_cucumber(&mut self, from: Pos, to: Pos) {
+        let mut temp = std::mem::take(&mut self.spaces[from]);
+        std::mem::swap(&mut self.spaces[to], &mut temp);
+        std::mem::swap(&mut self.spaces[from], &mut temp);
     }
     fn try_step_herd(&mut self, herd_type: HerdType) -> bool {
         let eligible: Vec<_> = self.spaces.iter().filter_map(|(coord, space)| {
             if let SpaceContents::SeaCucumber(sea_cucumber) = &space.contents {
                 if herd_type == sea_cucumber.herd_type {
                     let dest = self.get_destination(coord, &herd_type);
-                    if self.space_is_empty(&dest) {
-                        return Some((coord.clone(), dest));
+                    if self.space_is_empty(dest) {
+                        return Some((coord, dest));
                     }
                 }
             }
             None
         }).collect();
         let return_val = !eligible.is_empty();
-        for (ref from_coord, ref to_coord) in eligible {
+        for (from_coord, to_coord) in eligible {
             self.move_sea_cucumber(from_coord, to_coord);
         }
 
@@ -146,17 +198,12 @@ impl Map {
     }
 }
 
-#[derive(Eq, PartialEq, Hash, Clone, Default)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct Space {
     contents: SpaceContents,
 }
 
+#[derive(Clone)]
 enum SpaceContents {
     Empty,
     SeaCucumber(SeaCucumber),
@@ -168,152 +215,44 @@ impl Default for SpaceContents {
     }
 }
 
+#[derive(Clone)]
 struct SeaCucumber {
     herd_type: HerdType,
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 enum HerdType {
     EastFacing,
     SouthFacing,
 }
 
-fn _get_input() -> &'static str {
-    "....v.>v..v.vvvv...>..v....vv.>..>v>...v>.v>vv.v.>v.vv..>>>v>vv.>.>...>>v..v.vv..v>.v..>.>.>..>....>....>v.vv>>v.>v>.>.>...v...>..vv>..v...
-vv>v..>..v......>..>>>.vv.....v....v>v.v.v.......>.v>...>.v..>>>.>>vvv.........v.>v....>>v.>>..>v.>.>.v.>.vv>>...v>...vv>.v>v...>>.>.>.>.v.
-v.>v.vv...v.>....>.v....>.v.>v..>>..>>..>..v>..v.>.v...v.vv>v.v.v.vv..v>..v.....>.v>.v>v>v>.v..v..v..v>>.v..>....>..>.v.>v>v.vv..v..>v.....
-.v>>vv...v.>....>>....vv>.>..>..>>.>v.v..vv>..v.>...>.>v.>v...>>v.v..v..>vv...>>vvv..>>v>>...vv>>...v.v>vvv..v>.....v.v.>.v>..>v..v..vv..>>
->.....>>>........>..vv>>>>>vv>v..v>>....v>>..>v........v.>v>v...vvv>v..vv..>....v>...v.v.v......>>..v.>...>..v>vvvv..>>..>.>.>.v.>vv>.vvv..
-...>vv>>>>..v..>>>>>v>>..>>v.>>..>.v>v...vv.....vv.v>....vv..>....>>.vv.>..>.v>........>.>.v.....>>.v..vv.>.>vv.....v.v.....>v..vv.>...v>v.
-...v>>.vv>v.....v>>..vv.v.v..v.>v.v.vv.vv>vvvv..vv>...vv>v.v.v.>.>>.v>>...vv..vv>..v>...v>.>.....v.v>.>v>>v.vv.v..>..>..v>..>v>..v>.>>>>..v
-...>vv.v.vvv..>.>..vv..>>>>v.vvv>.>.>>v..v....>..v..>>>v>v.v>>v.>...v.v>>.>>.v>v>v.vvv>.v>...vv.....vvv.v>......v.vv>v..>v.v...>>>vv.>>.>..
->.vv>>v>.v>..>.vv>>v>..v..vv..>.....>vv.v..v..>>>>.......v>v.....v.>.>..>v..v.>>...vv.>..>.>..v....v.>>.vv...v..v....>v.vv.vv...>>>v>v>v>.>
-.v..>.>.>v...>.v..v..vv.>>>.v....>>.>...>>>.>...>.v.vv.........v......>vv>.vvvvvv>v.v>>..>vv>..v>v>.v...>>....v.....>.v>.v...v>>>..v>v...>v
->.>....>v>v>.v>>...>>v.v>vvv.>..v..v.>vvvv..vv.>>.>>v..>>.v.>v..>.vv.v>.v>>.>...>.>vv.v>v...>.>v>>..>>.v..>.v...>..v>.v.>.v.vv......vvv...>
-..v...v..>..v>>.vv.>...>..v>...v.v>...v..vvvv...vv.vv...vv..v>.>v.v>>>..v>..>>...>>.vv.v>.vvv....>>>..v>vv.>.v.v.v...vv>>.v.>>.v>>...v..>..
-.............>..v.....v....vv.>......v.>>>v>v.>.vv.>.>>.>>..vv..vv.>v.>v.v.....>...>.......>vvv..>v>>.....>.>.>v>vv.v...>v..>>>v...v....>v.
-....>.v>>>.>v.>..vv.v..v>v>...>>>.vv>..v>.>>.>..>.>......v..v...>v...vvvv..>...v>.v.>.>>>v>>.v>..>v.>vv.>v>v.v.>.v.vv>.>>..>...>>vv...v>v>>
-vv>>.v>.>>.>>.v>.>vv.v>...v.vv.vvv..>v>>vv......>...vv>>..>v.v>v.v>v.>.v..>..v..vv>..v>vv>>.v>>v>>...v.>.>..v.vv.>>....v.v.>v.>...>..v>....
-...>v.v.>.v>...v.>>>..v>>.>....v>.v...>..v>...v...........>vvv>>>...>>>v>.vv>>vv>v>vvv.v>..v>.>vv..v.v>..>.>>v.v..v..v...>>...>.....>vv...>
->..v>...vv..>>.>..v...v..>..>>......>>v...>>>.vv..v.v.v>v>.v.>vv.v>..>.>...>..>vv.>..>.>...>.>v.v.v>>v.....vv>v..>....>.vvvv.vvv>v.vvv>.v..
-.>......vv>....v>>>..>...v.vv..v>>v.v..>vv>...vv>v>>.>v.v.v....>vv..v..>..>..>>...v.v.v.>>..>.>..>.v.>>..v>....>.v.>...>..v.>>>...v>>v....v
-...v>>..>v.....>v..v>..>.v>..........v.>v>.v>v..v........vv>.v.v.....vv...>.>v.v>....>...v...vv......>vv....>.v>v.....v..>.....>>........>.
->>...v.>.....vv...>v>.v..>v..vv>vv.>.>..>.v>.>.v..v.>>...>...>>.....>>>.vvv.v>v>>vv.v>..v>.>>vv..>vv>v...>...>v...vv..v.>>.>v>>......>...v.
-.>>.v.>.v.v>.v>>....v.....>v.v...v>v..>v.......v...>.>.>.>>.vv..>>v...v.>.v.>>v.v.....v..>>.>>>v>..>.>v>>vv>v>...>...>.v>v.>.v....v>..v>.vv
-...vv>.vv>vv>.>v...>v....>>>>vv.>.>>..>.v>....>v.v>...vv>....>>.>.>v...>>v.>.v....v..>>..>.>v>v.v>..>.>.>>>>v>..v..v.>v>.>.vv>>v..>vv.v..>v
-.....v>>>>..v>...v....>>.>.v..>..v..>>..v.v>..v.>.v>.vv...........>>.vv>.v>>.>...>.>v.>>>v..>..vv..>v.>....>v.v>.vv.>.....>>v>>>>>>>v..v>>>
-.>..>.v.>.v..v..>>v>>.>>v..>>..>.v..>.>>...>..>v...>.v>vv>.vv>>v...>>.vv...........v.>>vv>..>.>>...v>.....>>.>>.v>v>vv...>..>....>.vvv.v>v>
-.....vvvv....>.>>v..v.>.....v.>>>...>v>.vv..>v>>>v>.>v..vvv.v>v>..>..v>>.>vv.>.>vv>..vv...>v.>v..v>v.>...v........v>v.v.>..>...vv......v.vv
-v.vvvvv.v>>....>v>..>>.>vvv>.>v.v.>..>v.>>vv....>..>.>..>.vvv.vv.v>..v..>v>v>>>...v>.vv>..>.v.>>>..v...vv...v>v>v..v.>..>..>>.......v..>v.>
->.v.>v>.vvv>.>vv.>v.>v..>v.vvvv.>v>..>>.vv.v>...vvv..vv..v>v..vv...vv..>.....>.>v..>v..>vv.v>v.>>>...>.v>........>>v>v.>v...>v.>..>.vv.vvv.
->>...v..v....>.>...vv>>v>.>.vv>>..v..v>.v..>....v>>vv...vvv.v...vvv>>>.vvv>...v>vv>v>..>.>...v>vvv>...>v.>..v>>..v....v>>v.....>..v>.v.>>..
-vvvv.v>>vvv.>v..v.>..v..v>>.vv>.vv>>..v>>>vv.........>v....v>v>.>>>v>vv>..>.v>v...>>>vv.vv>>..>...v>>>....v.vv...>v..>>..v.v>>v..>>.>.>..v.
-..>vv.>.>v>>>>>.v.....vv.vv>..v>.v.v>...>....v.>.v>.v>..>..v...v..>v>>.>>..vv..>v..v>v>.v..v.>.>.v>.v.v...v..>>.vv.v...>vv..>>vv..v.v>>>v.>
-...>v...>.>v..v.v...v>v.>...>>v.vv.>v>v...>..vv..>.>>>.>.v>.v>.....>.vv....v..v.v...>.>.>>>>.>..>...v.>vv>.vvv....vv..>v..v>.....>...vv..v>
-v>.>>.....>...v.>..v.v.>v.>....v.v.>>>>..vv>vv...>.v>v.v.>.vv>vv>v.v>.v>..>...v>.>vvv...>..>>.v.>.v..>..>.>>>.v>.v>.v.>>v.>>..>.>.v>>>>...v
->.>v..>v>>...v...>...v>v....v..v.>>.>..>.v.>.>.>v.v.>.v...>vvv..v.>..vv...>>vvv...vv....v...v>v>v.vv>vv>.vv.>v.v..vv..>>.>.>>.>vv..v.vv..vv
->v.v>...>.....v.>vv.>.vv.v..vv>.vv.v...>>..>>>.v>>v>.v>v>...v>vvv.>..>v.>.v..v...>.......v>>.vv..>v>.>.........>>....>>.>...vv...v..>..>.>.
-v>vvvv...>...>......>...>>.>v.>>vvv.>.v>.v>..v>..v.v.v..v..v>.v.....v..v>...v>>vvv>..>..v.vvv>v>....vv..>...>..v>.>..>>.>..v...>.v.vv...>..
-v..>>v..>.v.vvv..>....v..v>.>>.v>>..v.vvv.>>>>v..>.....>....v>v>vv...>...v.>..v.vvvv>.vv.>.>>>..>...>v>>.vv.v>v.>>.>...v>..v.>.v>.v>>.>..>.
-......v>v>..>v.v..vv>>v..v.....vv.>v.>>>..>vv.v...vv..>.>v.v>>..>.v>>.v>>.vv.v>...v...v>>>vv>....v..vvv.v>vv>.v>.>v.>>..>..v..>>..vvv...>v.
->......v.>>.v>v...>...>..vv.>>>>>v..vv>>...>>v.v...v...>.vv>.vv.>....>.....v.>.>.>v>.v......v>..>>...>>>.vvv..vv...>...v.>.v>>>.>>.>..v..v.
-...>..>...>...v.vv>.>>v>v>v..v...v...v.>.>.>..v..>>>....vv>.vv.>.vvv>>.v.v..v>..v..v.>...vv.....>vv.>>v..>v>v>.>>.>>.>.v>v.>>>>>vvv..>...v.
-v...>>...vv>v...v>>...vv.>v..>>......>.>>..>....>v>.>vv>.>vv>...v..>.v.v....v>.....>.v.v.v..>v......v...vv.v..>vv>vv..>vv.v>>v.v>.>...v..v>
->>>>>..v>v..vv...>..>....>vv....>.vv.v>>v....>v.....>>>.>>>.>..vvv...>.>vv>vv.>v.v.......v.>.v.v..vv..v.>>vvv.>.>>....>.>.>.v.vv......v....
-..v..vvvv.>.v>>.>...v>..>...>...>.v.>>v...>>v>..vv...>>..v>>.v>v..v.v.>....>..>..v.v.v>.>v>.....v>.>>>..>v.vvvv.v.v.........>...>v.>>.>.vvv
-..v.>....vv......v...v>>>..>>..>.v..>.>...>>v.v....>>..>vv..>.>v.....v..>....v.>>v.>>...>..>.....>>.>...v.>vv>..vv>vv.>>.v.>.....>>>v...v.>
-v......>v.v.>.>>v...vv>....vv...v>vvv...>v.v>>..v..>.>..>.>>...>..vv.v>.>..v.......v>v....>......v.>..>.v>vv.v>>.v>vvv.>...>v.v>.>>vv>>...v
-v.v>..>.v.v..>vv.>.>>..v.....>..>.>v.>v.v.v.v.....>>>.vv.v.v.>.v..vv.v.>..>.v>v..>..>v>>.....v.v...>>.v.>v.>>v.v.v.v>.v.>>.vv>>.v....>.vv..
-...v....v....v>v>...>.....v.vv.>v..>vv....>.vv.>>.v.v..>..v.>>>.v>v.>.vv>>.>vv>....v>v>.v...>>>....>.v>.v.v>>.>.v>.>>.v>>.....>.vvv.v.vv..v
-v>....v.v>v..>..>...>.....>.v..v.>>>....v>>vv>.>..v.......>..>v>vv.vvv.v.v.vv.>...v..vv.....vvv.>>>.>v.........v.>.>.....>.v>>.v...>.v>.>vv
-v.>v.v..>.v..vv..>>..v....vv...>.....>....v.v>>.>.>..vv.v>>>....v.>.>.vv...>>.v...>......v..v.v.>v>..>....>.>v..>v>.v.>.v..>...v.>v.>....>.
-.....v.v>>>>>>v....>v.....v....>v.>>.>.>........>>..>v.>.v..vv>v>.v.vvv>....>...v.>>>>....>.>>>....>>v.>v>>.>...>>>.v>..vv...v>>..>..>v....
-..>vv.>>.>>..vvv.>v>v>v.>..>>v...v>....v>.>...>v>.....>...>>..>v.vv.vv>..>v..>>>>.>>.........>.>...vv>.>>.v...>>..v...v>.v.vv>..>>v.>v..v.>
-vvv....>...>>.vv>v>v.v......>..>.......>.>v..vv.>>>vvvv.v...vv>..>.v>.>....>....>.v.>.v.v>v>v..>..>..vvvv...>>.vvv>..vv>.v>v>>>>.....vv....
-.>>.vv>..>v.....v....>..v>.v.v>..vv>v.v......>>>..>.>v>v>.v..>...v.>>v.>...>..>v......v>>.....>>v.>>v.v...>>.>>..>>>>.>....>..vv..>..vv.vv.
-.v>...>>..>..>.>..vv.v..>vvvv>.>...>..v..>vv........v>.....v.>..>..>.>v...>.v>vv.v>>...>>>v>..>>..>v.vvv....v>v..v.>.v....v>>...>....v.v>.v
-v>....>vvv..>.v..>..>..v.v.v>v>v>vv.>>.>....>v.....>v...v.>...v.>..v>.vv>..v>>.>vv....>v..vvv.v.vvv.>..>..vvvv..>...>.vv.v.>v..v>v..v.>>...
-..>>>v>>.>>.>>......>.>....vv.v...>v>vv>.>.v>v.>v>>>v.>.v..v>....>.v...>..>.v.....v..>.>...v...vv.>>.v.>>...>.vv>...v...>.v>>>v.>vv>>v.>..>
-.v.v.>.>>>>..>v.vv>v>.vv..v....v....v.>..>>.v>..v>.v>.v.>.>..v.>..>.>v..>>.vvv>.v......>......>.......>...>v.vv>v.v.>vv..v.>.>.>..>>v.>...>
->.>vvv....>v.........>....>...>..>.v.v.v>>vv>v..>.>v..>....v.>.>>.>.v.>>...v>>..v>v....>v>..vv>>>..v.>..>vv.vv.v>...>.>vv...>vv.v>..>.>v..v
-....v.>..v.>.>>>v>...>v.....v...>...vv>v.>v.vv>vv..v..>>v>v..>......vv.v>.v.>v.>..>.v..>.>..vv.>..v.>.>..vvv.v..v..v.>.v..v>v.>vv>.>...>>..
-..>..>v....v>v>>.v.>v>.>.>>>>.>...v>>>.v...>.v>..vvv..>>v...v>.>>.>>v......v.>v>....>>..v.>...vv>.v.>v..v..>>>...v>>.>>.>..>>.>....>>>..v>>
->.vv>>.>.v...vv>>.>vv.>v....v...vv....>v..>>....>v....>>>v>>....>....>v>.>...>v.v..>...v.v.....>.>vvv.v...v..vv>.v.v..v.v>.>>v.>v.......>v>
-v...>vv>......>..>v..vvv.>.vvv>..v..v>.>vvv.v>.>vv..vv....v>.>>>.>.>>v..v.>>vvv.......>..v>>vvv.v.v..>.....>>v.>.v>>>...v.v.>..>>>vv.vvv...
-v..vvvv....v>...>>>......>>..>>.>.v.v>v.v>>vv.vv.vv...>.v..>>v.>v..>>....>v..>...v...v>.v......v>vv...>..>>>.>.v>>..vv.>v.v>.>>.>.>>.v.>>..
-v>.>..>>>.>vvv.v>vv.v>...>.v>>.v...>>v.>>...>.>>.v.v>.v..v>.>>..v>v>>v.....v>.vv..>v.>.>.>...vv>>.>.....>>..v>>..vvv..v.>...v>>.v>.>..>>>..
-v.>...v.v.vv>v..>..>>>vv.vv.>vv.....v....v.....>v.>..v>v.>>v.>>....v....>...v..v>v>.>..v>.>..>...>.v>>>.v.v.vv.vv>v...v>vv...vvv..v..>v...v
->.>.v.vv..v.vv>.>...v>.....vv.vv>..v>.vvv.>.v>......>vv.>>.>>.v..v>.....>.v.>v..v....>..>>>.>>>..>>...>.>..v.>v.v..vv.v...>v.>vv..v>>>.v>..
->vvvv.v..v..v.>..>v>.v>vvv.>>>.vv>v..>v>.v...vv.>.v.vvv.v>v>>vv.vv.>..vvv.>>>vv..>>v>.v.>v>.....>>.>.v...v.vv...>.>..>..vv..>>vv>>...>>.vv.
->.>.v>.>v..v.v.v>>.>v.vv>>>>.vvv.vv..v>.>v.v..>.vv..>>.>.>>vv.v..>..v.v..>.....v>.>..v..>.vvv.v....>>.vvv...>..>>..>.v..>vv..>v.v..>>>vvvv.
-.....v...v>>vvv.vv>>>>>v>.vv.vv.vv...>>..v..>.>.>>vv>.>...v.....v.>>.vv.>....vvv..>.>v.>v.v>>>v.v..v>vv..>.>..>.>.v...>..vv>>.>..>.>.>v>vv>
-..vv...vv.v.>..>.>.vv..>.>.v>v...v>.....v>.>v..>vvvv>...>>>...>>v....>.>.v..>v..>.vv>v..v>>v..v>..v>...>>v.>>v.>..vv.....vv.v..>..>v..v>.vv
->v>v.>>>..>>.v......v....vv.v..v.>.v.v.v.v..>..>>..>..v..vvvv.>>....v.>>...>.v>.>>.v>>.>v>>..vv.vv>>v>.>v..>...>..>..v..>.......v..v...>v.>
-v>>..>>vv>>>>v>v...>v.v.v....v>>...>>>>..>.v..v>.>....>.>.vv>>vv.vvv.>...v...>.v.v..v.>.>..v>..>.v>.v>.>>v>v>vv...v.>>>..v>.v>.>...vv.v>...
-.>.v...>..>.v..vv...>.vv.v>v..vv.v>.>v...>>>...v>.>.>..v>...v.v.vv.v.>>>vvv.vv.>.v>.>.>v>v>v.vv>...>.>.v>.>....>...>>.>vvv.v.v.....>..>v..v
->.>....>v..v...v..v>.v.>..v>>v.vvv>.v>.>v.v.>v>vv>..>..>>vv...v.v..v..>v..>.vv.>..>..>....v>v.>vv.>...>....>v...v>>>>v>...v>>vvv>>>v..>vvvv
-v.v>vv..v.>>.v>>>.v..>..>.>>..>>>v.v>>v...v..>>.>.....>..v..>vv>........vv...vv>.vv.vvv..>..v>.v.v...vvv>>v..>.>..>v>>..>......>>vv...v...>
-.>v>..v>.v>.v..vv>.v>....vv.....v.>v.v...v.vv>.>>.>.>v..>vv..v..v.>v>>v...v.>>>.>...v...>v.>>..>.>.v.>vv..vv>..vvv.v>.vv.vv>.>.vvv.>>v.>.v>
-....v..v..>.>.>>.>.v.>......v.v.v>.>>...v.v...vv.>vv..>.vv..v...v....vv>.>>>v...v......>>>.>.>v.vv.vv..>.>.>.>..>>...v>....vv..v.>...v..v.v
-.>>vv..vvvv.>....>>.vvvv...v.v>....v..v.>.>>.>v.>>v>v..>...>>....>>v..>.....>.>.>>v.>vv>.>.......>.v.v.v>v...>.>......>v.v......v.>v....v.>
->v..v>.>....v>>>>..>..>>v..>>.v.v.v.v.>.>...v..>v>>.v.v.v......>..>.>v>...vv>..v....vv.>.vvv...>>>v>v.v...vv>>.....>>vv.>..>>>v.>.v>.v.v>v.
-.v.>>.v....v>v.......v..>.v...v.>.v.>vvv..>>..>.v.>>v>v.>.>v..>v>>..>>..>..v..>.vv..>...vv..........v..>.v.>..>vvv..v..v..v...vv..vv>>.v.v.
-....v.>>..vv>..v>........v.>>.v>.v>...>..>.vvv>.v....>..vv.>..v.v.>.v>>.>..vv..vv..>v>>>..v..v...>v...v...>.v.vv.vvv...v.>.>..vvvv.v>>>....
-.>.vv>.v.vv.>.v..>.>..>>....>.v>.>>v..vv>.v.....>v>>>>.>>.v>.....>...>v.>v.>vv...v..>vv..>.v>.>>>.>v...>.>.v...>>.>>>vvvvvvv.v....>...v>vvv
-v>....>>..v..vv>>.>.v.v>v.........>...v.v>v>vvv>..>>.....v..>..>v..v>>.vvvv>v.v...>v...>..>v>v>vv....vv>.>..>>vv>.v>....>>.v>v......>>.v>v>
->.vvvv>....>...>>v..v...>.>v...>v..vv..>.>.v..>v>.vv>.>.>v..v>.v.vv..vvv...v.v.v.v.v.>.....vv>....v>>v..>>.>....>v>v.>v..>>......>.v>..vv>.
-.vv>..v.>vvv>v.v.vvv>..>..>.>v..v.>>>.vv..v.>>.....v...v..>..v.>>....>>>>vv.>.v.>>>..v...v.>...>>...v>v..>v>.>.vvv.>>...vv...v..>.>>...>>.v
->>.>>v.>..>>.v.v..v>..>.v>>.>..v..>..>.v.>....>.>>>v>.>..v>vv.....>vv.>..vv...>.>.>v....>......vv..>..>.v.>>v.v..vvv.vv.v....>.....>vv..>.v
->...v>.>..v.v>>v>.v.>v>...v.vvv....v....v.v..>v.v.>v>vv........v>...>v>.>vv...v.>>v........v>.>.>>v.v...>.>.vv..>.v..>>..v..vv>v...>.vv>...
-....v.v...>v>...>....vv.>v...>.>v>>>....v..>vv>>v.v...v...>..>..>>.>...>.>.vvv..>.>vv..>v>v>..vv..>.>.>>v>v..>v>v>.v..>>vvv>........>.v..vv
-.....v.v>..>>...v>v.v........>.>.>...vv.v>>v....>>.>v.>v....v...v.v....vv...v.vv..vv.>v>>v....v.>.vv>v......>....>..v>>v.v.vv>..>.v..v>>v.v
-v>v.v.v.v.>.v.v.>vvvvv.>v>.v.>v......vv...>>...>>>v.>v>>>>v>vv>>>vv...v>vv>.v>.v..>........>.>.v.v..>.>>.>.....v...vv..v....v.>.v.>v.>...>.
->..>>v.>.v...>v>v..>v..>..>.v>.>.....>...v.......>...>v..>.>>v.>..v.v.>.>>.>>v.v>.v..v.>>>v...vvvv.>v..v.v.vv>.>...v>v>>.v.>v...>v......>v>
->v..v.vv>v.....v.>v>v>>...>.v......v.....>.vv>...v.>.>..>>..v>.>vv.vv>>.>>>v.v>>v>vv..v..>vv..v>v>....>v..>v>..>v..v..vv.v..>>.....>.vv.v.v
->>v...vvv.>>.>.v.vv.>>>>........vv.....>..v>.vvv...>v....>>vv>.....v.>.>>.>v..vv..vv>...>v>>>vvv.>....>v>..>....v.>.>.vv>v.>...>>.......vv.
-v>>.>>v>.v>vv...v.>.>v..>.....>..>.vv>>v.v>>..vv>>..>.>..>>vv..v.>..v.....vv....>v.....vv.v>...>v...>v>>.v>....>v..v>>>.>.vv..>v..v..v..v.>
-.>v.vvv...>.>........v..>>..v..vv>..>..vv.v>.v>v>v>.v>>.v.>.vv..v.v.>>>>.........>v>v>.vvv.>.>v..>v.v.v...>.>>vvvv.>.v>v>vv.>>>vv>v>v.....v
->.v.>.v.v..>......>..v....>vvv..v>v.vv.>...v>.vv....>v.>>>v..v..v.>>>>v>.>.....v.>>..>.....>.>..v>.>>v..>...v.v>>v.>.>.>.>.v>.v...>v...v..>
-v......vv.v......>v.>>v.v>vv>.v..>.v....>>.v...vvv...v>>vv>>..>..>>..v>>v.....v>.......vv.v....v>...v>....v.v>v.........>..>>..>.>>..v>v...
-v.>.v..v..v>.>>...>.>.v.vv...v..>v..>vv>..vv>v..vv...>..v...v...v>>..>.v.v.....>v>>..v.v>..v>...v..>>vv.>.v.vv.>.v..v..>>>>v>......>..>.>vv
-...>..>v.v.>..>.>>v..>v..vv....>v.>v.v>.>v.>...>.>v..>....v...>...vv.>>>.v>v>.v...>>..>>.>..>.vvv..v.>..v.v..>.>>>>v.....>..>v..>v..v>....>
-.v>>.v.v.>>..v..>>v>..v>....v.v>v........v>..>v...>..vv..vvvv>...>>>.>v......v>vv.>..vvvvvv...vv..v..>v.v.v.vv.v.v>vv>.>>..>..v..>v>..v.>v>
->>>.>vv>..>....>.>..>..v.>v>>.>.v.....vv....>v.....>>>.v...>v>v>.vv.>.>...>..vvv.v.>...v>vv..v>..>.>..v>..>.v...>.>vv..>v>vv...>>..>.>vv..v
-.vvvv.v>.>.>vv>>>v>.>.v>.>..>.>....>v>vv>..>..v>vvv>..>>..>v>.v>.v.v>...v>>.v.......>.vvv>.....>>v>v.>>v...>.vv.vv..>v>..vv>..v>v.>v>v...v>
->v.v.v.v>v.....>v>vv.v.v>..>.v>>v.v.v.>>.v...>..>..>.vv>.v>>..v>.vvv..>vv>v>>..vv.>.vv>..>>...v.>vv...v>>vv..v..>..vv.v....vv.>v.>vv.vvvv.v
-.>..v..>>v>.>..vv....>>.>.....>.>..>>>>>>.v.v...v.v.>>vv.>.>vv.>>>........v>...v...>v.....v>.v>.v.v>v..v....vv>...vv...v.vv>.>.>..v.v.>..>v
-v.v.>.v.>.v.v>v>>.....>...v....>vvv..>v>>>..vv>.>>..v>>>>.....v...>...vv..v>>>...v>..>..>..>.>...>>....v.>v>....>..>....vv...vv.>v>v>...>..
->.>..>..>..v>v.>>>..v>.v>.vv>..>..>v.>.vv...v>.....>>.>...v..>vvv>v......>v..v.>>......>..v>vv>.v...>.v>v.>..v.v.>v.....>v>v..>vv.>.>>>>>>v
->.v>.v.vvvv>vvv..>...>vvv.v>..>..>.v......>..v>>.>...>>..........>vv>>>.vv.....v.>.>.>v>vv...>v>>.....>..>..v...>>v..>...>>>.vv...v..vv.>>.
-v...vvv.v.....v.v.v.v>>v>v.vv...v.v..>>v..>.>>.vv..>.v...v>.vv.>>..>...v.>>...>.v..v>v>v.>>v.v.>v..v...vv.>.v.v>>.v.>.....>.>....v>...v>vv.
-..>..v.>.>.v>.v>v>>>>>v.v..>>...>.v..>v..v>v...vv.>>v.v.......v>>>v.v>v.>..>.v..>>>...v.>>...>vv....v>v>>.v.v.>..v>>v.v>vvv...vv..>.v>vv.>>
-v>>>v>v>.vv.v..vv.v..>>.v.>>>..v.>v>>.....v>..vvv.>>..>.>>.>.v..v...>...>.>v..v>v.>.>.v..>>...>...>v.vv>.>>.>v>.vvv...v.>.........vv.>>.>.v
->.v..>v....v......>.>>v.>>vvvvvv>.v.v.v>>v..>vv>.v.v>>.v>v>....v...vv>v>>..v..v..>vv..>..vv>..vvv..v.>..>>v>v.>.>.v.>>.v.....vv.vv..vvv>v..
-v..vv..>.vvv>>v.>.v..v>>.v...v.v>...v..>>>...>....vv>....>.>vvv.v>....>.v.v.vv.v..vv.>vv>..vv..v.v....v.>.>.v>..v.v..>>.v....vvvv>>>..>>..v
->.>.>...>..vv.......v.vv.........>vv..vv>..>..v..v.>v.v.v...>>>...v.vv>v...vv>>v>..>.v..v.>.v>>v>v..>v>>>.v..>>>........v...v>>...>..v.>..>
->..v>v.v...>v...v>>..>....vv>.>...>>.vvvvv>vvv.>..>...>v.v>v.>.>vvv...vvvv>>>>>>..vv.v.>>>v.>vv....vv..v>.v.>.vv......v.>.vv>..v.>>>...v>>>
-.>>.v..>>.vvv.>.>..>..>>..>.v.vv..>>.v.>>>vv>>.v>v.>v>>.v.v...>...v..>>.vv.v...v..>...vv>>.v.v>v>.>.>v....>>v>.>....v.>.>.>.>v.vvv.v.vv>.v.
->.v>.>v.....>.>vvv..>.>v.>...v>>.>.v..v>.>..>.>.vv...>...vv.v>..>vv...>..>>.>.>..>v>.>>..>.>.>>.>>vv....>>.vv>>.v.v...>..>..v..>>vv.>>...vv
-....>>.>v.v..v..vv>>v>>.>.>.vvvv.v..>.>>...>vvv>v..>.v..>.>>v.......v.v.>v....v...>.v.>.......v.>>>......>>v.v.>.v>.>..v>..>..>..vv..>...v.
->v>>..>...>vv..>vvv..vvv.>.>v...v...v>...>.>...v.v..>.v.>.vv..>.vvv.>.vv>.>>v>.v.vv>.>v>.v>v>v.>>.v>>v...v...>.>>.>v..vv.>v..>>v.>.>v..>>>>
-vv....>...>v>..>...v..v.>v>.>>.>..v...v>.>>>v..>v.v>>v>>..>>v>..vv..>.>>>.v>.>...v>v.vv.>>vv..>v>.v.>.>vvv..v>..v>vv...>v.v..>......v.>v.>v
-....v....>.>...v>>vv.>.>..>>..>..>>v>..vv.v.>....>.>..v>...v.>.v>...>v...>....v.>.>..>.>v..>.....v....>.v..>vv.>.>>vv>...v.vv..>...v>vv...>
->.>..vv.v>>>.v>vvv>v..vv....>........>.v.>>....>.v>>.vvv>.>v..>v.>>v....v>..v..v>vvv>.v>.vv..vv.>vv..v.>.v>>..>..>.....>..vv.v>>....v>.v.v.
-v..>..>vv.v.v>.....vvv>..>v>v.....>.v>>>>>vvv>..>.>v.v>.vv.>.v.vv....>.v..v....v>...>>.>.>>.>..>..>...vv>v>v>.v...v.vv>.v>.....>.....>>...v
-vv.>v.>v.v.>....v.>v>v>>.>...v.vv.>.vv>....v>>>.vv......>...>vvv..>>>.>.>......>v.vv>>.>...........>.v..>..>>v.vv..v.>>.v>v..v>>v>>v..>.>vv
-.v..>v.>v...>..v...>>..>......v.vv.>>>..v>..v.>.>>..v.>.....vvv.>>....>>>>v>.v.vv.>...v>>....v.v.>.>.>.v..>...v.v>>>.v........>...>.vv.....
->>.v>...v.>.v..>..>vv.....>vvv..>..>>v.v...>>v.>vvvv>...vv...v..>>>.>.>.vvv..vv..vvv.>>.>.v..>v>>.>v...>vv..v.v.vvv.>.>>>.>v..>>.....v.>v>v
-.>.......>.>>v>.>..>.>..>.vv.>v>..>v.>vv..>vvv>>..>.v...v...v..v.v>v>....>.v....vv>.>v>v.v...v.....>..>>>....v>v...v..>.....>..>......v>vv.
-vv>..>v>.......v>>>v>>.>vv..>vvv...>>..>>vv...v>..v...v...v>vvvvvv...vv..>v.vvv..v.vv....>..vvv>>......v....vv....>.vv.>..>v.>..>v>..>.>...
-.v>v..v.v...v>..>>v.vv...>.v.v...v>.v>.>>.......>.vv..v.v>>>>.v..vv>..>.vv>.>.v>>.>.v>.......>.v.v.v.>v...>>.>>v.vv>.v.>.>>>..vvv..>>>v>.v.
-v>..vv>>vv..v...>v.vv.v.>..>.vv..>v.v..>vv.v...>.v>.>...v>v.v.>vv.......>>.vv>>.vv>v.vv>v>>.v>vv..>>..>.vv...>>.>.v.vv.>v.>>>....>>.v>>.>vv
->...v.>.v..>vv.vvvv.......>>...>.>v...v.v..>.v....>>.>.vv.>v>.>..>>>....v>..>.vv>..>...>v.>.>.vv>.v..>..>>v.....>.>.>........vv.v>..>.....v
-..v>.>..v.>.v>>................v.v>v.....v>>>.....>...v..>.v>...>>>.v>..v.>..>>.v.>..>..vv.v>>>.v>.>..v.v.>v.>......>..>.>v.>.v>.>>>v...v.v
-v>..vv...>.>.>..v.......>.>..>>v>v.v.v.v.v..v...v.vv>>.v>v>.......>>vv.>.v.>v.....v..v>v.....v..v.>...>.....>>.>v.>>...>>.v.>.v.>v>.>.v..v.
-v.v>....v>v.v.vv.v.>..vv.....v>..vvv.>.vv.v>>..v.v.>.v...v..>..>.>>v..v.v..>.vv>>v>vv..vv..v>...>.>.vv.v..v.v...v.>.v.>.v.>>>v.v.v.>>v...vv
-vv.vv.vv.>....v..vv>.>>.v.>v...>.v...v..>.>>v>>>>v...v.v.>.v>..>v.v>...>>...v......v.v>.v....>.v.>>v>v>.>>v.v>>..>>v.vv.>>v.>.v...>v.v.....
-v.>..v.v..>vvv>>v>v>>.v>>v..>v>...>vvv..>v.>vv.>.>>..v.v....v.>....vvv>.>..>...>>..>>>.v.v.>v>vv>..v...v.>...v>vvv...v.>.....v..v.v..>v>...
->.>>v.>.v..>.....>..>>v...v>..>.>.....v....v.v.v.v.v.>.>v..>......>v.v..>>..>>....>>>vvvv...vv.v.vv..v..v.>.vv>vv.v..>vv.v>>vv..>.v.v>>..>v
-...>>v>..v..v.v.>v.>..v.>.>..v..v...>>.>>..v.>.>vv..>.....vv.>....>v.>vv>..v>.v.v......v..v>.>>v.>v>...>...>...>>.>...v>vv>>..v...>>>v.v>.v
-vv..v>>..v..v.>..v...>vv.v>.>>vv>v..v..v..>.v>vvv..v...>v>v>.>>..>.v>.v.>.v.v..vv...v..>v.......>.....>.v.v...vv>vv..vv..>.>.v>>v>>v...>>.v"
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use crate::solution::solve;
+    use super::*;
+
+    const EXAMPLE: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    #[test]
+    fn example() {
+        let mut input = Day25::parse(EXAMPLE).unwrap();
+        Day25::prepare(&mut input).unwrap();
+        assert_eq!(Day25::part1(&input), 58);
+        assert_eq!(Day25::part2(&input), "Merry Christmas!");
+    }
+
+    #[test]
+    fn reports_sea_cucumbers_that_never_stop() {
+        assert!(Day25::parse(">.").is_ok());
+        assert_eq!(solve::<Day25>(">.").err(),
+                   Some(ParseError::new(1, 1, "sea cucumbers that stop within 2000 steps").on_day(25)));
+    }
+}
@@ -1,1102 +1,268 @@
-use std::ops::{Add};
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use crate::error::{next_token, parse_lines, parse_next, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
 
-pub fn run() {
-//     let input = "forward 5
-// down 5
-// forward 8
-// up 3
-// down 8
-// forward 2";
-    let input = get_input();
+pub struct Day2;
 
-    let position: Position = input.split('\n')
-        .fold(Default::default(), |sum, current| {
-            let trajectory: Position = current.parse().unwrap();
-            let next = sum + trajectory.clone();
-            println!("current: {:?} -> {:?}", trajectory, next);
-            next
-        });
+impl Solution for Day2 {
+    const DAY: usize = 2;
+
+    type Input<'a> = Vec<Command>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+
+    fn part1(commands: &Vec<Command>) -> Self::Part1 {
+        let position = Submarine::new(Simple).run(commands);
+
+        info!("final position: {:?}", position);
+        position.product()
+    }
 
-    println!("final position: {:?}", position);
-    println!("product: {}", position.product());
+    fn part2(commands: &Vec<Command>) -> Self::Part2 {
+        let position = Submarine::new(Aimed).run(commands);
+
+        info!("final position: {:?}", position);
+        position.product()
+    }
 }
 
-#[derive(Debug, Clone)]
-struct Position {
-    depth: i32,
-    horizontal: i32,
-    aim: i32,
-    aim_x: i32
+impl Generate for Day2 {
+    const SIZE: Option<Size> = Some(Size::new("commands", 1000));
+
+    /// More downs than ups, so the submarine keeps diving, but slowly enough that the answers fit.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{} {}", rng.choose(&["forward", "down", "down", "up"]), rng.range(1..=9)))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
 }
 
-impl Default for Position {
-    fn default() -> Self {
-        Self {
-            depth: 0,
-            horizontal: 0,
-            aim: 0,
-            aim_x: 0
+/// One line of the course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Forward(value) => write!(f, "forward {}", value),
+            Command::Down(value) => write!(f, "down {}", value),
+            Command::Up(value) => write!(f, "up {}", value),
         }
     }
 }
 
-impl Add for Position {
-    type Output = Self;
+impl FromStr for Command {
+    type Err = ParseError;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        if rhs.aim_x == 0 {
-            Self {
-                horizontal: self.horizontal + rhs.horizontal,
-                depth: self.depth + rhs.depth,
-                aim: self.aim + rhs.aim,
-                aim_x: 0
-            }
-        } else {
-            Self {
-                horizontal: self.horizontal + rhs.horizontal + rhs.aim_x,
-                depth: self.depth + rhs.depth + (self.aim + rhs.aim) * rhs.aim_x,
-                aim: self.aim + rhs.aim,
-                aim_x: 0
-            }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(' ');
+        let direction = next_token(s, &mut parts, "a direction")?;
+        let value: i32 = parse_next(s, &mut parts, "an integer")?;
+        if let Some(extra) = parts.next() {
+            return Err(ParseError::at(s, extra, "the end of the command"));
+        }
+        match direction {
+            "forward" => Ok(Command::Forward(value)),
+            "down" => Ok(Command::Down(value)),
+            "up" => Ok(Command::Up(value)),
+            _ => Err(ParseError::at(s, direction, "'forward', 'down' or 'up'"))
         }
     }
 }
 
+/// Where the submarine is, and which way it is pointed; it starts at the surface, pointed level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: i32,
+    pub depth: i32,
+    pub aim: i32,
+}
+
 impl Position {
     pub fn product(&self) -> i32 {
         self.depth * self.horizontal
     }
 }
 
-impl FromStr for Position {
-    type Err = ();
+/// How a submarine reads its commands.
+pub trait Semantics {
+    fn apply(&self, position: Position, command: Command) -> Position;
+    /// The shortest course from the surface that ends at `horizontal` and `depth`, whatever the aim ends up as,
+    /// or `None` if no course gets there.
+    fn plan(&self, horizontal: i32, depth: i32) -> Option<Vec<Command>>;
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value: i32 = s.split(' ').last().ok_or(())?.parse().or(Err(()))?;
-        match s.split(' ').next().ok_or(())? {
-            "down" => Ok(Self {
-                depth: 0,
-                horizontal: 0,
-                aim: value,
-                aim_x: 0
-            }),
-            "up" => Ok(Self {
-                depth: 0,
-                horizontal: 0,
-                aim: -value,
-                aim_x: 0
-            }),
-            "forward" => Ok(Self {
-                depth: 0,
-                horizontal: 0,
-                aim: 0,
-                aim_x: value
-            }),
-            _ => Err(())
+/// `down` or `up` by `amount`, as commands only take positive values; `None` if there is nowhere to go.
+fn dive(amount: i32) -> Option<Command> {
+    match amount {
+        0 => None,
+        1.. => Some(Command::Down(amount)),
+        _ => amount.checked_neg().map(Command::Up),
+    }
+}
+
+/// Part 1's reading, where `down` and `up` change the depth directly.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Simple;
+
+impl Semantics for Simple {
+    fn apply(&self, position: Position, command: Command) -> Position {
+        match command {
+            Command::Forward(value) => Position { horizontal: position.horizontal + value, ..position },
+            Command::Down(value) => Position { depth: position.depth + value, ..position },
+            Command::Up(value) => Position { depth: position.depth - value, ..position },
+        }
+    }
+
+    fn plan(&self, horizontal: i32, depth: i32) -> Option<Vec<Command>> {
+        if horizontal < 0 || depth == i32::MIN {
+            return None;
         }
+        Some((horizontal > 0).then_some(Command::Forward(horizontal)).into_iter().chain(dive(depth)).collect())
     }
 }
 
-fn get_input() -> &'static str {
-    "forward 6
-forward 6
-down 6
-down 5
-up 2
-forward 4
-forward 8
-up 9
-forward 3
-down 1
-forward 2
-forward 3
-down 1
-down 3
-down 3
-forward 9
-down 6
-forward 1
-up 5
-down 1
-forward 8
-forward 7
-up 8
-forward 7
-down 9
-forward 5
-forward 4
-forward 2
-down 3
-down 6
-down 6
-down 5
-forward 5
-forward 4
-forward 8
-up 3
-forward 6
-down 4
-up 2
-forward 8
-forward 6
-forward 1
-up 2
-down 9
-down 9
-up 5
-down 1
-up 1
-up 3
-forward 2
-forward 4
-down 8
-down 1
-up 1
-up 4
-up 1
-up 2
-forward 4
-down 1
-forward 1
-down 3
-forward 4
-down 1
-down 6
-forward 3
-down 9
-down 4
-up 4
-down 3
-forward 4
-down 3
-up 4
-forward 5
-down 9
-forward 4
-forward 1
-forward 1
-forward 4
-up 6
-up 9
-down 1
-down 1
-forward 6
-down 1
-down 5
-down 4
-forward 8
-down 8
-down 2
-down 5
-down 6
-down 4
-down 9
-up 8
-down 4
-forward 5
-up 6
-forward 2
-forward 9
-down 5
-forward 3
-forward 6
-down 9
-up 3
-forward 7
-forward 1
-forward 1
-up 6
-forward 3
-down 3
-down 1
-up 7
-forward 2
-forward 9
-forward 4
-down 9
-forward 4
-forward 5
-up 7
-down 1
-up 9
-down 6
-up 5
-forward 9
-forward 9
-down 4
-forward 1
-forward 2
-forward 1
-down 2
-forward 7
-up 6
-up 5
-up 6
-down 4
-down 6
-down 9
-forward 9
-down 9
-down 1
-down 2
-up 7
-forward 3
-down 2
-up 8
-forward 5
-forward 2
-up 2
-down 9
-down 2
-down 8
-forward 8
-down 2
-down 8
-forward 3
-up 1
-forward 7
-forward 1
-up 9
-forward 1
-forward 1
-forward 1
-down 1
-down 6
-forward 2
-down 8
-down 9
-forward 3
-up 9
-down 5
-down 2
-forward 7
-forward 1
-forward 6
-down 5
-down 4
-down 2
-down 7
-down 1
-forward 8
-down 3
-up 8
-forward 2
-down 6
-forward 9
-up 6
-forward 3
-forward 7
-down 3
-down 8
-down 8
-down 7
-down 8
-forward 3
-down 1
-forward 4
-down 8
-forward 1
-forward 1
-forward 4
-forward 6
-up 9
-forward 8
-up 6
-forward 4
-forward 4
-down 1
-down 7
-up 9
-forward 5
-down 9
-down 1
-up 2
-down 7
-forward 8
-forward 9
-forward 6
-forward 8
-up 1
-forward 2
-down 7
-up 9
-up 5
-forward 6
-forward 7
-down 4
-forward 1
-down 2
-down 7
-down 4
-down 8
-down 4
-forward 7
-down 2
-down 7
-forward 5
-down 3
-forward 6
-up 5
-up 9
-down 5
-up 2
-up 6
-forward 6
-forward 9
-down 8
-forward 8
-forward 8
-forward 3
-up 2
-forward 4
-down 9
-down 3
-up 2
-down 9
-forward 9
-forward 8
-forward 6
-forward 4
-up 8
-down 3
-up 7
-up 7
-up 5
-up 3
-forward 3
-up 7
-up 8
-down 6
-down 3
-down 4
-down 1
-forward 1
-forward 3
-down 6
-down 6
-forward 2
-up 1
-forward 9
-up 1
-forward 9
-down 1
-forward 2
-forward 3
-up 3
-down 7
-forward 6
-up 4
-forward 5
-up 4
-forward 4
-down 6
-down 9
-down 8
-down 1
-forward 8
-up 4
-forward 6
-down 8
-down 7
-down 9
-forward 7
-forward 4
-down 4
-forward 8
-up 4
-down 7
-down 1
-down 7
-up 6
-forward 3
-down 8
-down 6
-down 5
-down 7
-down 5
-forward 3
-forward 5
-down 2
-down 8
-up 4
-forward 9
-down 5
-down 1
-forward 6
-down 2
-down 6
-down 3
-up 3
-up 5
-forward 8
-up 2
-down 4
-down 5
-up 4
-forward 1
-forward 2
-up 4
-forward 7
-forward 2
-forward 4
-forward 6
-down 1
-down 9
-up 2
-down 7
-down 6
-up 1
-up 2
-forward 7
-forward 9
-forward 4
-forward 6
-down 4
-up 7
-up 2
-forward 3
-down 8
-up 5
-up 7
-down 4
-down 4
-forward 1
-forward 8
-forward 4
-forward 1
-up 8
-down 3
-down 5
-down 7
-up 2
-forward 6
-down 6
-down 8
-forward 2
-up 7
-down 6
-down 6
-up 4
-up 6
-up 4
-down 3
-forward 9
-up 4
-forward 8
-forward 7
-down 5
-down 4
-down 3
-forward 7
-forward 3
-up 7
-forward 5
-down 2
-forward 4
-forward 3
-forward 1
-down 9
-up 2
-up 3
-up 7
-up 6
-forward 1
-up 3
-down 3
-up 9
-forward 2
-forward 7
-forward 6
-forward 2
-forward 9
-forward 9
-forward 5
-up 2
-down 6
-down 3
-down 2
-forward 7
-down 4
-forward 1
-up 7
-forward 8
-down 5
-down 6
-down 7
-up 5
-forward 6
-forward 5
-up 5
-up 6
-down 4
-up 8
-up 3
-forward 9
-down 4
-down 4
-down 7
-up 7
-down 8
-down 7
-forward 2
-forward 9
-down 2
-down 1
-forward 5
-down 2
-forward 7
-down 5
-down 4
-down 7
-forward 9
-forward 2
-down 6
-forward 8
-down 6
-down 6
-up 8
-forward 9
-up 4
-down 9
-forward 7
-up 1
-up 2
-forward 9
-down 9
-down 6
-down 5
-forward 2
-down 9
-down 1
-forward 1
-down 7
-down 6
-up 6
-down 4
-forward 9
-up 5
-down 3
-down 9
-forward 5
-down 2
-forward 1
-forward 4
-forward 1
-forward 1
-forward 4
-down 2
-up 3
-forward 9
-down 5
-down 2
-forward 5
-down 6
-down 4
-forward 9
-forward 3
-forward 4
-forward 9
-forward 5
-forward 3
-down 5
-up 9
-down 5
-forward 8
-down 9
-forward 7
-down 3
-up 3
-down 7
-up 2
-forward 5
-forward 3
-up 7
-down 1
-forward 2
-down 9
-down 5
-down 2
-forward 6
-forward 6
-forward 5
-down 5
-down 1
-down 4
-down 7
-forward 4
-forward 3
-forward 1
-forward 4
-down 1
-up 7
-up 5
-forward 2
-up 3
-down 2
-forward 2
-forward 8
-down 7
-forward 9
-forward 8
-down 4
-down 5
-forward 4
-forward 7
-up 9
-down 5
-forward 4
-down 7
-forward 5
-down 8
-forward 5
-forward 2
-forward 7
-forward 3
-forward 1
-forward 2
-up 1
-up 5
-up 1
-up 3
-down 9
-up 9
-down 8
-forward 4
-down 3
-forward 7
-down 6
-forward 1
-down 7
-up 3
-forward 1
-forward 6
-up 9
-down 6
-forward 3
-down 1
-forward 7
-down 9
-up 3
-up 9
-forward 6
-up 1
-forward 5
-forward 7
-forward 7
-up 7
-down 2
-up 7
-down 8
-forward 7
-up 5
-down 9
-up 1
-forward 4
-forward 4
-forward 9
-down 6
-up 3
-down 8
-down 8
-up 2
-down 8
-down 8
-up 7
-down 8
-up 2
-up 4
-up 1
-forward 7
-forward 9
-forward 9
-down 4
-up 8
-forward 9
-down 9
-up 1
-forward 1
-forward 1
-down 5
-up 7
-down 8
-forward 4
-forward 3
-down 7
-forward 8
-up 2
-down 2
-down 6
-down 4
-forward 9
-forward 7
-down 9
-down 4
-forward 8
-down 5
-forward 7
-down 2
-forward 6
-up 8
-forward 3
-down 5
-forward 2
-forward 6
-down 9
-up 6
-up 9
-up 2
-forward 2
-down 2
-forward 5
-down 7
-down 8
-down 4
-down 5
-forward 6
-forward 2
-up 9
-down 3
-forward 3
-up 8
-forward 2
-down 9
-forward 4
-forward 1
-forward 1
-up 3
-up 9
-forward 6
-down 5
-down 2
-up 2
-up 9
-forward 9
-forward 6
-forward 3
-forward 9
-up 3
-forward 9
-up 4
-up 5
-forward 6
-forward 6
-down 8
-forward 5
-down 9
-up 5
-forward 5
-down 8
-down 3
-up 8
-down 2
-forward 4
-forward 6
-up 4
-down 3
-down 3
-down 5
-up 8
-down 7
-down 4
-forward 9
-forward 2
-down 1
-down 8
-forward 8
-up 9
-forward 3
-down 2
-up 8
-down 9
-up 2
-down 3
-forward 8
-forward 8
-forward 2
-down 3
-forward 7
-down 7
-down 4
-forward 9
-forward 9
-down 5
-up 7
-forward 2
-up 7
-up 1
-forward 4
-up 2
-down 4
-down 7
-down 4
-down 1
-up 3
-down 5
-down 5
-forward 6
-forward 2
-down 2
-forward 9
-down 4
-up 8
-forward 9
-forward 9
-up 7
-forward 4
-forward 9
-forward 2
-forward 2
-forward 1
-forward 6
-down 3
-down 5
-forward 7
-up 4
-forward 9
-forward 9
-up 1
-forward 9
-down 5
-up 2
-up 2
-down 5
-down 5
-forward 7
-down 1
-forward 5
-up 8
-up 9
-down 9
-forward 3
-up 1
-forward 6
-down 8
-down 2
-forward 6
-up 9
-down 3
-down 1
-down 1
-up 2
-up 1
-up 9
-forward 5
-forward 6
-down 7
-forward 1
-down 3
-forward 6
-forward 2
-forward 9
-forward 7
-up 5
-down 4
-down 6
-down 2
-down 5
-forward 7
-up 1
-down 7
-down 4
-down 7
-down 4
-forward 2
-forward 8
-up 7
-up 9
-down 6
-up 8
-up 3
-up 3
-up 5
-down 1
-forward 4
-forward 9
-forward 1
-down 9
-up 5
-down 3
-down 1
-down 1
-up 3
-down 1
-up 2
-up 2
+/// Part 2's reading, where `down` and `up` turn the aim and `forward` moves along it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Aimed;
+
+impl Semantics for Aimed {
+    fn apply(&self, position: Position, command: Command) -> Position {
+        match command {
+            Command::Forward(value) => Position {
+                horizontal: position.horizontal + value,
+                depth: position.depth + position.aim * value,
+                ..position
+            },
+            Command::Down(value) => Position { aim: position.aim + value, ..position },
+            Command::Up(value) => Position { aim: position.aim - value, ..position },
+        }
+    }
+
+    fn plan(&self, horizontal: i32, depth: i32) -> Option<Vec<Command>> {
+        // only going forward changes the depth, so it takes at least one forward command to get anywhere, and a turn
+        // before it if the depth is not level; one turn does if the whole way fits the depth, otherwise all but the
+        // last step are taken level and that one goes all the way down or up
+        match (horizontal, depth) {
+            (0, 0) => Some(vec![]),
+            (..=0, _) => None,
+            (_, 0) => Some(vec![Command::Forward(horizontal)]),
+            _ if depth % horizontal == 0 => Some(vec![dive(depth / horizontal)?, Command::Forward(horizontal)]),
+            _ => Some(vec![Command::Forward(horizontal - 1), dive(depth)?, Command::Forward(1)]),
+        }
+    }
+}
+
+/// Follows courses the way its [`Semantics`] reads them.
+pub struct Submarine<S> {
+    semantics: S,
+}
+
+impl<S: Semantics> Submarine<S> {
+    pub fn new(semantics: S) -> Self {
+        Self { semantics }
+    }
+
+    /// Where `commands` take the submarine from the surface.
+    pub fn run(&self, commands: &[Command]) -> Position {
+        commands.iter().fold(Position::default(), |position, &command| {
+            let next = self.semantics.apply(position, command);
+            trace!("{}: {:?} -> {:?}", command, position, next);
+            next
+        })
+    }
+
+    /// The shortest course to `horizontal` and `depth`, see [`Semantics::plan`].
+    pub fn plan(&self, horizontal: i32, depth: i32) -> Option<Vec<Command>> {
+        self.semantics.plan(horizontal, depth)
+    }
+
+    /// Every position on the way, from the surface to where `commands` end.
+    pub fn trajectory(&self, commands: &[Command]) -> Vec<Position> {
+        let mut positions = Vec::with_capacity(commands.len() + 1);
+        positions.push(Position::default());
+        for &command in commands {
+            positions.push(self.semantics.apply(positions[positions.len() - 1], command));
+        }
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    const EXAMPLE: &str = "forward 5
 down 5
 forward 8
-down 8
-up 6
-forward 9
-up 1
-up 3
-down 4
-forward 7
-up 4
-forward 3
-down 8
-forward 6
-down 6
-forward 1
-down 4
-down 7
 up 3
-down 4
-forward 5
-forward 4
-down 6
-up 4
-down 6
-up 8
-forward 1
-up 8
-forward 3
-down 8
-forward 3
-down 9
-forward 5
-down 6
-forward 5
-forward 7
-forward 8
-down 8
-down 1
-down 1
-down 7
-forward 9
-down 4
-forward 3
-down 8
-down 4
-down 6
-down 8
-forward 5
-down 5
-down 1
-down 9
-down 6
-down 7
-down 1
-forward 7
-down 2
-down 9
-down 9
-down 8
-down 9
-forward 9
-down 8
-forward 1
-up 2
-forward 4
-up 2
-up 7
-forward 1
-down 9
-up 7
-forward 4
-forward 2
-down 2
-down 5
-down 7
-down 4
-forward 8
-up 2
-forward 1
-forward 5
-down 7
-forward 3
-forward 6
-down 3
-up 5
-up 8
-down 5
-down 1
-down 7
-down 6
-forward 2
-forward 3
-forward 7
-forward 6
-down 2
-down 4
-down 1
-down 5
-down 4
-down 7
-up 5
-down 4
-up 9
-forward 7
-down 9
-down 9
-forward 3
-forward 9
-down 5
-forward 1
-up 1
-down 4
-forward 7
-up 4
-down 5
-forward 8
-forward 3
-forward 6
-forward 7
-down 8
-down 3
-forward 8
 down 8
-forward 7
-down 4
-down 2
-down 8
-down 3
-forward 4
-down 5
-up 5
-down 9
-up 5
-up 4
-up 3
-forward 7
-forward 8
-forward 9
-forward 5
-down 7
-down 2
-forward 2
-down 2
-forward 5
-forward 2
-forward 6
-down 4
-down 5
-down 7
-forward 3
-forward 3
-forward 9
-forward 6
-down 2
-forward 3
-down 5
-forward 5
-forward 9
-forward 6
-up 4
-forward 6
-forward 9
-down 3
-down 2
-forward 9
-down 1
-up 1
-forward 1
-up 3
-forward 3
-forward 6
-up 4
-up 4
-forward 8
-forward 1
-forward 2"
-}
\ No newline at end of file
+forward 2";
+
+    #[test]
+    fn example() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part1(&input), 150);
+        assert_eq!(Day2::part2(&input), 900);
+    }
+
+    #[test]
+    fn reports_trailing_tokens() {
+        assert_eq!(Day2::parse("down 5\nforward 5 x").err(), Some(ParseError::new(2, 11, "the end of the command")));
+    }
+
+    #[test]
+    fn traces_both_readings() {
+        let commands = Day2::parse(EXAMPLE).unwrap();
+
+        let simple = Submarine::new(Simple).trajectory(&commands);
+        assert_eq!(simple.len(), 7);
+        assert_eq!(simple[3], Position { horizontal: 13, depth: 5, aim: 0 });
+        assert_eq!(simple[6], Submarine::new(Simple).run(&commands));
+
+        let aimed = Submarine::new(Aimed).trajectory(&commands);
+        assert_eq!(aimed[3], Position { horizontal: 13, depth: 40, aim: 5 });
+        assert_eq!(aimed[6], Position { horizontal: 15, depth: 60, aim: 10 });
+        assert_eq!(commands.iter().map(Command::to_string).collect::<Vec<String>>().join("\n"), EXAMPLE);
+    }
+
+    #[test]
+    fn plans_the_shortest_courses() {
+        assert_eq!(Submarine::new(Simple).plan(15, 10), Some(vec![Command::Forward(15), Command::Down(10)]));
+        assert_eq!(Submarine::new(Simple).plan(0, -3), Some(vec![Command::Up(3)]));
+        assert_eq!(Submarine::new(Simple).plan(-1, 0), None);
+
+        assert_eq!(Submarine::new(Aimed).plan(15, 60), Some(vec![Command::Down(4), Command::Forward(15)]));
+        assert_eq!(Submarine::new(Aimed).plan(4, -7), Some(vec![Command::Forward(3), Command::Up(7), Command::Forward(1)]));
+        assert_eq!(Submarine::new(Aimed).plan(0, 0), Some(vec![]));
+        assert_eq!(Submarine::new(Aimed).plan(0, 5), None);
+    }
+
+    fn reaches<S: Semantics>(submarine: &Submarine<S>, horizontal: i32, depth: i32) -> Result<(), TestCaseError> {
+        if let Some(course) = submarine.plan(horizontal, depth) {
+            let position = submarine.run(&course);
+            prop_assert_eq!((position.horizontal, position.depth), (horizontal, depth));
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn planned_courses_reach_their_target(horizontal in 0..100_000, depth in -1_000_000..1_000_000) {
+            reaches(&Submarine::new(Simple), horizontal, depth)?;
+            reaches(&Submarine::new(Aimed), horizontal, depth)?;
+        }
+    }
+}
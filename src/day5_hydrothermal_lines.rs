@@ -1,688 +1,211 @@
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use utils::timer::Timer;
+use crate::error::{next_token, parse_lines, parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::grid::{Grid, Pos};
+use crate::picture::{Export, Picture};
+use crate::solution::Solution;
+use crate::vector::{Bounds, Vec2};
 
-pub(crate) fn run() {
-//     let input = "0,9 -> 5,9
-// 8,0 -> 0,8
-// 9,4 -> 3,4
-// 2,2 -> 2,1
-// 7,0 -> 7,4
-// 6,4 -> 2,0
-// 0,9 -> 2,9
-// 3,4 -> 1,4
-// 0,0 -> 8,8
-// 5,5 -> 8,2";
-    let input = get_input();
+pub struct Day5;
 
-    let lines: Vec<Line>;
-    {
-        let _timer = Timer::start(|elapsed| println!("line parsing took {} ms.", elapsed.as_millis()));
-        lines = input
-            .split('\n')
-            .map(|s| s.parse::<Line>().unwrap())
-            // .filter(|l| !l.is_diagonal())
-            .collect();
-    }
+impl Solution for Day5 {
+    const DAY: usize = 5;
 
-    // for line in lines.iter() {
-    //     println!("    {}", line);
-    // }
+    type Input<'a> = Vec<Line>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let mut grid: Grid;
-    {
-        let _timer = Timer::start(|elapsed| println!("grid initializing took {} ms.", elapsed.as_millis()));
-        let max_x = lines.iter().map(|line| line.x1.max(line.x2)).max().unwrap();
-        let max_y = lines.iter().map(|line| line.y1.max(line.y2)).max().unwrap();
-        grid = Grid::reserve((max_x + 1) as usize, (max_y + 1) as usize);
-    }
-    {
-        let _timer = Timer::start(|elapsed| println!("line adding took {} ms.", elapsed.as_millis()));
-        for line in lines {
-            grid.add_line(line);
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let _timer = Timer::start(|elapsed| debug!("line parsing took {} ms.", elapsed.as_millis()));
+        parse_lines(input, str::parse::<Line>)
     }
 
-    // grid._print();
+    fn part1(lines: &Vec<Line>) -> Self::Part1 {
+        count_overlaps(lines.iter().filter(|l| !l.is_diagonal()).collect())
+    }
 
-    println!("count >= 2: {}", grid.get_count_over(1));
+    fn part2(lines: &Vec<Line>) -> Self::Part2 {
+        count_overlaps(lines.iter().collect())
+    }
 }
 
-struct Grid(Vec<Vec<u32>>);
-
-impl Default for Grid {
-    fn default() -> Self {
-        Self(Default::default())
+impl Generate for Day5 {
+    const SIZE: Option<Size> = Some(Size::new("lines", 500));
+
+    /// Horizontal, vertical and 45 degree lines on a 1000 by 1000 floor.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let from = Vec2::new(rng.range(0..=989) as i32, rng.range(0..=989) as i32);
+                let length = rng.range(1..=(999 - from.x.max(from.y)) as i64) as i32;
+                let direction = *rng.choose(&[Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(1, 1)]);
+                let to = from + direction * length;
+                // diagonals also run from bottom left to top right
+                let (from, to) = if direction == Vec2::new(1, 1) && rng.chance(0.5) {
+                    (Vec2::new(from.x, to.y), Vec2::new(to.x, from.y))
+                } else {
+                    (from, to)
+                };
+                let (from, to) = if rng.chance(0.5) { (to, from) } else { (from, to) };
+                format!("{},{} -> {},{}", from.x, from.y, to.x, to.y)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 }
 
-impl Grid {
-    pub fn reserve(x: usize, y: usize) -> Self {
-        Self(vec![vec![0; x]; y])
-    }
-    pub fn get_count_over(&self, val: u32) -> usize {
-        self.0.iter().map(|row| row.iter().filter(|&&cell| cell > val).count()).sum()
+impl Export for Day5 {
+    fn picture(lines: &Vec<Line>) -> Picture {
+        Picture::Lines(lines.iter().map(|line| (line.from, line.to)).collect())
     }
+}
 
-    pub fn add_line(&mut self, line: Line) {
-        let (x1, y1, x2, y2) = line.decompose();
-        while self.0.len() <= y2 as usize {
-            self.0.push(Default::default())
-        }
-        for row in self.0.iter_mut() {
-            while row.len() <= x2 as usize {
-                row.push(Default::default())
-            }
-        }
+fn count_overlaps(lines: Vec<&Line>) -> usize {
+    // for line in lines.iter() {
+    //     info!("    {}", line);
+    // }
 
-        for y in y1..=y2 {
-            for x in x1..=x2 {
-                if line.crosses(&Point {
-                    x,
-                    y,
-                }) {
-                    self.0[y as usize][x as usize] += 1;
-                }
-            }
+    let mut grid: Grid<u32>;
+    {
+        let _timer = Timer::start(|elapsed| debug!("grid initializing took {} ms.", elapsed.as_millis()));
+        let max = match Bounds::from_points(lines.iter().flat_map(|line| [line.from, line.to])) {
+            Some(bounds) => bounds.max,
+            None => return 0,
+        };
+        grid = Grid::new(max.x as usize + 1, max.y as usize + 1, 0);
+    }
+    {
+        let _timer = Timer::start(|elapsed| debug!("line adding took {} ms.", elapsed.as_millis()));
+        for line in lines {
+            add_line(&mut grid, line);
         }
     }
 
-    pub fn _print(&self) {
-        for row in self.0.iter() {
-            let row_str: String = row.iter().map(|cell| match cell {
-                0 => ".".to_string(),
-                val => val.to_string()
-            }).flat_map(|s| s.chars().collect::<Vec<char>>()).collect();
-            println!("{}", row_str);
+    // _print(&grid);
+
+    grid.values().filter(|&&cell| cell > 1).count()
+}
+
+fn add_line(grid: &mut Grid<u32>, line: &Line) {
+    let Bounds { min, max } = line.bounds();
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            if line.crosses(Vec2::new(x, y)) {
+                grid[Pos::new(x as usize, y as usize)] += 1;
+            }
         }
     }
 }
 
-struct Point {
-    x: u32,
-    y: u32,
+fn _print(grid: &Grid<u32>) {
+    debug!("{}", grid.render(|&cell| match cell {
+        0 => '.',
+        val => std::char::from_digit(val.min(9), 10).unwrap()
+    }));
 }
 
-impl FromStr for Point {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(',');
-        let x: u32 = parts.next().unwrap().parse().unwrap();
-        let y: u32 = parts.next().unwrap().parse().unwrap();
-        Ok(Self {
-            x,
-            y,
-        })
-    }
+/// How far the vents reach from the corner of the floor along each axis, which keeps the grid of it in memory.
+const FLOOR_REACH: i32 = 1 << 12;
+
+/// Coordinates can't be negative, so that every point lands on the grid.
+fn parse_point(s: &str) -> Result<Vec2, ParseError> {
+    let mut parts = s.split(',');
+    let mut coordinate = |expected: &str| {
+        let token = next_token(s, &mut parts, expected)?;
+        match parse_token::<i32>(s, token, expected)? {
+            value if (0..=FLOOR_REACH).contains(&value) => Ok(value),
+            _ => Err(ParseError::at(s, token, format!("{} from 0 to {}", expected, FLOOR_REACH))),
+        }
+    };
+    let x = coordinate("an x coordinate")?;
+    let y = coordinate("a y coordinate")?;
+    Ok(Vec2::new(x, y))
 }
 
-struct Line {
-    x1: u32,
-    x2: u32,
-    y1: u32,
-    y2: u32,
+pub struct Line {
+    from: Vec2,
+    to: Vec2,
 }
 
 impl Display for Line {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{},{} -> {},{}", self.x1, self.y1, self.x2, self.y2)
-    }
-}
-
-impl From<(&Point, &Point)> for Line {
-    fn from((p1, p2): (&Point, &Point)) -> Self {
-        Self {
-            x1: p1.x,
-            x2: p2.x,
-            y1: p1.y,
-            y2: p2.y,
-        }
+        write!(f, "{},{} -> {},{}", self.from.x, self.from.y, self.to.x, self.to.y)
     }
 }
 
 impl Line {
-    fn rect_contains(&self, point: &Point) -> bool {
-        let (x1, y1, x2, y2) = Self::decompose(self);
-        y1 <= point.y && y2 >= point.y && x1 <= point.x && x2 >= point.x
-    }
-    pub fn decompose(&self) -> (u32, u32, u32, u32) {
-        let x1 = self.x1.min(self.x2);
-        let y1 = self.y1.min(self.y2);
-        let x2 = self.x1.max(self.x2);
-        let y2 = self.y1.max(self.y2);
-        return (x1, y1, x2, y2);
+    pub fn bounds(&self) -> Bounds<Vec2> {
+        Bounds::new(self.from, self.to)
     }
-    pub fn crosses(&self, point: &Point) -> bool {
-        if !Self::rect_contains(self, &point) {
+    pub fn crosses(&self, point: Vec2) -> bool {
+        if !self.bounds().contains(point) {
             return false;
         }
         if !self.is_diagonal() {
             return true;
         }
-        let dy = self.y2 as i32 - self.y1 as i32;
-        let dx = self.x2 as i32 - self.x1 as i32;
-        if dy % dx != 0 {
-            panic!("slope is not an integer");
-        }
-        point.y as i32 - self.y1 as i32 == (dy / dx) * (point.x as i32 - self.x1 as i32)
+        // parsing keeps diagonals at 45 degrees, so the slope is 1 or -1
+        let d = self.to - self.from;
+        point.y - self.from.y == d.x.signum() * d.y.signum() * (point.x - self.from.x)
     }
     pub fn is_diagonal(&self) -> bool {
-        self.x1 != self.x2 && self.y1 != self.y2
+        self.from.x != self.to.x && self.from.y != self.to.y
     }
 }
 
 impl FromStr for Line {
-    type Err = ();
+    type Err = ParseError;
 
     // 0,9 -> 5,9
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut points = s.split("->");
-        let p1: Point = points.next().unwrap().trim().parse().unwrap();
-        let p2: Point = points.next().unwrap().trim().parse().unwrap();
-        Ok((&p1, &p2).into())
+        let point = |part: &str| parse_point(part.trim()).map_err(|e| e.relative_to(s, part.trim()));
+        let from = point(next_token(s, &mut points, "a point")?)?;
+        let to_token = next_token(s, &mut points, "'->'")?;
+        let to = point(to_token)?;
+        let d = to - from;
+        if d.x != 0 && d.y != 0 && d.x.abs() != d.y.abs() {
+            return Err(ParseError::at(s, to_token.trim(), "a point in line with the first, across or at 45 degrees"));
+        }
+        Ok(Self { from, to })
     }
 }
 
-fn get_input() -> &'static str {
-    "541,808 -> 108,808
-982,23 -> 45,960
-558,21 -> 558,318
-907,877 -> 43,13
-532,213 -> 532,801
-599,387 -> 870,387
-762,208 -> 78,208
-739,527 -> 739,907
-64,21 -> 64,958
-258,267 -> 929,938
-22,75 -> 725,778
-347,950 -> 347,345
-705,906 -> 61,906
-53,16 -> 950,913
-468,474 -> 475,481
-567,602 -> 914,602
-570,531 -> 570,530
-180,307 -> 180,823
-546,374 -> 390,374
-750,142 -> 861,31
-586,631 -> 905,950
-971,680 -> 784,680
-428,174 -> 352,174
-825,676 -> 228,676
-630,617 -> 70,617
-156,912 -> 944,124
-805,203 -> 25,983
-726,808 -> 726,96
-986,564 -> 908,642
-594,293 -> 594,458
-182,126 -> 182,476
-979,43 -> 35,987
-642,272 -> 642,446
-759,690 -> 891,690
-951,518 -> 161,518
-357,769 -> 336,769
-904,297 -> 904,533
-326,332 -> 326,316
-758,356 -> 654,460
-432,425 -> 432,819
-31,602 -> 31,421
-318,555 -> 898,555
-326,220 -> 777,671
-708,957 -> 708,273
-26,24 -> 974,972
-341,172 -> 341,394
-33,926 -> 864,95
-486,324 -> 486,704
-850,82 -> 132,800
-62,506 -> 113,506
-816,429 -> 816,141
-184,17 -> 184,328
-40,680 -> 30,670
-640,294 -> 127,807
-654,512 -> 654,296
-722,301 -> 629,301
-255,430 -> 811,430
-376,385 -> 376,379
-227,207 -> 227,947
-363,533 -> 757,533
-150,616 -> 150,284
-943,100 -> 79,964
-275,963 -> 275,461
-409,768 -> 409,574
-516,349 -> 516,656
-19,666 -> 847,666
-962,358 -> 962,907
-781,789 -> 781,870
-778,380 -> 501,657
-895,29 -> 12,912
-12,677 -> 12,761
-614,728 -> 690,652
-415,786 -> 778,423
-683,84 -> 683,574
-973,909 -> 75,11
-854,791 -> 260,197
-965,55 -> 559,55
-624,542 -> 235,153
-924,57 -> 16,965
-540,736 -> 540,65
-293,293 -> 929,293
-587,176 -> 587,432
-389,126 -> 389,590
-267,149 -> 92,149
-424,596 -> 697,323
-412,697 -> 773,697
-473,579 -> 310,416
-149,175 -> 837,863
-848,984 -> 159,295
-608,613 -> 292,613
-344,970 -> 546,970
-589,531 -> 589,74
-220,727 -> 220,831
-651,240 -> 318,240
-717,609 -> 847,479
-917,954 -> 327,364
-780,102 -> 228,654
-201,95 -> 201,921
-403,88 -> 403,79
-755,76 -> 755,529
-654,762 -> 654,97
-957,988 -> 44,75
-899,43 -> 51,891
-855,892 -> 175,212
-295,962 -> 613,962
-284,800 -> 946,800
-494,771 -> 793,472
-212,799 -> 212,784
-25,829 -> 824,30
-277,135 -> 396,135
-981,986 -> 187,192
-794,88 -> 308,88
-425,140 -> 73,140
-155,900 -> 155,500
-339,768 -> 339,621
-720,407 -> 824,303
-746,679 -> 746,490
-672,632 -> 30,632
-74,628 -> 49,628
-534,770 -> 249,485
-59,802 -> 605,256
-481,543 -> 723,301
-163,425 -> 947,425
-10,782 -> 779,782
-185,851 -> 68,968
-536,479 -> 536,217
-987,472 -> 960,472
-802,623 -> 202,23
-548,73 -> 548,838
-711,678 -> 711,655
-91,578 -> 91,162
-803,27 -> 31,27
-198,524 -> 282,608
-45,585 -> 45,973
-741,157 -> 317,581
-263,843 -> 819,843
-240,786 -> 240,272
-453,148 -> 657,148
-646,74 -> 646,758
-367,521 -> 367,185
-355,356 -> 503,356
-306,780 -> 41,780
-783,779 -> 513,779
-81,886 -> 81,531
-403,592 -> 69,926
-575,458 -> 575,709
-406,695 -> 406,563
-342,690 -> 269,690
-712,766 -> 712,693
-929,608 -> 929,355
-562,750 -> 571,759
-353,780 -> 424,780
-296,111 -> 944,759
-341,28 -> 859,28
-415,28 -> 415,153
-104,16 -> 966,878
-554,780 -> 726,780
-749,645 -> 749,466
-188,724 -> 933,724
-806,185 -> 614,185
-244,181 -> 682,181
-104,937 -> 367,937
-400,116 -> 183,333
-749,94 -> 301,542
-638,543 -> 811,716
-694,727 -> 694,44
-46,259 -> 46,721
-467,748 -> 620,595
-739,543 -> 391,543
-734,454 -> 734,101
-200,954 -> 200,654
-592,358 -> 592,581
-758,29 -> 758,401
-386,685 -> 519,685
-538,294 -> 298,294
-82,269 -> 766,269
-840,114 -> 332,114
-924,542 -> 185,542
-936,311 -> 369,878
-820,724 -> 590,724
-277,689 -> 68,689
-76,634 -> 167,634
-255,503 -> 144,503
-753,915 -> 753,27
-288,329 -> 513,554
-729,297 -> 447,297
-128,533 -> 128,530
-252,718 -> 856,114
-947,498 -> 312,498
-142,917 -> 950,109
-815,845 -> 770,845
-863,98 -> 332,98
-927,694 -> 927,276
-682,232 -> 227,687
-640,474 -> 840,274
-98,37 -> 98,149
-203,170 -> 99,170
-736,956 -> 736,539
-686,384 -> 882,580
-976,152 -> 380,748
-80,859 -> 80,208
-687,252 -> 687,748
-819,978 -> 101,260
-17,722 -> 17,306
-99,96 -> 99,929
-713,757 -> 157,201
-469,986 -> 469,393
-813,210 -> 789,186
-360,319 -> 360,43
-777,707 -> 606,707
-928,413 -> 380,961
-566,850 -> 828,850
-380,650 -> 717,650
-863,889 -> 373,399
-521,97 -> 967,97
-12,989 -> 975,26
-965,974 -> 965,848
-639,331 -> 639,948
-815,731 -> 235,151
-823,369 -> 610,369
-22,930 -> 898,54
-635,113 -> 635,495
-462,123 -> 771,123
-445,274 -> 304,133
-743,633 -> 43,633
-811,267 -> 811,936
-745,549 -> 636,549
-321,720 -> 511,530
-949,138 -> 308,138
-563,34 -> 720,191
-449,313 -> 966,830
-857,308 -> 392,773
-244,80 -> 820,80
-212,345 -> 327,460
-28,941 -> 28,22
-122,988 -> 122,50
-51,889 -> 880,60
-328,161 -> 328,820
-703,639 -> 40,639
-107,47 -> 839,779
-338,260 -> 828,750
-947,304 -> 276,975
-509,281 -> 281,281
-200,42 -> 820,662
-728,940 -> 728,897
-511,770 -> 376,635
-197,99 -> 929,99
-699,648 -> 352,301
-915,291 -> 256,950
-641,586 -> 641,346
-337,722 -> 965,722
-739,92 -> 739,884
-656,676 -> 649,676
-369,450 -> 686,450
-33,672 -> 409,296
-336,979 -> 336,595
-95,289 -> 888,289
-286,128 -> 985,827
-471,300 -> 899,300
-824,959 -> 66,201
-884,104 -> 166,822
-562,681 -> 562,948
-987,974 -> 77,64
-61,47 -> 61,528
-359,647 -> 709,647
-34,398 -> 270,398
-526,66 -> 257,335
-744,545 -> 502,787
-805,726 -> 728,803
-184,749 -> 184,839
-136,148 -> 842,148
-538,244 -> 306,476
-202,698 -> 958,698
-264,519 -> 903,519
-367,310 -> 26,310
-391,747 -> 307,831
-580,945 -> 706,945
-199,776 -> 98,776
-408,242 -> 408,474
-929,706 -> 929,570
-351,524 -> 221,524
-773,783 -> 274,783
-770,790 -> 770,653
-572,182 -> 572,574
-979,13 -> 29,963
-175,454 -> 457,736
-170,399 -> 170,202
-570,406 -> 97,406
-813,37 -> 556,37
-80,886 -> 465,501
-317,799 -> 876,799
-602,399 -> 362,639
-891,257 -> 445,257
-346,275 -> 297,275
-345,136 -> 345,755
-252,460 -> 252,731
-17,573 -> 729,573
-901,838 -> 230,167
-754,582 -> 754,970
-415,964 -> 415,605
-857,758 -> 857,612
-319,613 -> 256,613
-34,614 -> 34,802
-443,118 -> 443,891
-335,300 -> 80,45
-284,340 -> 552,608
-154,31 -> 33,31
-440,720 -> 20,720
-331,219 -> 311,219
-163,83 -> 868,788
-775,733 -> 775,284
-859,240 -> 859,159
-144,249 -> 105,210
-710,809 -> 710,910
-76,481 -> 76,86
-825,603 -> 916,603
-404,575 -> 740,575
-612,427 -> 612,571
-63,765 -> 63,538
-979,170 -> 183,966
-781,372 -> 911,372
-237,732 -> 321,648
-457,474 -> 954,971
-887,110 -> 551,446
-894,962 -> 48,116
-276,534 -> 800,534
-40,42 -> 950,952
-986,941 -> 123,78
-348,857 -> 899,857
-57,728 -> 57,802
-776,536 -> 776,462
-683,618 -> 683,443
-468,914 -> 750,914
-420,129 -> 984,693
-254,913 -> 166,913
-832,737 -> 832,422
-472,724 -> 12,724
-483,916 -> 483,226
-206,751 -> 206,250
-890,901 -> 20,31
-156,198 -> 883,925
-972,367 -> 67,367
-409,196 -> 320,196
-59,188 -> 815,188
-856,856 -> 856,756
-84,871 -> 942,13
-475,91 -> 475,784
-363,578 -> 363,405
-219,976 -> 219,717
-243,25 -> 243,173
-115,194 -> 462,194
-91,960 -> 889,162
-20,957 -> 915,62
-60,955 -> 250,765
-882,707 -> 267,92
-122,626 -> 122,202
-705,74 -> 147,632
-418,122 -> 976,680
-500,522 -> 936,522
-715,652 -> 82,19
-118,872 -> 118,479
-918,70 -> 918,811
-968,968 -> 347,347
-985,479 -> 587,877
-749,259 -> 749,841
-475,102 -> 200,102
-808,976 -> 515,976
-761,726 -> 761,358
-778,523 -> 729,474
-266,251 -> 396,251
-46,921 -> 914,921
-384,424 -> 804,844
-442,359 -> 646,155
-929,774 -> 920,783
-344,958 -> 344,281
-33,173 -> 607,173
-40,125 -> 897,982
-345,640 -> 432,640
-845,170 -> 403,612
-763,84 -> 763,885
-855,388 -> 123,388
-861,858 -> 861,940
-449,736 -> 97,384
-576,592 -> 906,262
-868,817 -> 868,633
-14,100 -> 610,696
-878,412 -> 416,412
-43,427 -> 118,502
-250,829 -> 770,829
-814,444 -> 814,769
-647,857 -> 528,857
-648,102 -> 514,102
-956,12 -> 887,12
-665,957 -> 665,891
-760,367 -> 178,949
-704,524 -> 815,524
-269,88 -> 322,88
-414,881 -> 414,788
-550,696 -> 550,788
-624,367 -> 391,367
-133,536 -> 432,835
-635,154 -> 169,154
-396,149 -> 396,166
-796,505 -> 886,505
-348,235 -> 530,235
-428,851 -> 240,851
-535,441 -> 637,441
-661,175 -> 782,175
-589,780 -> 99,290
-771,746 -> 771,239
-16,567 -> 821,567
-320,572 -> 320,136
-200,990 -> 513,990
-984,987 -> 11,14
-60,712 -> 60,137
-629,405 -> 738,405
-541,536 -> 541,225
-250,579 -> 507,579
-825,264 -> 974,264
-380,832 -> 262,832
-598,695 -> 598,159
-716,782 -> 418,782
-124,698 -> 713,698
-930,83 -> 212,801
-389,287 -> 344,287
-571,788 -> 106,788
-519,498 -> 135,114
-281,761 -> 221,761
-295,133 -> 295,654
-686,960 -> 592,960
-984,495 -> 984,913
-677,751 -> 677,102
-252,326 -> 252,824
-921,500 -> 357,500
-608,908 -> 608,381
-587,878 -> 587,677
-392,929 -> 449,929
-239,444 -> 822,444
-87,897 -> 252,897
-865,887 -> 368,887
-368,934 -> 368,308
-64,950 -> 182,950
-747,429 -> 636,540
-378,109 -> 378,865
-187,266 -> 856,935
-159,769 -> 708,769
-71,119 -> 892,940
-629,825 -> 87,283
-900,903 -> 900,656
-749,703 -> 812,703
-225,421 -> 842,421
-15,896 -> 837,74
-800,887 -> 58,145
-456,798 -> 679,798
-434,963 -> 434,166
-508,491 -> 976,959
-809,540 -> 809,614
-624,632 -> 975,983
-733,102 -> 195,640
-83,691 -> 623,151
-140,257 -> 29,257
-429,934 -> 429,482
-702,91 -> 702,137
-986,185 -> 986,386
-84,920 -> 448,920
-927,779 -> 927,679
-903,345 -> 546,345
-303,523 -> 303,862
-360,877 -> 360,202
-544,593 -> 544,802
-27,192 -> 27,837
-105,24 -> 574,24
-955,783 -> 556,384
-416,85 -> 416,322
-923,139 -> 553,139
-527,523 -> 828,523
-788,320 -> 949,320
-391,652 -> 391,166
-754,378 -> 607,378
-563,409 -> 563,27
-285,169 -> 285,883
-16,10 -> 988,982
-452,563 -> 452,479
-881,800 -> 881,542
-564,272 -> 457,272
-887,441 -> 887,298
-941,837 -> 119,15
-606,137 -> 606,152
-135,472 -> 135,322
-881,775 -> 881,132
-566,263 -> 406,103
-912,696 -> 912,965
-123,554 -> 123,911
-735,737 -> 533,939
-61,197 -> 534,197
-91,856 -> 91,465
-624,411 -> 624,247
-607,899 -> 607,786
-139,408 -> 466,735
-89,274 -> 545,730"
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part1(&input), 5);
+        assert_eq!(Day5::part2(&input), 12);
+    }
+
+    #[test]
+    fn keeps_lines_straight_or_at_45_degrees() {
+        assert_eq!(Day5::parse("0,0 -> 1,3").err(), Some(ParseError::new(1, 8, "a point in line with the first, across or at 45 degrees")));
+        let diagonals = Day5::parse("0,0 -> 2,2\n2,0 -> 0,2").unwrap();
+        assert_eq!((Day5::part1(&diagonals), Day5::part2(&diagonals)), (0, 1));
+    }
+
+    #[test]
+    fn keeps_points_on_the_grid() {
+        assert_eq!(Day5::parse("1,-2 -> 3,4").err(), Some(ParseError::new(1, 3, "a y coordinate from 0 to 4096")));
+        assert_eq!(Day5::parse("0,0 -> 4097,0").err(), Some(ParseError::new(1, 8, "an x coordinate from 0 to 4096")));
+        assert_eq!(Day5::parse("0,0 -> 2147483648,0").err(), Some(ParseError::new(1, 8, "an x coordinate")));
+    }
 }
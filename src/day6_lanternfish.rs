@@ -1,26 +1,64 @@
 use std::collections::HashMap;
 use std::str::FromStr;
+use crate::error::{parse_token, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::reference::Reference;
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    // let input = "3,4,3,1,2";
-    let input = get_input();
+pub struct Day6;
 
-    let school: School = input.parse().unwrap();
-    println!("initial state: {}", school._get_state());
+impl Solution for Day6 {
+    const DAY: usize = 6;
 
-    const NUM_DAYS: u32 = 256;
+    type Input<'a> = School;
+    type Part1 = u64;
+    type Part2 = u64;
 
-    // for _i in 0..NUM_DAYS {
-    //     school._advance_day();
-    //     // println!("after {} days: {}", _i + 1, school._get_state());
-    //     // println!("{}", _i);
-    // }
-    //
-    // println!("after {} days, there are {} fish", NUM_DAYS, school._size());
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let school: School = input.parse()?;
+        debug!("initial state: {}", school._get_state());
+        Ok(school)
+    }
+
+    fn part1(school: &School) -> Self::Part1 {
+        fish_after(school, 80)
+    }
+
+    fn part2(school: &School) -> Self::Part2 {
+        fish_after(school, 256)
+    }
+}
+
+impl Generate for Day6 {
+    const SIZE: Option<Size> = Some(Size::new("lanternfish", 300));
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| rng.range(1..=5).to_string()).collect::<Vec<String>>().join(",")
+    }
+}
 
-    println!("after {} days, there are {} fish", NUM_DAYS, school.size_after(NUM_DAYS as i32));
+impl Reference for Day6 {
+    /// Every fish simulated one by one; by day 256 there are far too many for that.
+    fn reference_part1(school: &School) -> Option<Self::Part1> {
+        let mut school = school.clone();
+        for _ in 0..80 {
+            school.advance_day();
+        }
+        Some(school.size() as u64)
+    }
+
+    fn reference_part2(_school: &School) -> Option<Self::Part2> {
+        None
+    }
+}
+
+fn fish_after(school: &School, num_days: u32) -> u64 {
+    let count = school.size_after(num_days as i32);
+    info!("after {} days, there are {} fish", num_days, count);
+    count
 }
 
+#[derive(Clone)]
 struct Lanternfish(u8);
 
 const ROUND_TIMER: i32 = 7;
@@ -43,7 +81,7 @@ impl CalcCache {
 }
 
 impl Lanternfish {
-    pub fn _advance_day(&mut self) -> Option<Lanternfish> {
+    pub fn advance_day(&mut self) -> Option<Lanternfish> {
         if self.0 == 0 {
             self.0 = ROUND_TIMER as u8 - 1;
             Some(Lanternfish(NEWBORN_TIMER as u8 - 1))
@@ -65,7 +103,7 @@ impl Lanternfish {
             return *value;
         }
         while num_days > 0 {
-            // println!("{}", num_days);
+            // info!("{}", num_days);
             count += Self::do_count_after_days(NEWBORN_TIMER, num_days, cache);
             num_days -= ROUND_TIMER;
         }
@@ -78,39 +116,40 @@ impl Lanternfish {
 
 
 impl FromStr for Lanternfish {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let i: u8 = s.parse().or(Err(()))?;
+        let i: u8 = parse_token(s, s, "a timer value")?;
         Ok(Lanternfish(i))
     }
 }
 
-struct School(Vec<Lanternfish>);
+#[derive(Clone)]
+pub struct School(Vec<Lanternfish>);
 
 impl FromStr for School {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(School(s.split(',')
-            .map(|s| s.parse())
-            .collect::<Result<Vec<Lanternfish>, _>>().or(Err(()))?))
+            .map(|part| part.parse::<Lanternfish>().map_err(|e| e.relative_to(s, part)))
+            .collect::<Result<Vec<Lanternfish>, _>>()?))
     }
 }
 
 impl School {
-    pub fn _advance_day(&mut self) {
+    pub fn advance_day(&mut self) {
         let mut new_fish: Vec<Lanternfish> = Vec::with_capacity(self.0.len() / (ROUND_TIMER as usize / 2));
 
         for fish in self.0.iter_mut() {
-            if let Some(newborn) = fish._advance_day() {
+            if let Some(newborn) = fish.advance_day() {
                 new_fish.push(newborn);
             }
         }
 
         self.0.append(&mut new_fish);
     }
-    pub fn _size(&self) -> usize {
+    pub fn size(&self) -> usize {
         self.0.len()
     }
     pub fn _get_state(&self) -> String {
@@ -126,6 +165,17 @@ impl School {
 
 impl School {}
 
-fn get_input() -> &'static str {
-    "3,1,5,4,4,4,5,3,4,4,1,4,2,3,1,3,3,2,3,2,5,1,1,4,4,3,2,4,2,4,1,5,3,3,2,2,2,5,5,1,3,4,5,1,5,5,1,1,1,4,3,2,3,3,3,4,4,4,5,5,1,3,3,5,4,5,5,5,1,1,2,4,3,4,5,4,5,2,2,3,5,2,1,2,4,3,5,1,3,1,4,4,1,3,2,3,2,4,5,2,4,1,4,3,1,3,1,5,1,3,5,4,3,1,5,3,3,5,4,2,3,4,1,2,1,1,4,4,4,3,1,1,1,1,1,4,2,5,1,1,2,1,5,3,4,1,5,4,1,3,3,1,4,4,5,3,1,1,3,3,3,1,1,5,4,2,5,1,1,5,5,1,4,2,2,5,3,1,1,3,3,5,3,3,2,4,3,2,5,2,5,4,5,4,3,2,4,3,5,1,2,2,4,3,1,5,5,1,3,1,3,2,2,4,5,4,2,3,2,3,4,1,3,4,2,5,4,4,2,2,1,4,1,5,1,5,4,3,3,3,3,3,5,2,1,5,5,3,5,2,1,1,4,2,2,5,1,4,3,3,4,4,2,3,2,1,3,1,5,2,1,5,1,3,1,4,2,4,5,1,4,5,5,3,5,1,5,4,1,3,4,1,1,4,5,5,2,1,3,3"
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "3,4,3,1,2";
+
+    #[test]
+    fn example() {
+        let input = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&input), 5934);
+        assert_eq!(Day6::part2(&input), 26984457539);
+        assert_eq!(Day6::reference_part1(&input), Some(5934));
+    }
+}
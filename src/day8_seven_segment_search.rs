@@ -1,67 +1,81 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
+use crate::error::{next_token, parse_lines, ParseError};
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
 
-pub(crate) fn run() {
-    let input = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
-edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
-fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
-fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
-aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
-fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
-dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
-bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
-egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
-gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
-//     let input = _get_input();
+pub struct Day8;
 
-    let data: Vec<Sentence> = input.split('\n').map(|s| s.into()).collect();
+impl Solution for Day8 {
+    const DAY: usize = 8;
 
-    println!("count 1,4,7,8: {}", data.iter().map(|s| s.output.iter().filter(|s| s.is_1_4_7_8()).count()).sum::<usize>());
+    type Input<'a> = Notes<'a>;
+    type Part1 = usize;
+    type Part2 = u32;
 
-    let data: Vec<InputOutput> = input.split('\n').map(|s| s.parse().unwrap()).collect();
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(Notes { text: input, entries: parse_lines(input, |line| Ok((line, line.parse::<InputOutput>()?)))?, values: None })
+    }
 
-    let mut sum = 0u32;
-    for sentence in data {
-        let mut mapping_candidates: MappingCandidates = Default::default();
-        for word in sentence.input.iter().chain(sentence.output.iter()) {
-            mapping_candidates.reduce(word);
-        }
-        println!("mapping candidates:\n{:?}", mapping_candidates);
-        let mapping: Mapping = mapping_candidates.try_resolve().expect("could not resolve candidates");
+    fn prepare(notes: &mut Notes<'_>) -> Result<(), ParseError> {
+        notes.values = Some(notes.entries.iter()
+            .map(|(line, entry)| entry.value()
+                .ok_or_else(|| ParseError::at(notes.text, line, "signal patterns of the ten digits, wired the same way")))
+            .collect::<Result<_, _>>()?);
+        Ok(())
+    }
 
-        println!("mapping: {:?}", mapping);
-        let output = mapping.get_value(sentence.output);
-        println!("output: {}", output);
-        sum += output;
+    fn part1(notes: &Notes<'_>) -> Self::Part1 {
+        notes.entries.iter().map(|(_, s)| s.output.iter().filter(|w| w.is_1_4_7_8()).count()).sum()
+    }
+
+    fn part2(notes: &Notes<'_>) -> Self::Part2 {
+        notes.values().iter().sum()
     }
-    println!("sum: {}", sum);
 }
 
-struct Sentence<'a> {
-    _input: Vec<Word<'a>>,
-    output: Vec<Word<'a>>,
+/// Every entry with its line, and once prepared, the value each one's output shows.
+pub struct Notes<'a> {
+    text: &'a str,
+    entries: Vec<(&'a str, InputOutput)>,
+    values: Option<Vec<u32>>,
 }
 
-impl<'a> From<&'a str> for Sentence<'a> {
-    fn from(s: &'a str) -> Self {
-        let map_words = |s: &'a str| s.trim().split_whitespace().map(|w| Word(w)).collect::<Vec<Word>>();
-        let mut parts = s.split('|');
-        Self {
-            _input: map_words(parts.next().unwrap()),
-            output: map_words(parts.next().unwrap()),
-        }
+impl Notes<'_> {
+    fn values(&self) -> &[u32] {
+        self.values.as_deref().expect("the outputs are decoded by Day8::prepare")
     }
 }
 
-struct Word<'a>(&'a str);
-
-impl<'a> Word<'a> {
-    pub fn is_1_4_7_8(&self) -> bool {
-        match self.0.len() {
-            2 | 4 | 3 | 7 => true,
-            _ => false
-        }
+impl Generate for Day8 {
+    const SIZE: Option<Size> = Some(Size::new("entries", 200));
+
+    /// Each entry rewires the segments at random and lists the ten digits in any order.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const DIGITS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+        (0..size)
+            .map(|_| {
+                let mut wires: Vec<char> = "abcdefg".chars().collect();
+                rng.shuffle(&mut wires);
+                let pattern = |digit: &str, rng: &mut Rng| {
+                    let mut pattern: Vec<char> = digit.bytes().map(|segment| wires[(segment - b'a') as usize]).collect();
+                    rng.shuffle(&mut pattern);
+                    pattern.into_iter().collect::<String>()
+                };
+                let mut digits = DIGITS;
+                rng.shuffle(&mut digits);
+                let patterns: Vec<String> = digits.iter().map(|digit| pattern(digit, rng)).collect();
+                let output: Vec<String> = (0..4)
+                    .map(|_| {
+                        let digit = *rng.choose(&DIGITS);
+                        pattern(digit, rng)
+                    })
+                    .collect();
+                format!("{} | {}", patterns.join(" "), output.join(" "))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 }
 
@@ -69,7 +83,7 @@ impl<'a> Word<'a> {
 struct Wiring(u8);
 
 impl Wiring {
-    pub fn _is_1_4_7_8(&self) -> bool {
+    pub fn is_1_4_7_8(&self) -> bool {
         match count_set_bits(self.0) {
             2 | 4 | 3 | 7 => true,
             _ => false
@@ -90,6 +104,22 @@ impl Wiring {
     pub fn position_of_set_bits(&self) -> impl Iterator<Item=usize> {
         self.to_bits().into_iter().enumerate().filter(|(_, b)| *b == 1).map(|(i, _)| i)
     }
+    /// The digit these segments light up, if any.
+    pub fn digit(&self) -> Option<u32> {
+        match self.to_bits() {
+            [1, 1, 1, 0, 1, 1, 1] => Some(0),
+            [0, 0, 1, 0, 0, 1, 0] => Some(1),
+            [1, 0, 1, 1, 1, 0, 1] => Some(2),
+            [1, 0, 1, 1, 0, 1, 1] => Some(3),
+            [0, 1, 1, 1, 0, 1, 0] => Some(4),
+            [1, 1, 0, 1, 0, 1, 1] => Some(5),
+            [1, 1, 0, 1, 1, 1, 1] => Some(6),
+            [1, 0, 1, 0, 0, 1, 0] => Some(7),
+            [1, 1, 1, 1, 1, 1, 1] => Some(8),
+            [1, 1, 1, 1, 0, 1, 1] => Some(9),
+            _ => None
+        }
+    }
     pub fn map_wire(&self, mapping: &Mapping) -> Self {
         Self((0usize..7)
             .filter(|&i| self.0 & 2u8.pow(i as u32) != 0)
@@ -100,19 +130,7 @@ impl Wiring {
 
 impl From<Wiring> for u32 {
     fn from(num: Wiring) -> Self {
-        match num.to_bits() {
-            [1, 1, 1, 0, 1, 1, 1] => 0,
-            [0, 0, 1, 0, 0, 1, 0] => 1,
-            [1, 0, 1, 1, 1, 0, 1] => 2,
-            [1, 0, 1, 1, 0, 1, 1] => 3,
-            [0, 1, 1, 1, 0, 1, 0] => 4,
-            [1, 1, 0, 1, 0, 1, 1] => 5,
-            [1, 1, 0, 1, 1, 1, 1] => 6,
-            [1, 0, 1, 0, 0, 1, 0] => 7,
-            [1, 1, 1, 1, 1, 1, 1] => 8,
-            [1, 1, 1, 1, 0, 1, 1] => 9,
-            _ => panic!("out of range wiring to u8")
-        }
+        num.digit().expect("out of range wiring to u8")
     }
 }
 
@@ -142,28 +160,74 @@ impl From<u32> for Wiring {
 }
 
 impl FromStr for Wiring {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Wiring(s.chars().into_iter().map(|c| 2u8.pow(c as u32 - 'a' as u32)).sum::<u8>()))
+        let mut wiring = 0u8;
+        for (i, c) in s.char_indices() {
+            if !('a'..='g').contains(&c) {
+                return Err(ParseError::new(1, i + 1, "a segment between 'a' and 'g'"));
+            }
+            let segment = 2u8.pow(c as u32 - 'a' as u32);
+            if wiring & segment != 0 {
+                return Err(ParseError::new(1, i + 1, "each segment at most once"));
+            }
+            wiring |= segment;
+        }
+        if count_set_bits(wiring) < 2 {
+            return Err(ParseError::new(1, 1, "a pattern of at least two segments"));
+        }
+        Ok(Wiring(wiring))
     }
 }
 
-struct InputOutput {
+pub struct InputOutput {
     input: Vec<Wiring>,
     output: Vec<Wiring>,
 }
 
 impl FromStr for InputOutput {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map_words = |s: &str| s.trim().split_whitespace().map(|w| w.parse().unwrap()).collect::<Vec<Wiring>>();
+        let map_words = |part: &str| part.split_whitespace()
+            .map(|w| w.parse::<Wiring>().map_err(|e| e.relative_to(s, w)))
+            .collect::<Result<Vec<Wiring>, _>>();
         let mut parts = s.split('|');
-        Ok(Self {
-            input: map_words(parts.next().unwrap()),
-            output: map_words(parts.next().unwrap()),
-        })
+        let patterns = next_token(s, &mut parts, "the signal patterns")?;
+        let outputs = next_token(s, &mut parts, "'|'")?;
+        let entry = Self { input: map_words(patterns)?, output: map_words(outputs)? };
+        if entry.input.len() != 10 {
+            return Err(ParseError::at(s, patterns, "ten signal patterns"));
+        }
+        if entry.output.len() != 4 {
+            return Err(ParseError::at(s, outputs, "four output values"));
+        }
+        Ok(entry)
+    }
+}
+
+impl InputOutput {
+    /// The value the output shows, if the patterns can be wired so that every one of them lights up a digit, as
+    /// every puzzle entry can.
+    fn value(&self) -> Option<u32> {
+        let mapping = self.mapping()?;
+        if !self.input.iter().all(|word| word.map_wire(&mapping).digit().is_some()) {
+            return None;
+        }
+        let output = mapping.get_value(&self.output)?;
+        debug!("output: {}", output);
+        Some(output)
+    }
+    fn mapping(&self) -> Option<Mapping> {
+        let mut mapping_candidates: MappingCandidates = Default::default();
+        for word in self.input.iter().chain(self.output.iter()) {
+            mapping_candidates.reduce(word);
+        }
+        trace!("mapping candidates:\n{:?}", mapping_candidates);
+        let mapping = mapping_candidates.try_resolve();
+        trace!("mapping: {:?}", mapping);
+        mapping
     }
 }
 
@@ -199,12 +263,10 @@ impl Mapping {
     pub fn get(&self, i: usize) -> u8 {
         self.0[i]
     }
-    pub fn get_value(&self, words: Vec<Wiring>) -> u32 {
+    /// The number `words` show once rewired, if each of them lights up a digit.
+    pub fn get_value(&self, words: &[Wiring]) -> Option<u32> {
         words.iter().rev().enumerate()
-            .map(|(i, word)| {
-                let val: u32 = word.map_wire(self).into();
-                10u32.pow(i as u32) * val
-            })
+            .map(|(i, word)| word.map_wire(self).digit().map(|val| 10u32.pow(i as u32) * val))
             .sum()
     }
 }
@@ -275,7 +337,7 @@ impl MappingCandidates {
     fn reduce_to_targets(&mut self, source: &Wiring, targets: &Vec<Wiring>, empty_target_bits: &Wiring) {
         let target_space = self.mapped_space(source);
         let reduced_targets: Vec<&Wiring> = targets.iter().filter(|target| target.0 & target_space == target.0).collect();
-        println!("source: {:?}, targets: {:?}, reduced targets: {:?}", source, targets, reduced_targets);
+        trace!("source: {:?}, targets: {:?}, reduced targets: {:?}", source, targets, reduced_targets);
         let aggr_target = reduced_targets.iter().fold(0u8, |val, current| val | current.0);
         let source_bits: HashSet<usize> = source.position_of_set_bits().collect();
         let fully_captures = Wiring(aggr_target).position_of_set_bits().count() == source_bits.len();
@@ -302,206 +364,82 @@ fn count_set_bits(number: u8) -> u8 {
     count
 }
 
-fn _get_input() -> &'static str {
-    "dbcfeag cgaed fe bfgad aefcdb efa efgda gcef dcaebg dfeagc | fae cfge fae baefdc
-ea bfecadg bgafcd deac ebcag eab debfag eabgdc bcgfe bagdc | cdbagf fagbcd bae fcegb
-fbgdec cbgeaf cbfag bd bgda cgbfda dbf fecda bfadc cbedagf | gecfdb cbfga bacgef dfb
-geabdf cfbge dcbeaf aebdf dgea acdgfbe fag gfbea ag cabdfg | dfeabc dbaefc fdaebc fga
-gcae cefdg abdcgfe cg fcg afcegd dbgfea agdfe dcafbg bcdef | gc caefdg gcf egdcf
-efgcb fbac dbefg bce efacg fbgcdae cb egfbca dgfaec dcgbea | fgecb bcfa bc efcag
-efcgdb da bdefg dfegba fbacg gbcdea dbafceg adb feda fadgb | da ad cgabde cebdga
-ca fcabegd facd agbed acg cfadeg acdeg agcefb cdfgeb efcdg | cdeafg cag efgadc gacbfe
-fdacgb cfa cfage gabce dfagce edagfb dcbfeag fc fced gfdae | gabdef fdegba gaedcf fac
-acgdbf faedcbg fa cbgdae fgbdae aedbg adf fbea cfdge fedga | fad egdcf feab bfcagd
-ged cadgf gcdafeb cabgfd cgedf aedc ebfcg faebgd ed fcadeg | dfgca gabefd dafgc ecfgd
-bcedga dceba gcadfbe dfec adcbfe fda fabge df fbdae bcfgda | adgcfbe fbgae dgebafc gacedb
-fgcabde edbcfg efcdga dfbcea dbaeg fea fa dbaef acfb dbfec | aecbfd aedcfb bdaef abfc
-cfbd gefad dce fdeac dc ecabfd dgabce gabdecf afbce fagceb | bdfc bcgaed bgdace afegd
-acfgde egdfb dcaf gecfd ecdbgfa gcdbae cefgba dcg gcefa cd | eadcbg cdfega fdgeac dgc
-cadfgb edcgbfa dc cgd fegcb dfabg cdaebg dafc fcbdg gbdeaf | afdc adcf befdag gfcdb
-deafg ebafdcg gcdfba bge eb fgcbd gbefac bdce fdebg gdcfeb | cbdagf bdce gdfeb fgead
-acbdef bg agebdf afbcg fgbeca edafcgb eacfb gba ebgc fgcad | begdaf gcbe ebdfag dabefg
-ga agc bdegcaf gefbc cfdea dafgec fagce dgebca aefbdc gdfa | acfedbg bfaecdg gca facedg
-abdg ad egcabf ebagf afd gdfceab dcebf dagbef cdgafe deabf | bfedc cbedf gdab afd
-efbadc cafbd eba fegda fcbe dbefa efcbdag adbcge badfcg be | be agdfe cedbag dcbeaf
-adegb cgafb badgcfe fged fgaebd eabgf bceafd eaf eacbdg fe | bdgeac adgbfe bdgae acfgbed
-dbfega befag gbfdec gacfed fcgdabe bgf deagf bf dafb bceag | bdfa cagbe gceba agbdfe
-fecagd cbfeagd fdecb fg fgbde ebgda cagdbe fge gfab agefdb | geabcd gdbea ebgafd egdab
-ab agdcfe dcfgb cba adefcb adfbc bdae ebacgf aegbdcf fcade | gfecda dfabc edab bfcad
-bcfae ebd gefdacb fdceg dbaegc gdbf gcefbd cedfb adfgce bd | gebdcf bd dgfb ebdcgf
-gbcafde cdaef dfe ecbf egcda fadgcb bfdac dacfbe bgefda ef | dcgafb dfaebg fbec agbdfc
-fe cfedab dcfgeb gdbef gadeb cegf fgcbd fbe abdcgf ebacgfd | debfg abecfd adbefc gdcbfe
-ad cbfeda acegfb gdcaeb adcgfbe bda abefc dcabf fgcdb afde | gdcbf cedbaf dbagec becfa
-acegb efcgab edcfgab aebdc gce ge fage egcdfb agfbc cbdfga | eg cafegb fcbeag acbgef
-bcaf agefdc bcegaf eaf bdgfe agfdecb efgab af abegc dgacbe | defbg dgaebc eaf cebga
-edabcf bdeg gd cadebg dgcab gfcaed febdgca cdg beacd cagbf | befadcg fbgca afdecg bgceda
-gadfeb ag gfda cbeda fbeacg bgdcaef dgfeb eag gbdea fegcbd | cabefg degbcf gdbef bcdgef
-efbcda cgfd dgefac dfcgbae degaf adg debacg aedcf gd aebgf | ecfad cgbade aegdcb gfbae
-bdc cdfgb cabgf gabd fdebagc eabgcf bd abdcef gfcabd fgedc | faecdb fgecd cbfdag bcfadg
-dfagbec ceg egfad fdeabg acedg afedcg gc bcgaef eacdb fdcg | gcfd eadbc dbegcfa eabcd
-gfb egfba eadcfb gf adfg dgebfa dacfegb eagcb cbedgf fadeb | edfba afgebd adbegf efcbda
-fec caegd efgb cgaedfb gfbdec gfdbc ef cfdgab fdcge fbaedc | abcdef gcfbd bfegdac gcead
-gefdba abcef ead gedcab ad bfdecg cbged ebcad cafbdeg cadg | defcgb ade bgecd ceabf
-cabg adgefbc cb ebafg febgc fbc gdcfe cafedb egfabc aebdgf | bcdafeg ecbafd adbecf fbcge
-acged febgd agdcfb bdceg gdbecf bc bgc cfeb cfgbaed bgefad | dceag efbc cbfdeg ebcdfg
-gab adgcbef cebfg abdce dcbefg bafdgc abefgc aegf ag cgbea | gaceb aegf fgcbe cgebf
-fa baf cadfbge dbgef ebacgd dfceba adbec fdeba cfae dacgfb | dabcef dafcbe acgdbe ecdfab
-fgeca gecadfb aecdfb fb gcbed dfgb cgdbea ecfgb gfbdec efb | abcedg bf bf agedcfb
-fg aefgd bafcde gcfe bdgae gfa fedca cagdfb cfaedg ebgacfd | dcfgab dgfea gf cafdgb
-afbcge debgc cafdbg ae eadf afdgb gbdeaf aeb cgfebad abgde | deaf cbafge cbegd aedbg
-fbc afcegb fadgc fbeda cbed ecdafb cfdab cb fegdcab bdaefg | cgfad ecbd edafb cgafd
-fegbcd abcf gfabedc bgcad gfacd gedab efdagc dfgcab cdb bc | fbca cdbag cdfgab cgedbfa
-gfca gbdef eadcfg ebdcag dcg afecd gc decafbg fcegd bcfade | gcefd bcdafge cdefg edfgc
-gdae ad gecfd dcfaeb dfegcb gdcaf fdacge adf agbcf cebfgad | dgebafc edfgc da cbgafed
-ceg febgdca bfgdea gc adcfge cefbag agdc edfcb dfgce gadef | ceg ebdagf agdfe afbgce
-dabegcf bcdfag gdecb gdbeca gaebd fbdce bdagef cg bgc ecag | dfcabg abfdge gadbe cg
-dacefb efc gfaebc gbfe egcda agfbdc beadfcg fe fbcga cgafe | ef fbaedc ef efc
-cbgae faedbg fegab agc ebdgc eacf bgeafc ca cgdbafe gadcbf | eagfb aebgf gcbfad gfeba
-dcf dacbg bdcaef dfeacg gacfe cdfag fgde faecgb daebcgf df | fcd fecadb egacf deabcf
-egdfa dfaec gdab ga agf dbefga dgebf afbgecd bgafec bgefcd | fegbd gdfbe begdfc efcbadg
-cfd edafbc afbd cadbeg fgecbad gcaef dacef df dbcae efcdgb | adbf cabde baegdc cgbeda
-cbag gc cdgbe defbg agebdfc becda dbgcae cdg efacbd fecgad | fcegabd abgc dacbe cdaebg
-bf dacgf dfb befc fbedcg gebdc dbacefg abdegf gcdfb bedacg | bfec feagcbd egdcb daegbf
-aebgfdc gadcf gcdfeb dagce agdeb acfe bgacdf dec gfecad ec | ceadg edcag fdcga cdbagef
-befdgca dgcbf fedagc be bfe dcefb ecfdba acfed ebca ebgfda | dfbcae bafgdce bgcdf egbdfa
-bfa bdagfc dbega bfgea eagfdb dbgace bcefg dcbgeaf fa defa | ebcgdaf aecgdbf fgcadb fa
-agcdeb cgf agbdcef bgdfe efac cgefb abefgc cagdbf fc gebca | fegbc acfe becga deagbc
-adbfce agebd eadcbfg ega abedf fgab dgebc dgfcea ag agdebf | gdfcabe ebadf ga bfag
-gcebfd feacdbg acegfb afgce gbac geb cdaefg daefb gb fgbae | agefb gb adfbe efabd
-gadbfc ebgad dgecba fgae eacgdbf af ebcfd afd bdeaf gadefb | fa bdgaef gdbacf af
-bfeacd edgcabf dg gcabd cdaeb acfgb bcgdae dgae cgd bgfdec | bedac acbed abcdfge dgc
-cdage dgabec cbadg ge acfde edg becfagd gbec adbcgf fbeadg | cdbgea cgdaeb cegad gdeac
-ab gbaf cdegba fceadbg gfbecd bca eadcf fgeabc becfa cebfg | cagfeb cefabg fcbeg eadcgb
-afdec bcaefg ca abcd efcdg aec aebdf cdafbe begdaf eadgbfc | cdfeg afdce edfac gdfabe
-faebd fed df bgdcaef cbdega gdbeaf fbecgd badge afebc fadg | dcebfg dgaf fd cdegfba
-abdgf efg fe gdeabf cdbge cfadeg gdbef cafgdeb fbea bgcadf | bgdfe fabe gbfadc gbfcda
-agdfe cfbd dgfca cd abgfc cdgbea gbecfa gbadcef cda dfgbac | cegfba fbcd bfdc dca
-acdb bdcega dbfcge aedfgb dec fcega dc adegc fgceabd gaedb | badeg bdecgf cd cdageb
-abe ab dbecfag adgec agbf eadbg cbeafd fbcegd gadbfe bfgde | ebgfd efdgb gceadbf ba
-dgfceba egbcaf fgc bfgdca cbgfe cg faebc agce gdfeb debcaf | bedfac edbfg aegc eabcdgf
-bdfac dcfeb dbcefa dbgcaef fbae egdcb fgaecd fec cdbafg ef | bdfagc dcfabg gdbfeca eabf
-gfca dbcaf bfcgd fcgdeab degcb abcedf cbagfd gfb fg fdgabe | gcaf bfg fbg gf
-efcagd dbag fgd defgacb aebfg dbgfe bdeafg edfbc gd fgecab | fbeag ecgfabd degacf debgfac
-efcbg bacfe gbdce fge acegbdf cgdf aebdfg dgbcae ebcdgf gf | caefb cbfdage ecfgb bgedc
-fgdeb dcbfg fgae fbeagd edbfa aegdbc egb gafecdb bfdeca eg | dgbcea bdaef gfbde dfgbea
-bdcaf gdafc cbf bdefa cb fgceda bdfagc fcbgde afcgedb cbga | gbca fbdca cegfdb fcagd
-cfebgd cedgfa dcfabge dbfgc dgeb ge fcegb gfe abfdcg fecab | fge fabec gfbadec bfceg
-aeb ab gebfd abcdef edcag cdegaf gaedbc bgfecda debag gbac | beacgd bdfeg afgecd gedbf
-ab gebfadc acfge edafbc abegc dcegab gbecfd gbda cab ebcdg | dagb cegfa bca bac
-gaedb ebcd fgcea cba dcgafb febadg egcab adcbge efgadcb cb | dfcagb cbed bagce dbce
-ca dgfceb bfeacd fcbgd bdgca cfag gaedb abc fcbagd fgecadb | cgdfab cfag bac cbfegad
-gade gafcb bfgecd bge ecbda gdcbea eg beacg fdaecb agfbecd | dgae bgfca abegc cfdebg
-eac cdbef ac gfdae dagc afcedgb dcfae dafcge dbfega caebfg | gafceb dbeacgf cdga fbcde
-dbagfc bgedca df afgec daf beagdfc aefcbd bedac acfed dbfe | bedca cdaef baecfd befcad
-fdacb baedc acedfb bacedg fd gfcab ecdf daf fedgba befgdac | egabcd cedab eabdfg adf
-efdgc dfecga adfbe ac cage adc gfbdec cagbfd efdabgc caefd | efdab efdgcab ca cedfg
-ca acgf cadbgfe egfcab gefab cea defbc fecab edbcga fbgdae | cfeba efdcbag dacebg agefcdb
-bcedfa agecd ebgf fcbdg fde cgfed acgbfd cagfdeb ef cgbdef | fgeb bcfdg def ef
-bfcdea ga fbdegac gecdf cfdab bcgadf facdg aegbcd gac afgb | cbadfg facdb afbg dfecba
-fdcae afeg fa bceda dfebcga afcgdb dgecf acf fgceda cbfdeg | fa bdfcga fcbegd efacd
-efgbcd cf bdgace fgbad dfc fceabd dbaegfc bdcgf becdg gfce | bcedaf adbegc badgec bgcfd
-efcba ebdfga fda gdefbc da facde dacg fgedc gfeabcd acdefg | da bgafde cegfd edafgb
-daegb cbge bgcaed afgced ge cdabe fdbcaeg fcbeda bgfda eag | gae egcb egbc egdafcb
-cefda fadg bfedac bedfacg dgecfa eag ga fegcb becagd gcefa | dcfea eag cedfa bgfcaed
-dcfe gbeafd df bdcaf ebfcag fcedagb cdbag dcfaeb eabcf fbd | dfce geacbf cefd ebdacf
-efgbc agbd gd eadgcf becdfa bdace dge debgc adecgb bfdgcae | bgcfe bedcg abgd gdba
-cdbf edbfgc feadg cd ced gaecdb degfc efcgb fcbgaed befcga | cfabgde fgdcbe efacgb abgcef
-ae caegfd eadgc fedgba fecgd gbacd efac aed afcbged cgdbef | ae eacf abdegcf eda
-fc afcge dbgecf adcgfe eafbg gdebacf eagcd gfc aecdgb afcd | cfg dgeac afbge edgcba
-aedcg gfd cafgebd fg eadbgc adfcb cefg gabefd dacfg efcgda | dgeca afgedb cfdga aefgcd
-bfgaed facedb fbec dbgca cagfbde egcfda efdac acbfd fbd fb | efbc agcdb fagdbe bacdf
-fac dcae fgbda fagbce bdfac cbdfe ca cadbfe aegcdfb cebdfg | cdea cfbdeg ac afdcb
-geabcfd gfcaeb gcbead eba facbg fdgcab ea bgafe fdgbe caef | bagef cafgb afbeg cbgaf
-gcfdeb ceg fabcge edbag fcabdg bcgaf ceaf ec agefbdc cbega | bgafc baecgdf bcfged gbdfeac
-cf adgef cfgbaed bfdc gfc egcdfb dcgaeb degbc fcedg ecagfb | fdbc cgdbe gdabce gfdce
-bc afgcb cfdga dfcb cbdgea bgc efcgdab cfedga beafg bagcfd | bc bc cbfadg adcbge
-egdfb fa agfbe eacf befagc fba fbedacg cadebg agcbe cfgbda | bagfec bcdgaf cbfgad dgcbfa
-acgbde acgdbef cba ca bcgfd cade egdafb aebgfc agbed adgcb | fdgbc gbadef gfcdbae edfgab
-egbac efacgd gfbd fbcdag fecbad dgc badcf gfcebda dbcag dg | bacdg facbegd cgd dfcgae
-adgcf fbgcda efcgda dcbea fedg cfebag fae ef acdef gecbfda | fcagd gcadf gcefdba gefabc
-fegdba ba cedgaf gbade bfad egfda gba cadbgef gbced fcaegb | gedaf bdega abgde bafd
-gbacef gdab defgc bagfdc dfb afgcb efdbac bd dfbcg befcgad | cdgef bd gbfac ecbgfad
-fgacd ef dagfce ecdfa fae adfbgc cegf febdga ceabd bgacedf | abcgfed gfce fae efacgdb
-fbgdc afedg gcbafd gfedbc cfdag acd fbdeacg acbfde cgab ac | gdcbf cedfbg dacgf dcafg
-becf feadb abfedc dfcageb gcbda bafedg aec daecfg acdbe ec | abdef fdeacg ace ecbf
-dc cbfegda bfeadg dbcg gabfd adc dbcfga bface fgcade fdabc | dacbgf dc cdfba dc
-bc bfgedc fbc adfbceg cegb degcf eabdf defcb cdfgae fcagbd | bcefd gceb fcagde cbfgad
-ge cabgd egca egd adgbecf bfdagc decbag agbde deafb ebdgfc | cagdfb bgecda cgedba fabdcg
-cg cfagbd gcedfa efdbc gcd gbefda fegad efdcg caegdbf agce | fgced dgfacb befdagc cefgda
-bfedgc bec ce fcaebd efabg gdec cgfdb aebdfcg bfgcda cgbfe | agebf bgdcfe ec agcbdf
-db dfcbge edgfca gabd dcb agfcd bafcd afbgcd ebafc edgfbca | acfdg cbdfa afceb gbda
-cadegbf egdfbc adc acbdg dgbcf adgfbc da cgfdae fbda ebcga | dgacb bagdc fgcbd aegbc
-geacf abc gfedba cbadfg dbcg gfdba cagfb cb bfdcae adfecbg | cb eadfgb bcfag dgcb
-abdfcge adbeg badcef egcf fg fgb fbdec gebfd abgfcd gfebdc | cbeadgf cfdbag gfdbe gf
-facdbe acgfbd dbc bd abgcd adceg bcegfa fdgb bfgca badcfeg | ceafdb bfcag dcgba bgdf
-gcda cadbe abcedf cabeg edbgcf begcda begaf cg acbdefg gbc | dbace gdbeac dacfgbe abdce
-cgf gf fadegbc afgcde abgced fecba ceagf dcagbf fgde cdaeg | gdebacf gf gdfe cfega
-abdef bc gecaf dbcgfae cbfg efcba bac acebfg degcba dcegfa | bcfadeg afceb edfgac gcabed
-bfcged bgdac becad fagc aegbdfc gacfdb dbgfc dafgbe ga bga | fgdecab abdcg cefabdg egdbfa
-abcfg baecf agec fcgaeb agedbf ag dacfbeg bag eafcdb fcgdb | bcdfea efdabg ga fdbgc
-ba baf dfbgce cgab cefgdab efcad efacbg cgfeb ebafgd cafeb | fdcegb abf baf ecabf
-cegafd egdcba gbedf cgfde cfad ecf fcegadb eagdc bgaefc cf | bfged defgb gbfed efc
-gedfa abdce fadbe ebfc fba badgce bf gabefcd fdbgac befcad | bcdgaf fdbgca bfa fgbdca
-bacef cg dgabec agfc edfgb cge agfdbec fcabeg cgebf abfdce | decfbga cagf adcgbe dgefb
-eafgd ce fbgaced ecfga eafdgc gdce cea cefdba cbfag bdgafe | eadfbg dgec efgca degfa
-decgabf fecd acd acbegf agdec gabed gacbfd faceg cd fgeadc | dc dcafge abecgf dc
-egd fdaebg gcbeadf fcdge ge adecf gbce dgfbac dcfgeb gdbcf | fdcae gfdbea bdfcg eg
-ad cbafe dabe dfa gbfcea ceafd dcbagf fabdce bfceadg dfgec | cebaf aecbgdf cgdbfa fbaec
-fc cdgbae gfcbad badcg fcdba feadb fac cgefad fdgbeac gfbc | cf cfabd caf gdfcea
-fdgace edac cdfag de dcfge facebgd cgebf dfebag def bafgdc | fdgac gacfbed edgacf dgcfa
-gedfac acedb fbcadge acgefb dbgf fdaeb dgafe fgaedb bf fba | fb bf bf agefcd
-gefbdca fagbed cgaeb gf ebdfa gdbf afebg feg feadcb geacfd | egf fedab gaefb eagbf
-efadgbc bfag edgcb cga cefab afbegc ga gaebc eafcdb daefgc | ga acg gcfbade dgecb
-efcgbd gebcf gcadbf agfce fcdeagb gfa ageb aefdc ga gacbef | gcebaf geba acfde fga
-ga adbg fdeac gfa fdgbcae dgfabc cfgdb fegbdc fcabge agcdf | adbcfg efadc agcdf cgdbf
-febdc dacfeb gbfce cfgae bge bgdf dgcfbea baecdg gb ecfgbd | gb fcedb becdf gb
-bgacfd cdfabeg adgce bcegd acdefg gb cgb bgae ecbadg bdfec | adcbfg gdfcea deafbcg ceadfg
-bc gdfeac fbaec egfcba dacbeg cab gacbfde eabfd faceg fgcb | afbegc cdgabe bagecf ebcdga
-fd dfe fdgae abegfd dbcaeg dfgb caedbf efcag dageb dbfeagc | dgfb gaedbf gfbd dgfcabe
-agefbc aefdb abdge cdef bfdcga dacbf fe gbdcefa eabcdf efb | cdbefa adfcbg fecagdb ebf
-gbade adecfg abegcdf dcbfa cgbafd cfeb dfaebc fe feabd efa | dbcfea bcfe fecb bafde
-dfba dfc efbac cgead ecfgab gdfecb edacbf df afced bgecfad | afdce bcfegd efgbac adgce
-abfcg egacf bceafg dabgc fgb fb fbecadg fbce gafcde gfdbea | abdgc defagb cbef egcaf
-caegb decgbfa cebf edacg cgfbae gbc gfbea baedgf bc bfcdag | agebc bgcae cbg gefba
-ad gadf feacg aed agbfce egcda fedcba abcdgef bcdge gecafd | gedfca ad febgac dcaebf
-cegbd bgcfda dbfeag acef gcead cagfde dcfga gae fagbced ae | defgab dacgf cebdg gecdfa
-fag edfcg cdfa fa ceagfd fdgabe gebac egbfcd egdcfab gcaef | acebg fag fgdec dfegcb
-abe bcdfa acdbgf befd eadbc acbfgde eb fgcaeb gacde fcdbea | eadbc afdgbc bgaefc cabfdeg
-afdgce dgabe dgfbe bdegfc dfg agcbdfe fd abegfc dcfb bfgce | debag bedgf ebdcfg cgebafd
-dgfcea eagcbd gfceb adgb cgdeb dg eacdbf bedcafg dge abedc | ecbgf dg dbfcgea ebcad
-dgecf badfce egcdfab efabd edfca ac badc gbcfae eagdfb caf | feadc febad dacef caf
-gfb bacgde acbdgf afbd bdgca bf bgfdcea ecfag dgfecb fbcag | fbgdcae gdfbca fbg fgaec
-bcdg dc gedabc dec cdeag cbedaf gfaebc dafge bcegdaf agceb | dfage bcefad cfbage fdgae
-gc cag agbec dacbe dcbg edcbgaf gdcfae dacebg eabfg febacd | gc cbdg gc fedcba
-gfdbace edbc ecfgab efcdbg ce fdcabg fedga egc cbdgf gedfc | ecdb edgfbc eagcfb cdafbg
-afceg bgdeaf daceg degacb fe dfec adfgec acdgefb gcbfa gfe | efg gcfea dcfe fcgea
-bdca gcfdbea dc acbedf gbfde cagefd cfebag dcf bdcfe ebafc | eafbc bcfage defcb agcbfe
-cabefg adgeb fd dacf afgcb bgefdca decbfg fbd dafgb dfagbc | gbfca cbeagf cfbgad fbd
-bacgef defcga afdgebc facebd afebc badc bdfeg dc dbfec dce | adbc eafdcb dabc fgedb
-cdf decb fagec dc dcgbaf bacfgde gdfeb efbadg efgbdc cfged | ecgfa efgbd fedgab dc
-faebgd egdfcb gecadb gdbce ag aeg cgeab gacd faecb debcafg | ag fdeabg decbg ag
-eagc bdegf ga gdaef adg cbgdaf cfaegd dabfce adcegbf dfeac | acedfg agd cega bgefd
-cfgbe ceadf ab cbefag cegfbd bega egdfcab fba gbfacd efcba | ceafbg abf ecfba ba
-fgdce ecgbaf gebd bfg fdbac dfacge fbegdc cgbdf dacbfeg gb | aebcgfd cdabf ebdg egdcaf
-febda edagf egf gdcf bcgafed fg agcbfe caged gcadeb dgaefc | cegbad gcdf gaedc ebdgca
-eabgf fbdcg dfecag acebgf bafdge fbgce ecf eacb begfacd ec | fbgea gcefb fegcad decfag
-fabgcd febdac acgdf beagc dgaec de eafcdg dcfbeag dae fdge | fcdgea ceadg fegd bdcaef
-bcafeg ecbad dcfe ecbgafd gdefab dfabce de cadgb ebd fcaeb | bed bgeafd fgeacb ecdfba
-afcgeb gcebf cdbfag cfea bdgfe cbgaf ec gfabecd bedgca egc | gce dacfbg cebfg afec
-ecdafb cfeag bdgaf febga egabfc eb gbec fcebdga eab dfegac | bea gbafd eab befdac
-fgbacd fbcedga fgc fg gdaf bcdgef cdfba fabced cgbfa gcabe | egcadfb adgf gbcefd gcf
-dfbcga caefbd ac gaedbfc afgc afgdb bdgce dgcab fdgaeb dca | afbgd cagf adc bfedga
-bdgce dbf cgeadfb fd gfbde dfbecg gaebf dacgfb cdebga dcef | fegbd fgbde bgfde fcegbd
-gbda egadbf bfegd acgfde bfgaec gabef cfbde aefdbcg dg ged | abgefd gdefba deg gfeba
-afebcd degcfb dce dcfgb degf bdcgafe bdcge gcabdf de cbaeg | de bagec gcdeb ebagc
-eadcfg dfc gcbfade cd edafb fbcge afdbec agdbef ecfbd bdac | efdab agefdc befad defabc
-agbedf degbfc begcd ceagdf cgabe ecd dcbf ebfdg abecgfd cd | fdebg fdgbe cdgeb bagdef
-aefdb degf fdbaeg egb agebd bcfage gcedfab bdagc ge febacd | bgfdae fdge acfdeb deafb
-cegfba dfcea gbea bac ebafc gdcebf gebcf ba gfdbac gacfdbe | febac aefcb cbfgda cafed
-ec ecfab feadbg ecb dabfc cbfegd dbafgec feabg caefgb ecag | acbefg afbeg fecgabd bcfad
-fd decag fagbe beafdg egdacbf gbfcde dbfa agfbec dgf geadf | bfda befgcda fgd adfb
-af eabfdg edabc dgfabc efga fdbae fcagbed abf gbcdfe efgdb | gefa gfabcd fba gcdfab
-dbfacg degcb gecdaf adcbg bafegcd dfbag gbdefa ac cabf agc | bcadg afbc bdgfa abcdg
-efab cbedg aedfbc bfcad geacfd ea fabdcg adbfgce ade ecabd | fdceba bfceda ecgdfa aefcdg"
-}
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use crate::solution::solve;
+    use super::*;
+
+    const EXAMPLE: &str = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
 
+    #[test]
+    fn example() {
+        let mut input = Day8::parse(EXAMPLE).unwrap();
+        Day8::prepare(&mut input).unwrap();
+        assert_eq!(Day8::part1(&input), 26);
+        assert_eq!(Day8::part2(&input), 61229);
+    }
+
+    #[test]
+    fn reports_repeated_and_missing_segments() {
+        let line = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfcb cdbaf";
+        assert_eq!(Day8::parse(line).err(), Some(ParseError::new(1, 77, "each segment at most once")));
+        assert_eq!(Day8::parse(&line.replace(" ab ", " a ")).err(), Some(ParseError::new(1, 57, "a pattern of at least two segments")));
+    }
+
+    #[test]
+    fn needs_the_ten_digits_wired_the_same_way() {
+        let line = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfbe cdbaf";
+        assert!(solve::<Day8>(line).is_ok());
+        assert_eq!(Day8::parse(&line.replace(" ab ", " ")).err(), Some(ParseError::new(1, 1, "ten signal patterns")));
+        assert_eq!(Day8::parse(&line.replace(" cdbaf", "")).err(), Some(ParseError::new(1, 61, "four output values")));
+        assert!(Day8::parse(&line.replace(" ab ", " ac ")).is_ok());
+        assert_eq!(solve::<Day8>(&line.replace(" ab ", " ac ")).err(),
+                   Some(ParseError::new(1, 1, "signal patterns of the ten digits, wired the same way").on_day(8)));
+    }
+
+    /// Where each of the seven segments is wired to.
+    fn permutation() -> impl Strategy<Value=[u8; 7]> {
+        Just((0..7u8).collect::<Vec<u8>>()).prop_shuffle().prop_map(|wires| wires.try_into().unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn mappings_rewire_without_losing_segments(wires in permutation()) {
+            let mapping = Mapping(wires);
+            let mut seen = [false; 128];
+            for wiring in 0..128u8 {
+                let mapped = Wiring(wiring).map_wire(&mapping);
+                prop_assert_eq!(count_set_bits(mapped.0), count_set_bits(wiring));
+                prop_assert!(!seen[mapped.0 as usize], "{:?} is mapped to twice", mapped);
+                seen[mapped.0 as usize] = true;
+            }
+        }
+
+        #[test]
+        fn candidates_resolve_any_rewiring(wires in permutation(), order in Just((0..10u32).collect::<Vec<u32>>()).prop_shuffle()) {
+            let scrambled: Vec<Wiring> = order.iter().map(|&digit| <Wiring as From<u32>>::from(digit).map_wire(&Mapping(wires))).collect();
+            let mut candidates = MappingCandidates::default();
+            for wiring in &scrambled {
+                candidates.reduce(wiring);
+            }
+            let mapping = candidates.try_resolve().expect("could not resolve candidates");
+
+            for (segment, &wire) in wires.iter().enumerate() {
+                prop_assert_eq!(mapping.get(wire as usize), segment as u8);
+            }
+            for (wiring, &digit) in scrambled.iter().zip(&order) {
+                prop_assert_eq!(u32::from(wiring.map_wire(&mapping)), digit);
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use advent_of_code_2021::reference;
+use crate::gen::{checked_size, clock_seed};
+use crate::{exit_with_usage, take_option, LAST_DAY};
+
+const DEFAULT_RUNS: u64 = 10;
+
+/// Checks fast solvers against their slow references on generated inputs: `diff [day] [--runs n] [--seed n] [--size n]`.
+/// Without a day it checks every day that has a reference; the first divergence is reported with the `gen` command
+/// that makes its input.
+pub(crate) fn run(mut args: Vec<String>) {
+    let number = |name: &str, args: &mut Vec<String>| take_option(args, name)
+        .map(|value| value.parse::<u64>().unwrap_or_else(|_| exit_with_usage(format!("{} should be a number", name))));
+    let runs = number("--runs", &mut args).unwrap_or(DEFAULT_RUNS);
+    if runs == 0 {
+        exit_with_usage("--runs should be a positive number");
+    }
+    let seed = number("--seed", &mut args);
+    let size = number("--size", &mut args);
+    let day = match args.first().map(|day| day.parse()) {
+        None => None,
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => Some(day),
+        Some(_) => exit_with_usage(format!("diff takes a day between 1 and {}", LAST_DAY)),
+    };
+    let size = match (day, size) {
+        (Some(day), Some(size)) => Some(checked_size(day, size)),
+        (None, Some(_)) => exit_with_usage("--size needs a day"),
+        (_, None) => None,
+    };
+    let seed = seed.unwrap_or_else(clock_seed);
+
+    let chosen = day.is_some();
+    let days: Vec<usize> = day.map_or_else(|| (1..=LAST_DAY).collect(), |day| vec![day]);
+    for day in days {
+        match reference::check_day(day, seed, runs, size) {
+            Some(Ok(parts)) => println!("day {:>2}: {} parts agree on the inputs of seeds {} to {}", day, parts, seed, seed.saturating_add(runs - 1)),
+            Some(Err(divergence)) => {
+                println!("{}", divergence);
+                let size = divergence.size.map_or_else(String::new, |size| format!(" --size {}", size));
+                println!("the input comes from: gen {} --seed {}{}", day, divergence.seed, size);
+                std::process::exit(1);
+            }
+            None if chosen => exit_with_usage(format!("day {} has no reference solver", day)),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A puzzle input that could not be parsed. `line` and `column` are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at the start of `token`, which must be a slice of `text`.
+    pub fn at(text: &str, token: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position_of(text, token);
+        Self::new(line, column, expected)
+    }
+
+    /// An error just past the end of `text`, for when it ends before an expected token.
+    pub fn after(text: &str, expected: impl Into<String>) -> Self {
+        Self::at(text, &text[text.len()..], expected)
+    }
+
+    /// Rebases an error found while parsing `part` onto `text`, which `part` is a slice of.
+    pub fn relative_to(mut self, text: &str, part: &str) -> Self {
+        let (line, column) = position_of(text, part);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+
+    pub fn on_day(mut self, day: usize) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(f, "line {}, column {}: expected {}", self.line, self.column, self.expected)
+    }
+}
+
+impl Error for ParseError {}
+
+// `token` is expected to point into `text`; anything else is reported at the start
+fn position_of(text: &str, token: &str) -> (usize, usize) {
+    let offset = (token.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+    if offset > text.len() {
+        return (1, 1);
+    }
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Parses `token`, a slice of `text`, reporting its position on failure.
+pub fn parse_token<T: FromStr>(text: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::at(text, token, expected))
+}
+
+/// Takes the next token from `tokens`, which are slices of `text`.
+pub fn next_token<'a>(text: &str, tokens: &mut impl Iterator<Item=&'a str>, expected: &str) -> Result<&'a str, ParseError> {
+    tokens.next().ok_or_else(|| ParseError::after(text, expected))
+}
+
+/// Takes and parses the next token from `tokens`, which are slices of `text`.
+pub fn parse_next<'a, T: FromStr>(text: &str, tokens: &mut impl Iterator<Item=&'a str>, expected: &str) -> Result<T, ParseError> {
+    parse_token(text, next_token(text, tokens, expected)?, expected)
+}
+
+/// Splits `input` at its first blank line, for inputs made of a header followed by a body.
+pub fn split_at_blank_line<'a>(input: &'a str, expected: &str) -> Result<(&'a str, &'a str), ParseError> {
+    input.split_once("\n\n")
+        .ok_or_else(|| ParseError::after(input.split('\n').next().unwrap_or(input), expected))
+}
+
+/// Parses every line of `input`, tagging errors with the line they were found on.
+pub fn parse_lines<'a, T>(input: &'a str, mut parse: impl FnMut(&'a str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    input.split('\n')
+        .map(|line| parse(line).map_err(|e| e.relative_to(input, line)))
+        .collect()
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::io::{self, Write};
+use advent_of_code_2021::info;
+use advent_of_code_2021::picture;
+use crate::input::InputSource;
+use crate::{exit_with_usage, take_option, LAST_DAY};
+
+/// Writes a picture of a day's state: `export day [input] [--out file] [--scale n]`.
+/// Without `--out` it goes to `day<N>.<pbm|ppm|svg>`; `--out -` writes it to stdout.
+pub(crate) fn run(mut args: Vec<String>) {
+    let out = take_option(&mut args, "--out");
+    let scale = take_option(&mut args, "--scale")
+        .map(|scale| match scale.parse() {
+            Ok(scale) if scale > 0 => scale,
+            _ => exit_with_usage("--scale should be a positive number"),
+        })
+        .unwrap_or(1);
+    let mut args = args.drain(..);
+    let day = match args.next().map(|day| day.parse()) {
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+        _ => exit_with_usage(format!("export needs a day between 1 and {}", LAST_DAY)),
+    };
+
+    let input = match InputSource::from_arg(args.next(), day).read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not read input for day {}: {}", day, e);
+            std::process::exit(1);
+        }
+    };
+    let picture = match picture::for_day(day, &input) {
+        Some(Ok(picture)) => picture,
+        Some(Err(e)) => {
+            eprintln!("could not parse input: {}", e);
+            std::process::exit(1);
+        }
+        None => exit_with_usage(format!("day {} has nothing to export", day)),
+    };
+
+    let bytes = picture.encode(scale);
+    let out = out.unwrap_or_else(|| format!("day{}.{}", day, picture.extension()));
+    let written = if out == "-" {
+        io::stdout().write_all(&bytes)
+    } else {
+        fs::write(&out, &bytes)
+    };
+    if let Err(e) = written {
+        eprintln!("could not write {}: {}", out, e);
+        std::process::exit(1);
+    }
+    if out != "-" {
+        info!("wrote {}", out);
+    }
+}
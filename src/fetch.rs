@@ -0,0 +1,29 @@
+use advent_of_code_2021::client::{Client, BASE_URL, SESSION_VAR};
+use crate::{exit_with_usage, input, take_flag, LAST_DAY};
+
+/// Downloads puzzle inputs into `inputs/`: `fetch [day] [--force]`.
+/// Without a day it gets every day's; inputs already there are kept unless `--force` is given.
+/// The site ties inputs to a login, so the session cookie of a logged-in browser has to be in `AOC_SESSION`.
+pub(crate) fn run(mut args: Vec<String>) {
+    let force = take_flag(&mut args, "--force");
+    let days: Vec<usize> = match args.first().map(|day| day.parse()) {
+        None => (1..=LAST_DAY).collect(),
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => vec![day],
+        _ => exit_with_usage(format!("fetch takes a day between 1 and {}", LAST_DAY)),
+    };
+    let mut client = Client::new(BASE_URL, session("fetch"));
+    for day in days {
+        if let Err(e) = client.input(day, &input::default_path(day), force) {
+            eprintln!("could not fetch day {}: {}", day, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The session cookie from `AOC_SESSION`; exits if there is none, as `command` can't do without.
+pub(crate) fn session(command: &str) -> String {
+    match std::env::var(SESSION_VAR) {
+        Ok(session) if !session.trim().is_empty() => session.trim().to_string(),
+        _ => exit_with_usage(format!("{} needs the session cookie of a logged-in browser in {}", command, SESSION_VAR)),
+    }
+}
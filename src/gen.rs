@@ -0,0 +1,64 @@
+use std::fs;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+use advent_of_code_2021::generate::{self, Rng};
+use advent_of_code_2021::info;
+use crate::{exit_with_usage, take_option, LAST_DAY};
+
+/// Makes up an input for a day: `gen day [--size n] [--seed n] [--out file]`.
+/// It goes to stdout unless `--out` is given; without `--seed` the seed is taken from the clock and logged,
+/// so that an input worth keeping can be made again.
+pub(crate) fn run(mut args: Vec<String>) {
+    let number = |name: &str, args: &mut Vec<String>| take_option(args, name)
+        .map(|value| value.parse::<u64>().unwrap_or_else(|_| exit_with_usage(format!("{} should be a number", name))));
+    let size = number("--size", &mut args);
+    let seed = number("--seed", &mut args);
+    let out = take_option(&mut args, "--out");
+    let day = match args.first().map(|day| day.parse()) {
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+        _ => exit_with_usage(format!("gen needs a day between 1 and {}", LAST_DAY)),
+    };
+
+    let generator = generate::for_day(day);
+    let size = match size {
+        Some(size) => checked_size(day, size),
+        None => generator.size.map_or(0, |size| size.default),
+    };
+    let seed = seed.unwrap_or_else(clock_seed);
+
+    let mut input = (generator.generate)(&mut Rng::new(seed), size);
+    input.push('\n');
+    let written = match &out {
+        Some(out) => fs::write(out, &input),
+        None => io::stdout().write_all(input.as_bytes()),
+    };
+    if let Err(e) = written {
+        eprintln!("could not write {}: {}", out.as_deref().unwrap_or("the input"), e);
+        std::process::exit(1);
+    }
+    if let Some(out) = out {
+        info!("wrote {}", out);
+    }
+}
+
+/// `size` as the day's generator takes it; exits if the day takes no size or not this one.
+pub(crate) fn checked_size(day: usize, size: u64) -> usize {
+    match generate::for_day(day).size {
+        None => exit_with_usage(format!("day {} inputs all have the same shape, so they take no --size", day)),
+        Some(limits) if size < limits.min as u64 || size > limits.max as u64 => {
+            let bounds = match limits.max {
+                usize::MAX => format!("at least {}", limits.min),
+                max => format!("between {} and {}", limits.min, max),
+            };
+            exit_with_usage(format!("--size counts {} for day {}, and should be {}", limits.counts, day, bounds))
+        }
+        Some(_) => size as usize,
+    }
+}
+
+/// A seed taken from the clock, and logged so that its inputs can be made again.
+pub(crate) fn clock_seed() -> u64 {
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_nanos() as u64);
+    info!("seed {}", seed);
+    seed
+}
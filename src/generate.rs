@@ -0,0 +1,193 @@
+use std::ops::RangeInclusive;
+use crate::day1_sonar_sweep::Day1;
+use crate::day2_dive::Day2;
+use crate::day3_binary_diagnostic::Day3;
+use crate::day4_bingo::Day4;
+use crate::day5_hydrothermal_lines::Day5;
+use crate::day6_lanternfish::Day6;
+use crate::day7_crabs::Day7;
+use crate::day8_seven_segment_search::Day8;
+use crate::day9_low_points::Day9;
+use crate::day10_syntax_scoring::Day10;
+use crate::day11_dumbo_octopus::Day11;
+use crate::day12_passage_pathing::Day12;
+use crate::day13_transparent_origami::Day13;
+use crate::day14_polymerization::Day14;
+use crate::day15_chiton::Day15;
+use crate::day16_packet_decoder::Day16;
+use crate::day17_trick_shot::Day17;
+use crate::day18_snailfish::Day18;
+use crate::day19_beacon_scanner::Day19;
+use crate::day20_trench_map::Day20;
+use crate::day21_dirac_dice::Day21;
+use crate::day22_reactor_reboot::Day22;
+use crate::day23_amphipod::Day23;
+use crate::day24_arithmetic_logic_unit::Day24;
+use crate::day25_sea_cucumber::Day25;
+use crate::solution::Solution;
+
+/// A small seeded pseudo-random generator (SplitMix64), so that a seed always gives the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing to choose from");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        start + (self.next_u64() % ((end - start) as u64 + 1)) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// What the size of a generated input counts, e.g. lines or scanners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub counts: &'static str,
+    /// The size of the real puzzle input.
+    pub default: usize,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Size {
+    pub const fn new(counts: &'static str, default: usize) -> Self {
+        Self { counts, default, min: 1, max: usize::MAX }
+    }
+
+    pub const fn at_least(self, min: usize) -> Self {
+        Self { min, ..self }
+    }
+
+    pub const fn up_to(self, max: usize) -> Self {
+        Self { max, ..self }
+    }
+}
+
+/// A [`Solution`] that can make up valid inputs of its own, for testing at other sizes than the real input's.
+pub trait Generate: Solution {
+    /// What `size` counts, or `None` if every input has the same shape.
+    const SIZE: Option<Size>;
+
+    /// An input that holds to everything the puzzle promises; `size` is within the bounds of
+    /// [`Generate::SIZE`], and ignored without one.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// A day's [`Generate`] implementation, looked up by number.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub size: Option<Size>,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+pub fn for_day(day: usize) -> Generator {
+    fn generator<S: Generate>() -> Generator {
+        Generator { size: S::SIZE, generate: S::generate }
+    }
+
+    match day {
+        1 => generator::<Day1>(),
+        2 => generator::<Day2>(),
+        3 => generator::<Day3>(),
+        4 => generator::<Day4>(),
+        5 => generator::<Day5>(),
+        6 => generator::<Day6>(),
+        7 => generator::<Day7>(),
+        8 => generator::<Day8>(),
+        9 => generator::<Day9>(),
+        10 => generator::<Day10>(),
+        11 => generator::<Day11>(),
+        12 => generator::<Day12>(),
+        13 => generator::<Day13>(),
+        14 => generator::<Day14>(),
+        15 => generator::<Day15>(),
+        16 => generator::<Day16>(),
+        17 => generator::<Day17>(),
+        18 => generator::<Day18>(),
+        19 => generator::<Day19>(),
+        20 => generator::<Day20>(),
+        21 => generator::<Day21>(),
+        22 => generator::<Day22>(),
+        23 => generator::<Day23>(),
+        24 => generator::<Day24>(),
+        25 => generator::<Day25>(),
+        _ => panic!("there is no day {}", day),
+    }
+}
+
+/// Lays cells out one row per line, as the grid-based days read them.
+pub fn grid_lines(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    (0..height)
+        .map(|y| (0..width).map(|x| cell(x, y)).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ParseError;
+    use crate::solution::{self, SolutionVisitor};
+    use super::*;
+
+    #[test]
+    fn seeds_repeat() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!((0..10).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert!((0..1000).map(|_| a.range(-2..=2)).all(|n| (-2..=2).contains(&n)));
+    }
+
+    #[test]
+    fn every_day_generates_input_it_can_parse() {
+        struct Parse<'a>(&'a str);
+
+        impl SolutionVisitor for Parse<'_> {
+            type Output = Result<(), ParseError>;
+
+            fn visit<S: Solution>(self) -> Self::Output {
+                S::parse(self.0).map(|_| ())
+            }
+        }
+
+        for day in 1..=25 {
+            let generator = for_day(day);
+            let size = generator.size.map_or(0, |size| size.default.min(5).max(size.min));
+            let input = (generator.generate)(&mut Rng::new(day as u64), size);
+            if let Err(e) = solution::for_day(day, Parse(&input)) {
+                panic!("day {}: {}\n{}", day, e, input);
+            }
+        }
+    }
+}
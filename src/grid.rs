@@ -0,0 +1,229 @@
+use std::ops::{Index, IndexMut};
+use crate::error::ParseError;
+
+/// A cell of a [`Grid`]; `x` grows to the right and `y` downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBORS_8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// A rectangular grid stored row by row. Moving off an edge either leaves the grid or,
+/// once [`Grid::wrapping`] is set, comes back in on the opposite edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    wraps: bool,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+        where T: Clone {
+        Self::from_fn(width, height, |_| value.clone())
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Pos { x, y }))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            wraps: false,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character, one row per line. Every row must be as long as the first.
+    pub fn parse(s: &str, expected: &str, mut parse_cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        for (y, line) in s.split('\n').enumerate() {
+            let mut x = 0;
+            for c in line.chars() {
+                if y > 0 && x == width {
+                    break;
+                }
+                cells.push(parse_cell(c).ok_or_else(|| ParseError::new(y + 1, x + 1, expected))?);
+                x += 1;
+            }
+            if y == 0 {
+                width = x;
+            } else if x != width || line.chars().count() != width {
+                return Err(ParseError::new(y + 1, x + 1, format!("a row of {} characters", width)));
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width,
+            height,
+            wraps: false,
+            cells,
+        })
+    }
+
+    /// Makes neighbours and offsets wrap around the edges, as on a torus.
+    pub fn wrapping(mut self) -> Self {
+        self.wraps = true;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    /// All positions, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item=Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos { x, y }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item=&T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item=&mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The position `dx` and `dy` away from `pos`, if it is on the grid.
+    pub fn offset(&self, pos: Pos, dx: isize, dy: isize) -> Option<Pos> {
+        if self.wraps {
+            return Some(Pos {
+                x: (pos.x as isize + dx).rem_euclid(self.width as isize) as usize,
+                y: (pos.y as isize + dy).rem_euclid(self.height as isize) as usize,
+            });
+        }
+        let x = pos.x.checked_add_signed(dx)?;
+        let y = pos.y.checked_add_signed(dy)?;
+        Some(Pos { x, y }).filter(|&pos| self.contains(pos))
+    }
+
+    /// The orthogonal neighbours of `pos`, in reading order.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item=Pos> + '_ {
+        NEIGHBORS_4.iter().filter_map(move |&(dx, dy)| self.offset(pos, dx, dy))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos`, in reading order.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item=Pos> + '_ {
+        NEIGHBORS_8.iter().filter_map(move |&(dx, dy)| self.offset(pos, dx, dy))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            wraps: self.wraps,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws one character per cell, one line per row.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is outside a {}x{} grid", pos, self.width, self.height);
+        &self.cells[pos.y * self.width + pos.x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is outside a {}x{} grid", pos, self.width, self.height);
+        &mut self.cells[pos.y * self.width + pos.x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(s, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(2, 1)], 6);
+        assert_eq!(grid.render(|&d| std::char::from_digit(d, 10).unwrap()), "123\n456");
+    }
+
+    #[test]
+    fn reports_bad_cells_and_ragged_rows() {
+        assert_eq!(digits("123\n4x6").unwrap_err(), ParseError::new(2, 2, "a digit"));
+        assert_eq!(digits("123\n45").unwrap_err(), ParseError::new(2, 3, "a row of 3 characters"));
+        assert_eq!(digits("123\n4567").unwrap_err(), ParseError::new(2, 4, "a row of 3 characters"));
+    }
+
+    #[test]
+    fn neighbors_stop_at_the_edges() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>(), vec![Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Pos::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn wrapping_neighbors_come_back_on_the_other_side() {
+        let grid = Grid::new(3, 2, 0).wrapping();
+        assert_eq!(grid.offset(Pos::new(2, 1), 1, 1), Some(Pos::new(0, 0)));
+        assert_eq!(grid.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>(),
+                   vec![Pos::new(0, 1), Pos::new(2, 0), Pos::new(1, 0), Pos::new(0, 1)]);
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+const INPUTS_DIR: &str = "inputs";
+
+pub(crate) enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    pub fn from_arg(arg: Option<String>, day: usize) -> Self {
+        match arg.as_deref() {
+            Some("-") => Self::Stdin,
+            Some(path) => Self::File(path.into()),
+            None => Self::File(default_path(day)),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        let mut text = String::new();
+        match self {
+            Self::Stdin => {
+                io::stdin().read_to_string(&mut text)?;
+            }
+            Self::File(path) => {
+                text = fs::read_to_string(path)
+                    .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            }
+        }
+
+        Ok(normalize(&text))
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "-"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub(crate) fn default_path(day: usize) -> PathBuf {
+    [INPUTS_DIR, &format!("day{}.txt", day)].iter().collect()
+}
+
+// the solvers split on '\n' and expect no trailing empty line
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n").trim_end().to_string()
+}
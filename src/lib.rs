@@ -0,0 +1,41 @@
+//! Solutions to [Advent of Code 2021](https://adventofcode.com/2021), one module per day.
+//!
+//! Every day exposes a `DayN` type implementing [`solution::Solution`], along with the types it parses its input into.
+
+#[macro_use]
+pub mod log;
+
+pub mod animation;
+pub mod client;
+pub mod day1_sonar_sweep;
+pub mod day2_dive;
+pub mod day3_binary_diagnostic;
+pub mod day4_bingo;
+pub mod day5_hydrothermal_lines;
+pub mod day6_lanternfish;
+pub mod day7_crabs;
+pub mod day8_seven_segment_search;
+pub mod day9_low_points;
+pub mod day10_syntax_scoring;
+pub mod day11_dumbo_octopus;
+pub mod day12_passage_pathing;
+pub mod day13_transparent_origami;
+pub mod day14_polymerization;
+pub mod day15_chiton;
+pub mod day16_packet_decoder;
+pub mod day17_trick_shot;
+pub mod day18_snailfish;
+pub mod day19_beacon_scanner;
+pub mod day20_trench_map;
+pub mod day21_dirac_dice;
+pub mod day22_reactor_reboot;
+pub mod day23_amphipod;
+pub mod day24_arithmetic_logic_unit;
+pub mod day25_sea_cucumber;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod picture;
+pub mod reference;
+pub mod solution;
+pub mod vector;
@@ -1,65 +1,166 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use serde_json::json;
 use utils::timer::Timer;
+use crate::alloc::CountingAllocator;
+use crate::input::InputSource;
+use crate::output::Format;
+use advent_of_code_2021::error::ParseError;
+use advent_of_code_2021::log::{self, Level};
+use advent_of_code_2021::info;
+use advent_of_code_2021::solution::{for_day, solve, Answers, Solution, SolutionVisitor};
 
-mod day1_sonar_sweep;
-mod day2_dive;
-mod day3_binary_diagnostic;
-mod day4_bingo;
-mod day5_hydrothermal_lines;
-mod day6_lanternfish;
-mod day7_crabs;
-mod day8_seven_segment_search;
-mod day9_low_points;
-mod day10_syntax_scoring;
-mod day11_dumbo_octopus;
-mod day12_passage_pathing;
-mod day13_transparent_origami;
-mod day14_polymerization;
-mod day15_chiton;
-mod day16_packet_decoder;
-mod day17_trick_shot;
-mod day18_snailfish;
-mod day19_beacon_scanner;
-mod day20_trench_map;
-mod day21_dirac_dice;
-mod day22_reactor_reboot;
-mod day23_amphipod;
-mod day24_arithmetic_logic_unit;
-mod day25_sea_cucumber;
+mod all;
+mod alloc;
+mod animate;
+mod answers;
+mod bench;
+mod diff;
+mod export;
+mod fetch;
+mod gen;
+mod input;
+mod output;
+mod submit;
+mod verify;
+
+const LAST_DAY: usize = 25;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() {
-    let _timer = Timer::start(|elapsed| println!("main took {} ms.", elapsed.as_millis()));
-    let day: usize = if let Some(arg1) = std::env::args().nth(1) {
-        arg1.parse().expect("argument should be an integer")
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_option(&mut args, "--format")
+        .map(|format| format.parse().unwrap_or_else(|e| exit_with_usage(e)))
+        .unwrap_or(Format::Text);
+    let parallel = take_flag(&mut args, "--parallel");
+    let level = take_log_level(&mut args);
+
+    // solver output would only skew the measurements, or drown the comparisons, so these are quiet unless asked otherwise
+    let is_quiet = matches!(args.first().map(String::as_str), Some("bench") | Some("diff"));
+    log::set_level(level.unwrap_or(if is_quiet { Level::Quiet } else { Level::Info }));
+
+    let mut args = args.into_iter();
+    match args.next() {
+        Some(arg) if arg == "all" => all::run(parallel, format),
+        Some(arg) if arg == "verify" => verify::run(parallel),
+        Some(arg) if arg == "bench" => bench::run(args.collect()),
+        Some(arg) if arg == "export" => export::run(args.collect()),
+        Some(arg) if arg == "animate" => animate::run(args.collect()),
+        Some(arg) if arg == "gen" => gen::run(args.collect()),
+        Some(arg) if arg == "diff" => diff::run(args.collect()),
+        Some(arg) if arg == "fetch" => fetch::run(args.collect()),
+        Some(arg) if arg == "submit" => submit::run(args.collect()),
+        arg => {
+            let day = match arg.map(|arg| arg.parse()) {
+                None => LAST_DAY,
+                Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+                Some(Ok(_)) => exit_with_usage(format!("day should be between 1 and {}", LAST_DAY)),
+                Some(Err(_)) => exit_with_usage("argument should be an integer, 'all', 'verify', 'bench', 'export', 'animate', 'gen', 'diff', 'fetch' or 'submit'"),
+            };
+            run_day(day, args.next(), format);
+        }
+    }
+}
+
+pub(crate) fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+fn take_log_level(args: &mut Vec<String>) -> Option<Level> {
+    let quiet = take_flag(args, "-q");
+    let trace = take_flag(args, "-vv");
+    let debug = take_flag(args, "-v");
+    match (quiet, debug, trace) {
+        (true, _, _) => Some(Level::Quiet),
+        (_, _, true) => Some(Level::Trace),
+        (_, true, _) => Some(Level::Debug),
+        _ => None,
+    }
+}
+
+pub(crate) fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let i = args.iter().position(|arg| arg == name)?;
+    if i + 1 >= args.len() {
+        exit_with_usage(format!("{} needs a value", name));
+    }
+    args.remove(i);
+    Some(args.remove(i))
+}
+
+pub(crate) fn exit_with_usage(message: impl Display) -> ! {
+    eprintln!("{}", message);
+    eprintln!("usage: advent-of-code-2021 [day [input] | all [--parallel] | verify [--parallel] | bench day [input] [--part 1|2] [--runs n] [--warmup n] [--save file] [--compare file] | export day [input] [--out file] [--scale n] | animate day [input] [--out file | --frames dir] [--every n] [--scale n] [--delay ms] | gen day [--size n] [--seed n] [--out file] | diff [day] [--runs n] [--seed n] [--size n] | fetch [day] [--force] | submit day 1|2 [input] [--answer value]] [--format text|json] [-q | -v | -vv]");
+    std::process::exit(2);
+}
+
+fn run_day(day: usize, input: Option<String>, format: Format) {
+    let _timer = Timer::start(|elapsed| info!("main took {} ms.", elapsed.as_millis()));
+    let source = InputSource::from_arg(input, day);
+    if format == Format::Text {
+        println!("running day {}\n", day);
+    }
+    let outcome = read_and_solve(day, &source);
+    match format {
+        Format::Text => match &outcome {
+            Ok(answers) => {
+                println!();
+                print_answer(1, &answers.part1);
+                print_answer(2, &answers.part2);
+            }
+            Err(e) => eprintln!("{}", e),
+        },
+        Format::Json => {
+            let metadata = json!({ "input": source.to_string() });
+            println!("{}", output::day_json(day, &outcome, metadata));
+        }
+    }
+    if outcome.is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// Why a day produced no answers.
+pub(crate) enum Failure {
+    Read(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Read(e) => write!(f, "could not read input: {}", e),
+            Failure::Parse(e) => write!(f, "could not parse input: {}", e),
+        }
+    }
+}
+
+pub(crate) fn read_and_solve(day: usize, source: &InputSource) -> Result<Answers, Failure> {
+    let input = source.read().map_err(Failure::Read)?;
+    solve_day(day, &input).map_err(Failure::Parse)
+}
+
+fn solve_day(day: usize, input: &str) -> Result<Answers, ParseError> {
+    struct Solve<'a>(&'a str);
+
+    impl SolutionVisitor for Solve<'_> {
+        type Output = Result<Answers, ParseError>;
+
+        fn visit<S: Solution>(self) -> Self::Output {
+            solve::<S>(self.0)
+        }
+    }
+
+    for_day(day, Solve(input))
+}
+
+fn print_answer(part: u8, answer: &str) {
+    if answer.contains('\n') {
+        println!("part {}:\n{}", part, answer);
     } else {
-        25
-    };
-    println!("running day {}\n", day);
-    match day {
-        1 => day1_sonar_sweep::run(),
-        2 => day2_dive::run(),
-        3 => day3_binary_diagnostic::run(),
-        4 => day4_bingo::run(),
-        5 => day5_hydrothermal_lines::run(),
-        6 => day6_lanternfish::run(),
-        7 => day7_crabs::run(),
-        8 => day8_seven_segment_search::run(),
-        9 => day9_low_points::run(),
-        10 => day10_syntax_scoring::run(),
-        11 => day11_dumbo_octopus::run(),
-        12 => day12_passage_pathing::run(),
-        13 => day13_transparent_origami::run(),
-        14 => day14_polymerization::run(),
-        15 => day15_chiton::run(),
-        16 => day16_packet_decoder::run(),
-        17 => day17_trick_shot::run(),
-        18 => day18_snailfish::run(),
-        19 => day19_beacon_scanner::run(),
-        20 => day20_trench_map::run(),
-        21 => day21_dirac_dice::run(),
-        22 => day22_reactor_reboot::run(),
-        23 => day23_amphipod::run(),
-        24 => day24_arithmetic_logic_unit::run(),
-        25 => day25_sea_cucumber::run(),
-        _ => panic!("day {} not found", day)
+        println!("part {}: {}", part, answer);
     }
 }
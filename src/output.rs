@@ -0,0 +1,77 @@
+use std::str::FromStr;
+use std::time::Duration;
+use serde_json::{json, Value};
+use advent_of_code_2021::solution::Answers;
+use crate::Failure;
+
+/// How answers are written to stdout. Progress output always goes to stderr.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format '{}', expected 'text' or 'json'", s)),
+        }
+    }
+}
+
+/// One day's answers, or why there are none, e.g.
+/// `{"day":1,"parts":[{"part":1,"answer":"7","duration_ms":0.1},...],"duration_ms":0.3,"metadata":{...}}`.
+pub(crate) fn day_json(day: usize, outcome: &Result<Answers, Failure>, metadata: Value) -> Value {
+    match outcome {
+        Ok(answers) => json!({
+            "day": day,
+            "parts": [
+                part_json(1, &answers.part1, answers.timings.part1),
+                part_json(2, &answers.part2, answers.timings.part2),
+            ],
+            "duration_ms": millis(answers.timings.total()),
+            "metadata": with_entry(with_entry(metadata, "parse_duration_ms", millis(answers.timings.parse).into()),
+                                   "prepare_duration_ms", millis(answers.timings.prepare).into()),
+        }),
+        Err(failure) => json!({
+            "day": day,
+            "error": failure_json(failure),
+            "metadata": metadata,
+        }),
+    }
+}
+
+fn part_json(part: u8, answer: &str, duration: Duration) -> Value {
+    json!({
+        "part": part,
+        "answer": answer,
+        "duration_ms": millis(duration),
+    })
+}
+
+fn failure_json(failure: &Failure) -> Value {
+    match failure {
+        Failure::Read(_) => json!({ "message": failure.to_string() }),
+        Failure::Parse(e) => json!({
+            "message": failure.to_string(),
+            "line": e.line,
+            "column": e.column,
+            "expected": e.expected,
+        }),
+    }
+}
+
+fn with_entry(mut metadata: Value, key: &str, value: Value) -> Value {
+    if let Value::Object(map) = &mut metadata {
+        map.insert(key.to_string(), value);
+    }
+    metadata
+}
+
+pub(crate) fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
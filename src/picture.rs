@@ -0,0 +1,152 @@
+use std::fmt::Write;
+use crate::day5_hydrothermal_lines::Day5;
+use crate::day9_low_points::Day9;
+use crate::day13_transparent_origami::Day13;
+use crate::day15_chiton::Day15;
+use crate::day20_trench_map::Day20;
+use crate::day25_sea_cucumber::Day25;
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::solution::Solution;
+use crate::vector::{Bounds, Vec2};
+
+/// A colour as stored in a PPM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+
+    /// This colour darkened or lightened by `factor`, which is at most 1 to stay in range.
+    pub fn scaled(self, factor: f64) -> Self {
+        let channel = |c: u8| (c as f64 * factor).round().clamp(0.0, 255.0) as u8;
+        Self(channel(self.0), channel(self.1), channel(self.2))
+    }
+}
+
+/// A picture of a day's state, in the form that suits it best.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Picture {
+    /// Black and white pixels, written as PBM; `true` is black.
+    Bitmap(Grid<bool>),
+    /// Coloured pixels, written as PPM.
+    Pixmap(Grid<Rgb>),
+    /// Line segments between cell centres, written as SVG.
+    Lines(Vec<(Vec2, Vec2)>),
+}
+
+impl Picture {
+    /// The file extension matching [`Picture::encode`].
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Picture::Bitmap(_) => "pbm",
+            Picture::Pixmap(_) => "ppm",
+            Picture::Lines(_) => "svg",
+        }
+    }
+
+    /// The picture as a binary PBM or PPM, or as SVG, with every cell drawn `scale` pixels wide.
+    pub fn encode(&self, scale: usize) -> Vec<u8> {
+        match self {
+            Picture::Bitmap(grid) => pbm(&upscale(grid, scale)),
+            Picture::Pixmap(grid) => ppm(&upscale(grid, scale)),
+            Picture::Lines(lines) => svg(lines, scale).into_bytes(),
+        }
+    }
+}
+
+/// A [`Solution`] whose state can be drawn, for viewing inputs too large for the terminal.
+pub trait Export: Solution {
+    /// A picture of the day's most telling state, usually the one its answer is read from.
+    fn picture(input: &Self::Input<'_>) -> Picture;
+}
+
+/// Parses and prepares `input` as the given day's and draws it, or `None` if the day has nothing to draw.
+pub fn for_day(day: usize, input: &str) -> Option<Result<Picture, ParseError>> {
+    fn picture<S: Export>(input: &str) -> Result<Picture, ParseError> {
+        let mut input = S::parse(input)?;
+        S::prepare(&mut input)?;
+        Ok(S::picture(&input))
+    }
+
+    Some(match day {
+        5 => picture::<Day5>(input),
+        9 => picture::<Day9>(input),
+        13 => picture::<Day13>(input),
+        15 => picture::<Day15>(input),
+        20 => picture::<Day20>(input),
+        25 => picture::<Day25>(input),
+        _ => return None,
+    })
+}
+
+pub(crate) fn upscale<T: Clone>(grid: &Grid<T>, scale: usize) -> Grid<T> {
+    Grid::from_fn(grid.width() * scale, grid.height() * scale, |pos| {
+        grid[Pos::new(pos.x / scale, pos.y / scale)].clone()
+    })
+}
+
+fn pbm(grid: &Grid<bool>) -> Vec<u8> {
+    let mut bytes = format!("P4\n{} {}\n", grid.width(), grid.height()).into_bytes();
+    for row in grid.rows() {
+        // each row starts on a fresh byte, most significant bit first
+        for chunk in row.chunks(8) {
+            bytes.push(chunk.iter().enumerate().fold(0, |byte, (i, &black)| byte | (black as u8) << (7 - i)));
+        }
+    }
+    bytes
+}
+
+fn ppm(grid: &Grid<Rgb>) -> Vec<u8> {
+    let mut bytes = format!("P6\n{} {}\n255\n", grid.width(), grid.height()).into_bytes();
+    for &Rgb(r, g, b) in grid.values() {
+        bytes.extend([r, g, b]);
+    }
+    bytes
+}
+
+fn svg(lines: &[(Vec2, Vec2)], scale: usize) -> String {
+    let bounds = Bounds::from_points(lines.iter().flat_map(|&(from, to)| [from, to]))
+        .unwrap_or_else(|| Bounds::new(Vec2::default(), Vec2::default()));
+    let width = bounds.max.x - bounds.min.x + 1;
+    let height = bounds.max.y - bounds.min.y + 1;
+    let mut svg = String::new();
+    writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+             bounds.min.x, bounds.min.y, width, height, width as usize * scale, height as usize * scale).unwrap();
+    writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white"/>"#,
+             bounds.min.x, bounds.min.y, width, height).unwrap();
+    // translucent strokes, so that overlapping lines show up darker
+    writeln!(svg, r#"<g stroke="black" stroke-opacity="0.5" stroke-linecap="square" transform="translate(0.5 0.5)">"#).unwrap();
+    for (from, to) in lines {
+        writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, from.x, from.y, to.x, to.y).unwrap();
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitmaps_pack_each_row_into_whole_bytes() {
+        let grid = Grid::from_fn(9, 2, |pos| pos.x == pos.y || pos.x == 8);
+        let picture = Picture::Bitmap(grid);
+        assert_eq!(picture.encode(1), b"P4\n9 2\n\x80\x80\x40\x80".to_vec());
+        assert_eq!(&picture.encode(2)[..10], b"P4\n18 4\n\xc0\x00");
+    }
+
+    #[test]
+    fn pixmaps_hold_three_bytes_per_pixel() {
+        let grid = Grid::from_fn(2, 1, |pos| if pos.x == 0 { Rgb::WHITE } else { Rgb(1, 2, 3) });
+        assert_eq!(Picture::Pixmap(grid).encode(1), b"P6\n2 1\n255\n\xff\xff\xff\x01\x02\x03".to_vec());
+    }
+
+    #[test]
+    fn lines_fit_the_view_box() {
+        let svg = String::from_utf8(Picture::Lines(vec![(Vec2::new(1, 2), Vec2::new(4, 2))]).encode(10)).unwrap();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="1 2 4 1" width="40" height="10">"#));
+        assert!(svg.contains(r#"<line x1="1" y1="2" x2="4" y2="2"/>"#));
+    }
+}
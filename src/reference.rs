@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter};
+use crate::day6_lanternfish::Day6;
+use crate::day14_polymerization::Day14;
+use crate::day22_reactor_reboot::Day22;
+use crate::day23_amphipod::Day23;
+use crate::generate::{Generate, Rng, Size};
+use crate::solution::Solution;
+
+/// A [`Solution`] that keeps a slow but plain way to its answers, to check the fast one against.
+pub trait Reference: Solution {
+    /// Part 1 the slow way, or `None` if that is out of reach for this input.
+    fn reference_part1(input: &Self::Input<'_>) -> Option<Self::Part1>;
+    /// Part 2 the slow way, or `None` if that is out of reach for this input.
+    fn reference_part2(input: &Self::Input<'_>) -> Option<Self::Part2>;
+}
+
+/// A generated input on which a fast solver and its reference disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub day: usize,
+    pub part: u8,
+    /// The seed and size that generate the input again; days whose inputs all have the same shape have no size.
+    pub seed: u64,
+    pub size: Option<usize>,
+    pub fast: String,
+    pub reference: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} part {}: the solver says {} but the reference {}, on the input of seed {}",
+               self.day, self.part, self.fast, self.reference, self.seed)?;
+        match self.size {
+            Some(size) => write!(f, " and size {}", size),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Checks both parts of the input generated from `seed` against the reference, returning how many parts it could check.
+pub fn check<S: Reference + Generate>(seed: u64, size: usize) -> Result<usize, Divergence> {
+    let input = S::generate(&mut Rng::new(seed), size);
+    let mut input = S::parse(&input).unwrap_or_else(|e| panic!("day {} generated an input it can't parse: {}", S::DAY, e));
+    S::prepare(&mut input).unwrap_or_else(|e| panic!("day {} generated an input it can't solve: {}", S::DAY, e));
+    let parts = [
+        (1, S::reference_part1(&input).map(|reference| (S::part1(&input).to_string(), reference.to_string()))),
+        (2, S::reference_part2(&input).map(|reference| (S::part2(&input).to_string(), reference.to_string()))),
+    ];
+
+    let mut checked = 0;
+    for (part, answers) in parts {
+        if let Some((fast, reference)) = answers {
+            if fast != reference {
+                return Err(Divergence { day: S::DAY, part, seed, size: S::SIZE.map(|_| size), fast, reference });
+            }
+            checked += 1;
+        }
+    }
+    Ok(checked)
+}
+
+/// Checks a day on the inputs of `runs` seeds counting up from `seed`, stopping at the first divergence, and returns
+/// how many parts it checked; `None` if the day has no reference. Without a `size` each input gets one between the
+/// smallest and the real input's, so that the references keep up.
+pub fn check_day(day: usize, seed: u64, runs: u64, size: Option<usize>) -> Option<Result<usize, Divergence>> {
+    fn check_runs<S: Reference + Generate>(seed: u64, runs: u64, size: Option<usize>) -> Result<usize, Divergence> {
+        (seed..seed.saturating_add(runs))
+            .map(|seed| check::<S>(seed, size.unwrap_or_else(|| varying_size(seed, S::SIZE))))
+            .sum()
+    }
+
+    Some(match day {
+        6 => check_runs::<Day6>(seed, runs, size),
+        14 => check_runs::<Day14>(seed, runs, size),
+        22 => check_runs::<Day22>(seed, runs, size),
+        23 => check_runs::<Day23>(seed, runs, size),
+        _ => return None,
+    })
+}
+
+fn varying_size(seed: u64, size: Option<Size>) -> usize {
+    // a generator of its own, as neighbouring seeds would otherwise get neighbouring sizes
+    size.map_or(0, |size| size.min + Rng::new(!seed).below(size.default - size.min + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_solvers_agree_with_their_references() {
+        // day 23 searches part 2's deeper burrow twice per input, so it keeps to the shallowest
+        for (day, size) in [(6, None), (14, None), (22, None), (23, Some(1))] {
+            match check_day(day, 1, 5, size) {
+                Some(Ok(checked)) => assert!(checked > 0, "day {} checked nothing", day),
+                Some(Err(divergence)) => panic!("{}", divergence),
+                None => panic!("day {} has no reference", day),
+            }
+        }
+    }
+
+    #[test]
+    fn days_without_a_reference_are_skipped() {
+        assert_eq!(check_day(1, 1, 1, None), None);
+    }
+}
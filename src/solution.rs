@@ -0,0 +1,149 @@
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::*;
+use crate::error::ParseError;
+
+/// A day's puzzle: parse the input once, then solve each part from the parsed value.
+pub trait Solution {
+    const DAY: usize;
+
+    type Input<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    /// Works out, once, what both parts need of the parsed input and keeps it there; a day whose well-formed input
+    /// can still be unsolvable reports that here, like a parse error. Most days have nothing to prepare.
+    fn prepare(_input: &mut Self::Input<'_>) -> Result<(), ParseError> {
+        Ok(())
+    }
+    fn part1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(input: &Self::Input<'_>) -> Self::Part2;
+}
+
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub timings: Timings,
+}
+
+/// Wall-clock time spent in each step of [`solve`].
+#[derive(Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub prepare: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.prepare + self.part1 + self.part2
+    }
+}
+
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, ParseError> {
+    let start = Instant::now();
+    let mut input = S::parse(input).map_err(|e| e.on_day(S::DAY))?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    S::prepare(&mut input).map_err(|e| e.on_day(S::DAY))?;
+    let prepare = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = S::part1(&input).to_string();
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = S::part2(&input).to_string();
+    let part2_time = start.elapsed();
+
+    Ok(Answers {
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            prepare,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    })
+}
+
+/// One of the steps [`solve`] goes through, for measuring them separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Prepare,
+    Part1,
+    Part2,
+}
+
+/// Hands each step of solving `input` to `measure`, as a closure it may call any number of times and that returns
+/// how long the step took; preparing needs a fresh parse every time, which is left out.
+pub fn bench<S: Solution>(input: &str, mut measure: impl FnMut(Step, &mut dyn FnMut() -> Duration)) -> Result<(), ParseError> {
+    let mut parsed = S::parse(input).map_err(|e| e.on_day(S::DAY))?;
+    S::prepare(&mut parsed).map_err(|e| e.on_day(S::DAY))?;
+    measure(Step::Parse, &mut || timed(|| {
+        let _ = black_box(S::parse(input));
+    }));
+    measure(Step::Prepare, &mut || {
+        let mut fresh = S::parse(input).expect("the input parsed before");
+        timed(|| {
+            let _ = black_box(S::prepare(&mut fresh));
+        })
+    });
+    measure(Step::Part1, &mut || timed(|| {
+        black_box(S::part1(&parsed));
+    }));
+    measure(Step::Part2, &mut || timed(|| {
+        black_box(S::part2(&parsed));
+    }));
+    Ok(())
+}
+
+fn timed(step: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    step();
+    start.elapsed()
+}
+
+/// Something to do with a day's [`Solution`], for when the day is only known at runtime.
+pub trait SolutionVisitor {
+    type Output;
+
+    fn visit<S: Solution>(self) -> Self::Output;
+}
+
+pub fn for_day<V: SolutionVisitor>(day: usize, visitor: V) -> V::Output {
+    match day {
+        1 => visitor.visit::<day1_sonar_sweep::Day1>(),
+        2 => visitor.visit::<day2_dive::Day2>(),
+        3 => visitor.visit::<day3_binary_diagnostic::Day3>(),
+        4 => visitor.visit::<day4_bingo::Day4>(),
+        5 => visitor.visit::<day5_hydrothermal_lines::Day5>(),
+        6 => visitor.visit::<day6_lanternfish::Day6>(),
+        7 => visitor.visit::<day7_crabs::Day7>(),
+        8 => visitor.visit::<day8_seven_segment_search::Day8>(),
+        9 => visitor.visit::<day9_low_points::Day9>(),
+        10 => visitor.visit::<day10_syntax_scoring::Day10>(),
+        11 => visitor.visit::<day11_dumbo_octopus::Day11>(),
+        12 => visitor.visit::<day12_passage_pathing::Day12>(),
+        13 => visitor.visit::<day13_transparent_origami::Day13>(),
+        14 => visitor.visit::<day14_polymerization::Day14>(),
+        15 => visitor.visit::<day15_chiton::Day15>(),
+        16 => visitor.visit::<day16_packet_decoder::Day16>(),
+        17 => visitor.visit::<day17_trick_shot::Day17>(),
+        18 => visitor.visit::<day18_snailfish::Day18>(),
+        19 => visitor.visit::<day19_beacon_scanner::Day19>(),
+        20 => visitor.visit::<day20_trench_map::Day20>(),
+        21 => visitor.visit::<day21_dirac_dice::Day21>(),
+        22 => visitor.visit::<day22_reactor_reboot::Day22>(),
+        23 => visitor.visit::<day23_amphipod::Day23>(),
+        24 => visitor.visit::<day24_arithmetic_logic_unit::Day24>(),
+        25 => visitor.visit::<day25_sea_cucumber::Day25>(),
+        _ => panic!("day {} not found", day)
+    }
+}
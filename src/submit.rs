@@ -0,0 +1,61 @@
+use advent_of_code_2021::client::{Client, Verdict, BASE_URL};
+use crate::answers::{self, AnswersFile};
+use crate::input::InputSource;
+use crate::{exit_with_usage, fetch, read_and_solve, take_option, LAST_DAY};
+
+/// Sends an answer to the site: `submit day 1|2 [input] [--answer value]`.
+/// The answer is worked out from the input unless given. Answers that the site turned down before, or that its hints
+/// rule out, are not sent again, and every verdict is kept in `answers/day{N}.toml` for next time.
+pub(crate) fn run(mut args: Vec<String>) {
+    let given = take_option(&mut args, "--answer");
+    let mut args = args.into_iter();
+    let day = match args.next().map(|day| day.parse()) {
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+        _ => exit_with_usage(format!("submit needs a day between 1 and {}", LAST_DAY)),
+    };
+    let part: u8 = match args.next().as_deref() {
+        Some("1") => 1,
+        Some("2") => 2,
+        _ => exit_with_usage("submit needs a part, 1 or 2"),
+    };
+    let answer = match given {
+        Some(answer) => answer,
+        None => match read_and_solve(day, &InputSource::from_arg(args.next(), day)) {
+            Ok(answers) if part == 1 => answers.part1,
+            Ok(answers) => answers.part2,
+            Err(e) => fail(e),
+        },
+    };
+
+    let path = answers::default_path(day);
+    let mut file = AnswersFile::load(day).unwrap_or_else(|e| fail(format!("could not read {}: {}", path.display(), e)));
+    match file.answer(part) {
+        Ok(Some(known)) if known == answer => {
+            println!("day {} part {}: {} is already known to be right", day, part, answer);
+            return;
+        }
+        Ok(Some(known)) => fail(format!("not submitting {}: the answer is known to be {}", answer, known)),
+        Ok(None) => {}
+        Err(e) => fail(format!("could not read {}: {}", path.display(), e)),
+    }
+    match file.rejection(part, &answer) {
+        Ok(Some(reason)) => fail(format!("not submitting {}: {}", answer, reason)),
+        Ok(None) => {}
+        Err(e) => fail(format!("could not read {}: {}", path.display(), e)),
+    }
+
+    let verdict = Client::new(BASE_URL, fetch::session("submit")).submit(day, part, &answer)
+        .unwrap_or_else(|e| fail(format!("could not submit: {}", e)));
+    println!("day {} part {}: {}: {}", day, part, answer, verdict);
+    if let Err(e) = file.record(part, &answer, verdict).and_then(|()| file.save()) {
+        fail(format!("could not record the verdict in {}: {}", path.display(), e));
+    }
+    if verdict != Verdict::Correct {
+        std::process::exit(1);
+    }
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
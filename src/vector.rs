@@ -0,0 +1,306 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or offset on a plane; unlike [`crate::grid::Pos`] it may be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A point or offset in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Vec2 {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+impl Vec3 {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs()).max((self.z - other.z).abs())
+    }
+}
+
+/// Component-wise operations shared by [`Vec2`] and [`Vec3`], as needed by [`Bounds`].
+pub trait Vector: Copy {
+    /// The smaller of each pair of components.
+    fn min_each(self, other: Self) -> Self;
+    /// The larger of each pair of components.
+    fn max_each(self, other: Self) -> Self;
+    /// Whether every component is less than or equal to the matching one of `other`.
+    fn all_le(self, other: Self) -> bool;
+}
+
+impl Vector for Vec2 {
+    fn min_each(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    fn max_each(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    fn all_le(self, other: Self) -> bool {
+        self.x <= other.x && self.y <= other.y
+    }
+}
+
+impl Vector for Vec3 {
+    fn min_each(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    fn max_each(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    fn all_le(self, other: Self) -> bool {
+        self.x <= other.x && self.y <= other.y && self.z <= other.z
+    }
+}
+
+macro_rules! impl_ops {
+    ($vec:ident { $($field:ident),+ }) => {
+        impl Add for $vec {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $vec {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Neg for $vec {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i32> for $vec {
+            type Output = Self;
+
+            fn mul(self, rhs: i32) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl AddAssign for $vec {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl SubAssign for $vec {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+    };
+}
+
+impl_ops!(Vec2 { x, y });
+impl_ops!(Vec3 { x, y, z });
+
+/// The smallest box holding a set of points; both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds<V> {
+    pub min: V,
+    pub max: V,
+}
+
+impl<V: Vector> Bounds<V> {
+    /// A box between two opposite corners, given in any order.
+    pub fn new(a: V, b: V) -> Self {
+        Self {
+            min: a.min_each(b),
+            max: a.max_each(b),
+        }
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item=V>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |bounds, p| bounds.including(p)))
+    }
+
+    pub fn including(self, point: V) -> Self {
+        Self {
+            min: self.min.min_each(point),
+            max: self.max.max_each(point),
+        }
+    }
+
+    pub fn contains(&self, point: V) -> bool {
+        self.min.all_le(point) && point.all_le(self.max)
+    }
+}
+
+/// One of the 24 ways to turn a set of axes while keeping them axis-aligned and right-handed,
+/// as a rotation matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation([[i32; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Self = Self([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+    /// A quarter turn about the x axis, taking y to z.
+    pub const X: Self = Self([[1, 0, 0], [0, 0, -1], [0, 1, 0]]);
+    /// A quarter turn about the y axis, taking z to x.
+    pub const Y: Self = Self([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]);
+    /// A quarter turn about the z axis, taking x to y.
+    pub const Z: Self = Self([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+
+    /// This rotation applied `times` times in a row.
+    pub fn pow(self, times: u32) -> Self {
+        (0..times).fold(Self::IDENTITY, |rotation, _| rotation * self)
+    }
+
+    /// Every rotation once: each of the six directions x can face, with the four turns about it.
+    pub fn all() -> [Self; 24] {
+        let facings = [
+            Self::IDENTITY,
+            Self::Z,
+            Self::Z.pow(2),
+            Self::Z.pow(3),
+            Self::Y,
+            Self::Y.pow(3),
+        ];
+        let mut rotations = [Self::IDENTITY; 24];
+        for (i, facing) in facings.iter().enumerate() {
+            for turns in 0..4 {
+                rotations[i * 4 + turns] = *facing * Self::X.pow((4 - turns as u32) % 4);
+            }
+        }
+        rotations
+    }
+}
+
+/// Composes two rotations; `a * b` applies `b` first.
+impl Mul for Rotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut result = [[0; 3]; 3];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.0[i][k] * rhs.0[k][j]).sum();
+            }
+        }
+        Self(result)
+    }
+}
+
+impl Mul<Vec3> for Rotation {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let [a, b, c] = self.0;
+        Vec3 {
+            x: a[0] * v.x + a[1] * v.y + a[2] * v.z,
+            y: b[0] * v.x + b[1] * v.y + b[2] * v.z,
+            z: c[0] * v.x + c[1] * v.y + c[2] * v.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use proptest::prelude::*;
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Vec3::new(1, -2, 3);
+        let b = Vec3::new(-1, 2, 3);
+        assert_eq!(a.manhattan_distance(b), 6);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(a - b + b, a);
+        assert_eq!(-a * 2, Vec3::new(-2, 4, -6));
+    }
+
+    #[test]
+    fn bounds_hold_their_points() {
+        let bounds = Bounds::from_points([Vec2::new(3, -1), Vec2::new(-2, 4), Vec2::new(0, 0)]).unwrap();
+        assert_eq!(bounds, Bounds::new(Vec2::new(3, 4), Vec2::new(-2, -1)));
+        assert!(bounds.contains(Vec2::new(-2, 4)));
+        assert!(!bounds.contains(Vec2::new(4, 0)));
+    }
+
+    #[test]
+    fn rotations_are_distinct_and_keep_lengths() {
+        let v = Vec3::new(1, 2, 3);
+        let rotated: HashSet<Vec3> = Rotation::all().iter().map(|&r| r * v).collect();
+        assert_eq!(rotated.len(), 24);
+        assert!(rotated.iter().all(|r| r.manhattan_distance(Vec3::default()) == 6));
+        assert_eq!(Rotation::X * Vec3::new(0, 1, 0), Vec3::new(0, 0, 1));
+        assert_eq!(Rotation::Z.pow(4), Rotation::IDENTITY);
+    }
+
+    fn rotation() -> impl Strategy<Value=Rotation> {
+        (0..24usize).prop_map(|i| Rotation::all()[i])
+    }
+
+    fn vec3() -> impl Strategy<Value=Vec3> {
+        (-2000..=2000, -2000..=2000, -2000..=2000).prop_map(|(x, y, z)| Vec3::new(x, y, z))
+    }
+
+    proptest! {
+        #[test]
+        fn rotations_compose_into_rotations(a in rotation(), b in rotation()) {
+            prop_assert!(Rotation::all().contains(&(a * b)));
+        }
+
+        #[test]
+        fn every_rotation_can_be_undone(a in rotation()) {
+            prop_assert!(Rotation::all().iter().any(|&b| a * b == Rotation::IDENTITY && b * a == Rotation::IDENTITY));
+        }
+
+        #[test]
+        fn composed_rotations_apply_in_turn(a in rotation(), b in rotation(), v in vec3()) {
+            prop_assert_eq!((a * b) * v, a * (b * v));
+        }
+
+        #[test]
+        fn rotations_keep_offsets(a in rotation(), v in vec3(), w in vec3()) {
+            prop_assert_eq!(a * v - a * w, a * (v - w));
+            prop_assert_eq!((a * v).manhattan_distance(a * w), v.manhattan_distance(w));
+        }
+
+        #[test]
+        fn distinct_rotations_tell_points_apart(a in rotation(), b in rotation()) {
+            // a point with distinct nonzero coordinates ends up in 24 different places
+            prop_assert_eq!(a == b, a * Vec3::new(1, 2, 3) == b * Vec3::new(1, 2, 3));
+        }
+    }
+}
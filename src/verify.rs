@@ -0,0 +1,95 @@
+use rayon::prelude::*;
+use crate::all::{run_day, DayResult};
+use crate::answers::ExpectedAnswers;
+use crate::LAST_DAY;
+
+enum Check {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+pub(crate) fn run(parallel: bool) {
+    let days = 1..=LAST_DAY;
+    let results: Vec<DayResult> = if parallel {
+        days.into_par_iter().map(run_day).collect()
+    } else {
+        days.map(run_day).collect()
+    };
+
+    println!();
+    let mut tally = Tally::default();
+    for result in results {
+        let expected = match ExpectedAnswers::load(result.day) {
+            Ok(expected) => expected,
+            Err(e) => {
+                println!("day {:>2}: FAIL - could not read answers: {}", result.day, e);
+                tally.failed += 2;
+                continue;
+            }
+        };
+        match result.outcome {
+            Ok(answers) => {
+                let checks = [
+                    check(expected.part1, answers.part1),
+                    check(expected.part2, answers.part2),
+                ];
+                let parts: Vec<String> = checks.iter().enumerate()
+                    .map(|(i, c)| format!("part {} {}", i + 1, describe(c)))
+                    .collect();
+                println!("day {:>2}: {}", result.day, parts.join(", "));
+                for c in checks.iter() {
+                    tally.add(c);
+                }
+            }
+            Err(e) => {
+                println!("day {:>2}: FAIL - {}", result.day, e);
+                tally.failed += 2;
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} missing", tally.passed, tally.failed, tally.missing);
+    if tally.failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn check(expected: Option<String>, actual: String) -> Check {
+    match expected {
+        None => Check::Missing,
+        Some(expected) if expected == actual => Check::Pass,
+        Some(expected) => Check::Fail { expected, actual },
+    }
+}
+
+fn describe(check: &Check) -> String {
+    match check {
+        Check::Pass => "pass".to_string(),
+        Check::Missing => "missing".to_string(),
+        Check::Fail { expected, actual } => format!("FAIL (expected {}, got {})", inline(expected), inline(actual)),
+    }
+}
+
+// keeps multi-line answers on the day's line of the report
+fn inline(answer: &str) -> String {
+    format!("{:?}", answer)
+}
+
+impl Tally {
+    fn add(&mut self, check: &Check) {
+        match check {
+            Check::Pass => self.passed += 1,
+            Check::Fail { .. } => self.failed += 1,
+            Check::Missing => self.missing += 1,
+        }
+    }
+}